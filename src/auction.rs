@@ -1,23 +1,124 @@
-use rand::{SeedableRng, rngs::StdRng};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
-use crate::collateral::collateral_requirement;
-use crate::commitment::{Commitment, CommitmentScheme, NonMalleableShaCommitment, Opening};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{Clock, LogicalClock};
+use crate::collateral::{collateral_requirement, collateral_requirement_with_shills};
+use crate::commitment::{
+    AuditedNonMalleableCommitment, BidCodec, CommitError, Commitment, CommitmentScheme,
+    NonMalleableShaCommitment, Opening, SharedRangeProof, SizeReport,
+};
 use crate::distribution::ValueDistribution;
-use crate::protocol::Phase;
+use crate::protocol::{Phase, ProtocolError};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ParticipantId {
     Auctioneer,
     Real(usize),
     False(usize),
 }
 
+/// Which kind of party a [`ParticipantId`] names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParticipantKind {
+    Auctioneer,
+    Real,
+    False,
+}
+
 impl ParticipantId {
-    fn tie_rank(&self) -> u64 {
+    pub fn kind(&self) -> ParticipantKind {
+        match self {
+            ParticipantId::Auctioneer => ParticipantKind::Auctioneer,
+            ParticipantId::Real(_) => ParticipantKind::Real,
+            ParticipantId::False(_) => ParticipantKind::False,
+        }
+    }
+
+    pub fn is_auctioneer(&self) -> bool {
+        matches!(self, ParticipantId::Auctioneer)
+    }
+
+    pub fn is_real(&self) -> bool {
+        matches!(self, ParticipantId::Real(_))
+    }
+
+    pub fn is_false(&self) -> bool {
+        matches!(self, ParticipantId::False(_))
+    }
+
+    /// Position among participants of the same kind; `None` for the auctioneer.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            ParticipantId::Auctioneer => None,
+            ParticipantId::Real(i) | ParticipantId::False(i) => Some(*i),
+        }
+    }
+
+    /// Tie-break order: the auctioneer, then real buyers, then false bids, each by index.
+    /// Compared as a tuple, so no index can reach into another kind's range.
+    pub(crate) fn tie_rank(&self) -> (u8, usize) {
+        match self {
+            ParticipantId::Auctioneer => (0, 0),
+            ParticipantId::Real(i) => (1, *i),
+            ParticipantId::False(j) => (2, *j),
+        }
+    }
+}
+
+/// Stable text form: `auctioneer`, `real:<i>` or `false:<j>`. Parsed back by [`FromStr`].
+impl fmt::Display for ParticipantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParticipantId::Auctioneer => f.write_str("auctioneer"),
+            ParticipantId::Real(i) => write!(f, "real:{i}"),
+            ParticipantId::False(j) => write!(f, "false:{j}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseParticipantIdError {
+    UnknownKind(String),
+    InvalidIndex(String),
+}
+
+impl fmt::Display for ParseParticipantIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ParticipantId::Auctioneer => 0,
-            ParticipantId::Real(i) => 1 + (*i as u64),
-            ParticipantId::False(j) => 50_000 + (*j as u64),
+            ParseParticipantIdError::UnknownKind(kind) => {
+                write!(f, "unknown participant kind {kind:?}")
+            }
+            ParseParticipantIdError::InvalidIndex(index) => {
+                write!(f, "invalid participant index {index:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseParticipantIdError {}
+
+impl FromStr for ParticipantId {
+    type Err = ParseParticipantIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "auctioneer" {
+            return Ok(ParticipantId::Auctioneer);
+        }
+        let (kind, index) = s
+            .split_once(':')
+            .ok_or_else(|| ParseParticipantIdError::UnknownKind(s.to_string()))?;
+        let index = index
+            .parse()
+            .map_err(|_| ParseParticipantIdError::InvalidIndex(index.to_string()))?;
+        match kind {
+            "real" => Ok(ParticipantId::Real(index)),
+            "false" => Ok(ParticipantId::False(index)),
+            _ => Err(ParseParticipantIdError::UnknownKind(kind.to_string())),
         }
     }
 }
@@ -37,9 +138,10 @@ pub struct FalseBid {
     pub reveal: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AuctionOutcome {
     pub reserve: f64,
+    /// Collateral per commitment; the largest amount posted when it varies by bid.
     pub collateral: f64,
     pub winner: Option<ParticipantId>,
     pub winning_bid: f64,
@@ -47,13 +149,124 @@ pub struct AuctionOutcome {
     pub transferred_collateral: f64,
     pub forfeited_to_auctioneer: f64,
     pub auctioneer_penalty: f64,
+    /// How the top bid was compared against `reserve`.
+    #[serde(default)]
+    pub reserve_comparison: ReserveComparison,
+    #[serde(default)]
+    pub allocation_rule: AllocationRule,
+    #[serde(default)]
+    pub tie_break: TieBreak,
     pub valid_bids: Vec<(ParticipantId, f64)>,
+    pub sizes: SizeReport,
+    /// One entry per committed participant: real buyers, then false bids, each by index.
+    #[serde(default)]
+    pub settlements: Vec<Settlement>,
+}
+
+impl AuctionOutcome {
+    /// Collateral held across every committed participant between commit and settlement:
+    /// `collateral` times the number of committers under a flat [`CollateralPolicy`]. Unlike
+    /// `transferred_collateral` and `forfeited_to_auctioneer`, this counts revealers too.
+    pub fn total_collateral_locked(&self) -> f64 {
+        self.settlements.iter().map(|s| s.posted_collateral).sum()
+    }
+}
+
+/// What one committed participant gets back, pays and receives once the auction resolves.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Settlement {
+    pub participant: ParticipantId,
+    pub posted_collateral: f64,
+    /// The posted collateral after a successful reveal; nothing if withheld.
+    pub refund: f64,
+    /// The price, for the winner.
+    pub payment: f64,
+    /// Withheld collateral, for the highest valid bidder.
+    pub received: f64,
+}
+
+/// Whether a top bid exactly at the reserve wins.
+///
+/// The distinction matters for [`EqualRevenue`](crate::EqualRevenue): its virtual value is
+/// zero for every value at or above `scale`, so the reserve is `scale` and a strict comparison
+/// never sells to a bidder sitting exactly on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReserveComparison {
+    /// Sell only when the top bid strictly exceeds the reserve.
+    #[default]
+    Strict,
+    /// Also sell when the top bid equals the reserve.
+    Inclusive,
+}
+
+impl ReserveComparison {
+    fn clears(self, bid: f64, reserve: f64) -> bool {
+        match self {
+            ReserveComparison::Strict => bid > reserve,
+            ReserveComparison::Inclusive => bid >= reserve,
+        }
+    }
+}
+
+/// What the winner pays once the top bid clears the reserve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AllocationRule {
+    /// The larger of the reserve and the second-highest valid bid, as in the paper.
+    #[default]
+    SecondPrice,
+    /// The winner's own bid.
+    FirstPrice,
+}
+
+/// Which of several equal top bids wins.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// Real buyers before false bids, lower indices first.
+    #[default]
+    LowestId,
+    /// False bids before real buyers, higher indices first.
+    HighestId,
+}
+
+/// How much collateral each commitment posts.
+///
+/// Forfeiture follows what was actually posted: a withheld reveal loses its own amount, so under
+/// [`FractionOfBid`](CollateralPolicy::FractionOfBid) a high withheld bid forfeits more than a
+/// low one, and the pot passed to the top valid bidder is the sum of the individual amounts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CollateralPolicy {
+    /// The Theorem 21 amount from [`collateral_requirement`], the same for every commitment.
+    #[default]
+    Theorem21,
+    /// A fraction of the reserve, the same for every commitment.
+    FractionOfReserve(f64),
+    /// A fraction of each committed bid.
+    FractionOfBid(f64),
+}
+
+impl TieBreak {
+    /// Order between equal bids; `Less` means `a` wins the tie.
+    fn order(self, a: &ParticipantId, b: &ParticipantId) -> Ordering {
+        match self {
+            TieBreak::LowestId => a.tie_rank().cmp(&b.tie_rank()),
+            TieBreak::HighestId => b.tie_rank().cmp(&a.tie_rank()),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
-pub struct PublicBroadcastDRA<D: ValueDistribution> {
+pub struct PublicBroadcastDRA<D: ValueDistribution, C: Clock = LogicalClock> {
     distribution: D,
     alpha: f64,
+    reserve_override: Option<f64>,
+    reserve_comparison: ReserveComparison,
+    allocation_rule: AllocationRule,
+    tie_break: TieBreak,
+    deterministic: bool,
+    abstain_below: Option<f64>,
+    collateral_policy: CollateralPolicy,
+    collateral_override: Option<f64>,
+    clock: C,
 }
 
 impl<D: ValueDistribution> PublicBroadcastDRA<D> {
@@ -62,26 +275,268 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
         Self {
             distribution,
             alpha,
+            reserve_override: None,
+            reserve_comparison: ReserveComparison::default(),
+            allocation_rule: AllocationRule::default(),
+            tie_break: TieBreak::default(),
+            deterministic: false,
+            abstain_below: None,
+            collateral_policy: CollateralPolicy::default(),
+            collateral_override: None,
+            clock: LogicalClock::default(),
+        }
+    }
+
+    /// Auction whose reserve is the `p`-quantile of `distribution` rather than the Myerson
+    /// reserve, e.g. `p = 0.8` for a reserve at the 80th percentile of values.
+    pub fn with_quantile_reserve(distribution: D, alpha: f64, p: f64) -> Self {
+        let reserve = distribution.quantile(p);
+        Self {
+            reserve_override: Some(reserve),
+            ..Self::new(distribution, alpha)
+        }
+    }
+
+    /// Start configuring an auction beyond the `new(distribution, alpha)` defaults.
+    pub fn builder() -> DraBuilder<D> {
+        DraBuilder::default()
+    }
+}
+
+/// Builder for [`PublicBroadcastDRA`]; the distribution and alpha are required.
+#[derive(Clone, Debug)]
+pub struct DraBuilder<D: ValueDistribution> {
+    distribution: Option<D>,
+    alpha: Option<f64>,
+    reserve_override: Option<f64>,
+    reserve_comparison: ReserveComparison,
+    allocation_rule: AllocationRule,
+    tie_break: TieBreak,
+    deterministic: bool,
+    abstain_below: Option<f64>,
+    collateral_policy: CollateralPolicy,
+    collateral_override: Option<f64>,
+}
+
+impl<D: ValueDistribution> Default for DraBuilder<D> {
+    fn default() -> Self {
+        Self {
+            distribution: None,
+            alpha: None,
+            reserve_override: None,
+            reserve_comparison: ReserveComparison::default(),
+            allocation_rule: AllocationRule::default(),
+            tie_break: TieBreak::default(),
+            deterministic: false,
+            abstain_below: None,
+            collateral_policy: CollateralPolicy::default(),
+            collateral_override: None,
+        }
+    }
+}
+
+impl<D: ValueDistribution> DraBuilder<D> {
+    pub fn distribution(mut self, distribution: D) -> Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    /// Use `reserve` instead of the distribution's optimal reserve price.
+    pub fn reserve_override(mut self, reserve: f64) -> Self {
+        self.reserve_override = Some(reserve);
+        self
+    }
+
+    pub fn reserve_comparison(mut self, comparison: ReserveComparison) -> Self {
+        self.reserve_comparison = comparison;
+        self
+    }
+
+    pub fn allocation_rule(mut self, rule: AllocationRule) -> Self {
+        self.allocation_rule = rule;
+        self
+    }
+
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Refuse to run without an explicit seed instead of drawing one from OS entropy, so every
+    /// run can be reproduced.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Buyers valuing the item below `threshold` abstain: they post no commitment and no
+    /// collateral, and the collateral is sized for the buyers that do commit.
+    pub fn abstain_below(mut self, threshold: f64) -> Self {
+        self.abstain_below = Some(threshold);
+        self
+    }
+
+    pub fn collateral_policy(mut self, policy: CollateralPolicy) -> Self {
+        self.collateral_policy = policy;
+        self
+    }
+
+    /// Use `collateral` instead of the Theorem 21 amount, e.g. to study an under-collateralised
+    /// auction.
+    pub fn collateral_override(mut self, collateral: f64) -> Self {
+        self.collateral_override = Some(collateral);
+        self
+    }
+
+    pub fn build(self) -> Result<PublicBroadcastDRA<D>, ValidationError> {
+        let distribution = self
+            .distribution
+            .ok_or(ValidationError::MissingParameter("distribution"))?;
+        let alpha = self
+            .alpha
+            .ok_or(ValidationError::MissingParameter("alpha"))?;
+        if alpha.is_nan() || alpha <= 0.0 {
+            return Err(ValidationError::NonPositiveAlpha(alpha));
+        }
+        if let Some(reserve) = self.reserve_override
+            && !(reserve.is_finite() && reserve >= 0.0)
+        {
+            return Err(ValidationError::InvalidReserve(reserve));
+        }
+        if let CollateralPolicy::FractionOfReserve(frac) | CollateralPolicy::FractionOfBid(frac) =
+            self.collateral_policy
+            && !(frac.is_finite() && frac >= 0.0)
+        {
+            return Err(ValidationError::InvalidCollateralFraction(frac));
+        }
+        if let Some(collateral) = self.collateral_override
+            && !(collateral.is_finite() && collateral >= 0.0)
+        {
+            return Err(ValidationError::InvalidCollateral(collateral));
+        }
+        Ok(PublicBroadcastDRA {
+            distribution,
+            alpha,
+            reserve_override: self.reserve_override,
+            reserve_comparison: self.reserve_comparison,
+            allocation_rule: self.allocation_rule,
+            tie_break: self.tie_break,
+            deterministic: self.deterministic,
+            abstain_below: self.abstain_below,
+            collateral_policy: self.collateral_policy,
+            collateral_override: self.collateral_override,
+            clock: LogicalClock::default(),
+        })
+    }
+}
+
+impl<D: ValueDistribution, C: Clock> PublicBroadcastDRA<D, C> {
+    /// Stamp transcript events with `clock` instead of the default logical counter. Each run
+    /// starts from a fresh copy of it.
+    pub fn with_clock<K: Clock>(self, clock: K) -> PublicBroadcastDRA<D, K> {
+        PublicBroadcastDRA {
+            distribution: self.distribution,
+            alpha: self.alpha,
+            reserve_override: self.reserve_override,
+            reserve_comparison: self.reserve_comparison,
+            allocation_rule: self.allocation_rule,
+            tie_break: self.tie_break,
+            deterministic: self.deterministic,
+            abstain_below: self.abstain_below,
+            collateral_policy: self.collateral_policy,
+            collateral_override: self.collateral_override,
+            clock,
+        }
+    }
+
+    pub(crate) fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Choose how a top bid exactly at the reserve is treated (strict by default).
+    pub fn with_reserve_comparison(mut self, comparison: ReserveComparison) -> Self {
+        self.reserve_comparison = comparison;
+        self
+    }
+
+    /// Choose how much collateral each commitment posts (Theorem 21 by default).
+    pub fn with_collateral_policy(mut self, policy: CollateralPolicy) -> Self {
+        self.collateral_policy = policy;
+        self
+    }
+
+    /// Post `collateral` wherever the Theorem 21 amount would be posted; it must be finite
+    /// and non-negative.
+    pub fn with_collateral_override(mut self, collateral: f64) -> Result<Self, ValidationError> {
+        if !(collateral.is_finite() && collateral >= 0.0) {
+            return Err(ValidationError::InvalidCollateral(collateral));
         }
+        self.collateral_override = Some(collateral);
+        Ok(self)
     }
 
     pub fn validate_inputs(&self, buyers: usize) -> Result<(), ValidationError> {
         if buyers == 0 {
             return Err(ValidationError::InsufficientBuyers);
         }
-        if let Some(max_alpha) = self.distribution.strong_regular_alpha() {
-            if self.alpha > max_alpha + f64::EPSILON {
-                return Err(ValidationError::AlphaTooLarge {
-                    requested: self.alpha,
-                    supported: max_alpha,
-                });
-            }
+        if let Some(max_alpha) = self.distribution.strong_regular_alpha()
+            && self.alpha > max_alpha + f64::EPSILON
+        {
+            return Err(ValidationError::AlphaTooLarge {
+                requested: self.alpha,
+                supported: max_alpha,
+            });
+        }
+        Ok(())
+    }
+
+    /// In deterministic mode a run must be given its seed.
+    fn require_seed(&self, rng_seed: Option<u64>) -> Result<(), ValidationError> {
+        if self.deterministic && rng_seed.is_none() {
+            return Err(ValidationError::MissingSeed);
         }
         Ok(())
     }
 
+    /// Collateral per commitment: the override if one was set, else the Theorem 21 amount.
     pub fn collateral(&self, n_buyers: usize) -> f64 {
-        collateral_requirement(n_buyers, &self.distribution, self.alpha)
+        self.collateral_override
+            .unwrap_or_else(|| collateral_requirement(n_buyers, &self.distribution, self.alpha))
+    }
+
+    /// Reserve in force: the override if one was set, else the distribution's optimal reserve.
+    pub fn reserve(&self) -> f64 {
+        self.reserve_override
+            .unwrap_or_else(|| self.distribution.reserve_price())
+    }
+
+    /// Smallest bid with which a new buyer, indexed after the buyers bidding `others`, wins.
+    /// It must clear the reserve and beat the best of `others`, or match it when ties go to
+    /// the higher index. Under second price it then pays `max(reserve, max(others))`.
+    pub fn min_winning_bid(&self, others: &[f64]) -> f64 {
+        let reserve = self.reserve();
+        let clears_reserve = match self.reserve_comparison {
+            ReserveComparison::Strict => reserve.next_up(),
+            ReserveComparison::Inclusive => reserve,
+        };
+        let best_other = others.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let beats_others = match self.tie_break {
+            TieBreak::LowestId => best_other.next_up(),
+            TieBreak::HighestId => best_other,
+        };
+        clears_reserve.max(beats_others)
+    }
+
+    /// Per-bid collateral when `shills` false bids are committed alongside `n_buyers` real ones.
+    pub fn collateral_with_shills(&self, n_buyers: usize, shills: usize) -> f64 {
+        self.collateral_override.unwrap_or_else(|| {
+            collateral_requirement_with_shills(n_buyers, shills, &self.distribution, self.alpha)
+        })
     }
 
     /// Run the DRA with public broadcast. `valuations` are the honest buyers'
@@ -92,8 +547,78 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
         false_bids: &[FalseBid],
         rng_seed: Option<u64>,
     ) -> AuctionOutcome {
-        let mut scheme = NonMalleableShaCommitment::default();
-        self.run_with_false_bids_using_scheme(valuations, false_bids, rng_seed, &mut scheme)
+        self.try_run_with_false_bids(valuations, false_bids, rng_seed)
+            .expect("invalid inputs for auction")
+    }
+
+    /// Fallible variant of [`Self::run_with_false_bids`] that reports bad buyer counts,
+    /// unsupported alpha, and unencodable bids instead of panicking.
+    pub fn try_run_with_false_bids(
+        &self,
+        valuations: &[f64],
+        false_bids: &[FalseBid],
+        rng_seed: Option<u64>,
+    ) -> Result<AuctionOutcome, AuctionError> {
+        let mut scheme = NonMalleableShaCommitment;
+        self.try_run_with_false_bids_using_scheme(valuations, false_bids, rng_seed, &mut scheme)
+    }
+
+    /// Like [`Self::try_run_with_false_bids`], additionally rejecting bids the scheme cannot
+    /// commit to (e.g. beyond a Bulletproofs range).
+    pub fn try_run_with_false_bids_using_scheme<S: CommitmentScheme>(
+        &self,
+        valuations: &[f64],
+        false_bids: &[FalseBid],
+        rng_seed: Option<u64>,
+        scheme: &mut S,
+    ) -> Result<AuctionOutcome, AuctionError> {
+        self.require_seed(rng_seed)?;
+        self.check_run_inputs(valuations, false_bids, scheme)?;
+        Ok(self.run_with_false_bids_using_scheme(valuations, false_bids, rng_seed, scheme))
+    }
+
+    /// Fallible variant of [`Self::run_with_false_bids_using_scheme_with_transcript`] with
+    /// every buyer revealing.
+    pub fn try_run_with_false_bids_using_scheme_with_transcript<S: CommitmentScheme>(
+        &self,
+        valuations: &[f64],
+        false_bids: &[FalseBid],
+        rng_seed: Option<u64>,
+        scheme: &mut S,
+    ) -> Result<(AuctionOutcome, Transcript), AuctionError> {
+        self.require_seed(rng_seed)?;
+        self.check_run_inputs(valuations, false_bids, scheme)?;
+        Ok(self.run_with_false_bids_using_scheme_with_transcript(
+            valuations, false_bids, None, rng_seed, scheme,
+        ))
+    }
+
+    fn check_run_inputs<S: CommitmentScheme>(
+        &self,
+        valuations: &[f64],
+        false_bids: &[FalseBid],
+        scheme: &S,
+    ) -> Result<(), AuctionError> {
+        self.validate_inputs(valuations.len())?;
+        let codec = BidCodec::default();
+        let bids = valuations
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (ParticipantId::Real(i), *v))
+            .chain(
+                false_bids
+                    .iter()
+                    .enumerate()
+                    .map(|(j, fb)| (ParticipantId::False(j), fb.bid)),
+            );
+        for (participant, bid) in bids {
+            scheme.check_bid(bid, &codec).map_err(|reason| AuctionError::InvalidBid {
+                participant,
+                bid,
+                reason,
+            })?;
+        }
+        Ok(())
     }
 
     pub fn run_with_false_bids_with_transcript(
@@ -102,7 +627,7 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
         false_bids: &[FalseBid],
         rng_seed: Option<u64>,
     ) -> (AuctionOutcome, Transcript) {
-        let mut scheme = NonMalleableShaCommitment::default();
+        let mut scheme = NonMalleableShaCommitment;
         self.run_with_false_bids_using_scheme_with_transcript(
             valuations,
             false_bids,
@@ -132,14 +657,51 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
         real_reveals: Option<&[bool]>,
         rng_seed: Option<u64>,
         scheme: &mut S,
+    ) -> (AuctionOutcome, Transcript) {
+        self.run_with_injected_commitments(
+            valuations,
+            false_bids,
+            real_reveals,
+            &[],
+            rng_seed,
+            scheme,
+        )
+    }
+
+    /// Like [`Self::run_with_false_bids_using_scheme_with_transcript`], but each participant
+    /// listed in `injected` commits to the given pair instead of a freshly generated one. The
+    /// pair still goes through `scheme.verify` at reveal time, so a malformed opening forfeits
+    /// its collateral just as a withheld reveal does.
+    pub fn run_with_injected_commitments<S: CommitmentScheme>(
+        &self,
+        valuations: &[f64],
+        false_bids: &[FalseBid],
+        real_reveals: Option<&[bool]>,
+        injected: &[(ParticipantId, Commitment, Opening)],
+        rng_seed: Option<u64>,
+        scheme: &mut S,
     ) -> (AuctionOutcome, Transcript) {
         let n = valuations.len();
         self.validate_inputs(n).expect("invalid inputs for auction");
-        let collateral = self.collateral(n);
-        let reserve = self.distribution.reserve_price();
+        self.require_seed(rng_seed)
+            .expect("deterministic auction run without a seed");
+        let bidders: Vec<usize> = (0..n)
+            .filter(|&i| self.abstain_below.is_none_or(|t| valuations[i] >= t))
+            .collect();
+        // False bids still post collateral when every buyer abstains; size it for one buyer.
+        let committing = bidders.len().max(1);
+        let reserve = self.reserve();
+        let posted_for = |bid: f64| match self.collateral_policy {
+            CollateralPolicy::Theorem21 if false_bids.len() > 1 => {
+                self.collateral_with_shills(committing, false_bids.len())
+            }
+            CollateralPolicy::Theorem21 => self.collateral(committing),
+            CollateralPolicy::FractionOfReserve(frac) => frac * reserve,
+            CollateralPolicy::FractionOfBid(frac) => frac * bid,
+        };
         let mut rng = rng_seed
             .map(StdRng::seed_from_u64)
-            .unwrap_or_else(|| StdRng::from_entropy());
+            .unwrap_or_else(StdRng::from_entropy);
 
         // Commitment phase.
         let mut commitments: Vec<CommitmentRecord> = Vec::new();
@@ -149,15 +711,29 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
             broadcasts: Vec::new(),
             timings: PhaseTimings::default(),
             outcome: None,
+            shared_proofs: Vec::new(),
+        };
+        let mut clock = self.clock.clone();
+        let bids: Vec<f64> = bidders
+            .iter()
+            .map(|&i| valuations[i])
+            .chain(false_bids.iter().map(|fb| fb.bid))
+            .collect();
+        let mut committed = scheme.commit_batch(&bids, &mut rng).into_iter();
+        let mut next_pair = |id: &ParticipantId| {
+            let generated = committed.next().expect("one commitment per bid");
+            injected
+                .iter()
+                .find(|(target, _, _)| target == id)
+                .map_or(generated, |(_, c, o)| (c.clone(), o.clone()))
         };
-        let mut clock: u64 = 0;
-        for (i, &v) in valuations.iter().enumerate() {
-            let (commitment, opening) = scheme.commit(v, &mut rng);
+        for &i in &bidders {
+            let (commitment, opening) = next_pair(&ParticipantId::Real(i));
             commitments.push(CommitmentRecord {
                 id: ParticipantId::Real(i),
                 commitment,
                 opening,
-                posted_collateral: collateral,
+                posted_collateral: posted_for(valuations[i]),
                 will_reveal: real_reveals
                     .map(|r| r.get(i).copied().unwrap_or(true))
                     .unwrap_or(true),
@@ -165,37 +741,37 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
             transcript.commitments.push(CommitmentEvent {
                 participant: ParticipantId::Real(i),
                 commitment: commitments.last().unwrap().commitment.clone(),
-                timestamp: clock,
+                timestamp: clock.now(),
             });
             transcript.broadcasts.push(BroadcastEvent {
-                timestamp: clock,
+                timestamp: clock.now(),
                 sender: ParticipantId::Real(i),
                 message: BroadcastMessage::CommitmentPublished,
             });
-            clock += 1;
+            clock.tick();
         }
         for (j, fb) in false_bids.iter().enumerate() {
-            let (commitment, opening) = scheme.commit(fb.bid, &mut rng);
+            let (commitment, opening) = next_pair(&ParticipantId::False(j));
             commitments.push(CommitmentRecord {
                 id: ParticipantId::False(j),
                 commitment,
                 opening,
-                posted_collateral: collateral,
+                posted_collateral: posted_for(fb.bid),
                 will_reveal: fb.reveal,
             });
             transcript.commitments.push(CommitmentEvent {
                 participant: ParticipantId::False(j),
                 commitment: commitments.last().unwrap().commitment.clone(),
-                timestamp: clock,
+                timestamp: clock.now(),
             });
             transcript.broadcasts.push(BroadcastEvent {
-                timestamp: clock,
+                timestamp: clock.now(),
                 sender: ParticipantId::False(j),
                 message: BroadcastMessage::CommitmentPublished,
             });
-            clock += 1;
+            clock.tick();
         }
-        let commit_deadline = clock;
+        let commit_deadline = clock.now();
         transcript.broadcasts.push(BroadcastEvent {
             timestamp: commit_deadline,
             sender: ParticipantId::Auctioneer,
@@ -204,22 +780,35 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
                 reason: PhaseTransitionReason::Manual,
             },
         });
-        clock = commit_deadline.saturating_add(1);
+        clock.tick();
 
-        // Revelation phase: only those who reveal enter the valid set.
+        // Revelation phase: only those who reveal enter the valid set. Every commitment is in,
+        // so openings are brought up to date against the final commitment state first.
+        for c in commitments.iter_mut() {
+            scheme.refresh_opening(&mut c.opening);
+        }
         let mut valid_bids: Vec<(ParticipantId, f64)> = Vec::new();
         let mut invalid_collateral = 0.0;
+        let mut sizes = SizeReport {
+            commitment_bytes_total: commitments.len() * scheme.commitment_size(),
+            ..SizeReport::default()
+        };
+        let mut posted = Vec::with_capacity(commitments.len());
+        let mut timed_out = Vec::new();
         for c in commitments.iter() {
-            if c.will_reveal && scheme.verify(&c.commitment, &c.opening) {
+            let revealed = c.will_reveal && scheme.verify(&c.commitment, &c.opening);
+            posted.push((c.id.clone(), c.posted_collateral, revealed));
+            if revealed {
                 valid_bids.push((c.id.clone(), c.opening.bid));
+                sizes.opening_bytes_total += scheme.opening_size(&c.opening);
                 transcript.reveals.push(RevealEvent {
                     participant: c.id.clone(),
                     revealed: true,
                     opening: Some(c.opening.clone()),
-                    timestamp: clock,
+                    timestamp: clock.now(),
                 });
                 transcript.broadcasts.push(BroadcastEvent {
-                    timestamp: clock,
+                    timestamp: clock.now(),
                     sender: c.id.clone(),
                     message: BroadcastMessage::RevealPublished { success: true },
                 });
@@ -229,20 +818,35 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
                     participant: c.id.clone(),
                     revealed: false,
                     opening: None,
-                    timestamp: clock,
-                });
-                transcript.broadcasts.push(BroadcastEvent {
-                    timestamp: clock,
-                    sender: ParticipantId::Auctioneer,
-                    message: BroadcastMessage::Timeout {
-                        phase: Phase::Reveal,
-                        target: c.id.clone(),
-                    },
+                    timestamp: clock.now(),
                 });
+                timed_out.push(c.id.clone());
             }
-            clock += 1;
+            clock.tick();
+        }
+        let revealed: Vec<&Opening> = transcript
+            .reveals
+            .iter()
+            .filter_map(|r| r.opening.as_ref())
+            .collect();
+        transcript.shared_proofs = scheme.shared_proofs(&revealed);
+        sizes.shared_proof_bytes_total = transcript
+            .shared_proofs
+            .iter()
+            .map(SharedRangeProof::wire_size)
+            .sum();
+        let reveal_deadline = clock.now();
+        // A missing reveal is only a timeout once the deadline has passed.
+        for target in timed_out {
+            transcript.broadcasts.push(BroadcastEvent {
+                timestamp: reveal_deadline,
+                sender: ParticipantId::Auctioneer,
+                message: BroadcastMessage::Timeout {
+                    phase: Phase::Reveal,
+                    target,
+                },
+            });
         }
-        let reveal_deadline = clock;
         transcript.broadcasts.push(BroadcastEvent {
             timestamp: reveal_deadline,
             sender: ParticipantId::Auctioneer,
@@ -257,40 +861,32 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
         };
 
         // Resolution phase.
-        let mut highest: Option<(ParticipantId, f64)> = None;
-        let mut second: Option<f64> = None;
-        for (id, bid) in valid_bids.iter() {
-            match highest {
-                None => highest = Some((id.clone(), *bid)),
-                Some((ref hid, hbid)) => {
-                    if *bid > hbid || (*bid == hbid && id.tie_rank() < hid.tie_rank()) {
-                        second = Some(hbid);
-                        highest = Some((id.clone(), *bid));
-                    } else if *bid == hbid {
-                        if second.map(|s| *bid > s).unwrap_or(true) {
-                            second = Some(*bid);
-                        }
-                    } else if second.map(|s| *bid > s).unwrap_or(true) && *bid < hbid {
-                        second = Some(*bid);
-                    }
-                }
-            }
-        }
-
-        let (winner, winning_bid, payment, transferred_collateral, forfeited_to_auctioneer) =
-            match highest {
-                None => (None, 0.0, 0.0, 0.0, invalid_collateral),
-                Some((id, bid)) => {
-                    if bid > reserve {
-                        let second_bid = second.unwrap_or(0.0);
-                        let pay = reserve.max(second_bid);
-                        (Some(id), bid, pay, invalid_collateral, 0.0)
-                    } else {
-                        (None, bid, 0.0, invalid_collateral, 0.0)
-                    }
-                }
-            };
+        let (winner, winning_bid, payment) = resolve_winner(
+            &valid_bids,
+            reserve,
+            self.reserve_comparison,
+            self.allocation_rule,
+            self.tie_break,
+        );
+        // Withheld collateral goes to the highest valid bidder, or to the auctioneer if none.
+        let (transferred_collateral, forfeited_to_auctioneer) = if valid_bids.is_empty() {
+            (0.0, invalid_collateral)
+        } else {
+            (invalid_collateral, 0.0)
+        };
 
+        let settlements = settle(
+            &posted,
+            &valid_bids,
+            winner.as_ref(),
+            payment,
+            self.tie_break,
+        );
+        // The flat amount, or the largest one posted when amounts vary by bid.
+        let collateral = commitments
+            .iter()
+            .map(|c| c.posted_collateral)
+            .fold(posted_for(0.0), f64::max);
         let outcome = AuctionOutcome {
             reserve,
             collateral,
@@ -300,16 +896,247 @@ impl<D: ValueDistribution> PublicBroadcastDRA<D> {
             transferred_collateral,
             forfeited_to_auctioneer,
             auctioneer_penalty: 0.0,
+            reserve_comparison: self.reserve_comparison,
+            allocation_rule: self.allocation_rule,
+            tie_break: self.tie_break,
+            settlements,
             valid_bids,
+            sizes,
         };
         transcript.outcome = Some(outcome.clone());
         (outcome, transcript)
     }
 }
 
+/// Resolution over the revealed bids: the highest bid wins if it clears the reserve and pays
+/// according to `rule`; `tie_break` orders equal bids. Returns the winner, the top bid (even
+/// if unsold) and the payment.
+fn resolve_winner(
+    valid_bids: &[(ParticipantId, f64)],
+    reserve: f64,
+    comparison: ReserveComparison,
+    rule: AllocationRule,
+    tie_break: TieBreak,
+) -> (Option<ParticipantId>, f64, f64) {
+    // Rank up front so the outcome cannot depend on the order bids were collected in.
+    let mut ranked: Vec<&(ParticipantId, f64)> = valid_bids.iter().collect();
+    ranked.sort_by(|(a, a_bid), (b, b_bid)| {
+        b_bid.total_cmp(a_bid).then_with(|| tie_break.order(a, b))
+    });
+    let second = ranked.get(1).map(|(_, bid)| *bid);
+    match ranked.first() {
+        None => (None, 0.0, 0.0),
+        Some((id, bid)) if comparison.clears(*bid, reserve) => {
+            let payment = match rule {
+                AllocationRule::SecondPrice => reserve.max(second.unwrap_or(0.0)),
+                AllocationRule::FirstPrice => *bid,
+            };
+            (Some(id.clone()), *bid, payment)
+        }
+        Some((_, bid)) => (None, *bid, 0.0),
+    }
+}
+
+/// Settle each `(participant, posted collateral, revealed)`: revealers are refunded, the
+/// winner pays `payment`, and the withheld collateral goes to the highest valid bidder.
+pub(crate) fn settle(
+    posted: &[(ParticipantId, f64, bool)],
+    valid_bids: &[(ParticipantId, f64)],
+    winner: Option<&ParticipantId>,
+    payment: f64,
+    tie_break: TieBreak,
+) -> Vec<Settlement> {
+    let withheld: f64 = posted
+        .iter()
+        .filter(|(_, _, revealed)| !revealed)
+        .map(|(_, amount, _)| amount)
+        .sum();
+    // The top bidder collects withheld collateral even when it misses the reserve.
+    let (top, _, _) = resolve_winner(
+        valid_bids,
+        f64::NEG_INFINITY,
+        ReserveComparison::Inclusive,
+        AllocationRule::SecondPrice,
+        tie_break,
+    );
+    posted
+        .iter()
+        .map(|(participant, amount, revealed)| Settlement {
+            participant: participant.clone(),
+            posted_collateral: *amount,
+            refund: if *revealed { *amount } else { 0.0 },
+            payment: if winner == Some(participant) {
+                payment
+            } else {
+                0.0
+            },
+            received: if top.as_ref() == Some(participant) {
+                withheld
+            } else {
+                0.0
+            },
+        })
+        .collect()
+}
+
+/// Collateral `participant` posted according to the recorded settlements, falling back to the
+/// flat per-commitment amount for outcomes recorded without them.
+fn recorded_collateral(outcome: Option<&AuctionOutcome>, participant: &ParticipantId) -> f64 {
+    outcome.map_or(0.0, |o| {
+        o.settlements
+            .iter()
+            .find(|s| &s.participant == participant)
+            .map_or(o.collateral, |s| s.posted_collateral)
+    })
+}
+
+/// Re-run resolution from a transcript's reveals: successful openings form the valid set and
+/// every failed reveal forfeits the collateral recorded in `transcript.outcome`.
+pub fn recompute_outcome(transcript: &Transcript, reserve: f64) -> AuctionOutcome {
+    let recorded = transcript.outcome.as_ref();
+    let collateral = recorded.map_or(0.0, |o| o.collateral);
+    let reserve_comparison =
+        recorded.map_or_else(ReserveComparison::default, |o| o.reserve_comparison);
+    let allocation_rule = recorded.map_or_else(AllocationRule::default, |o| o.allocation_rule);
+    let tie_break = recorded.map_or_else(TieBreak::default, |o| o.tie_break);
+    let valid_bids: Vec<(ParticipantId, f64)> = transcript
+        .reveals
+        .iter()
+        .filter(|rev| rev.revealed)
+        .filter_map(|rev| {
+            rev.opening
+                .as_ref()
+                .map(|o| (rev.participant.clone(), o.bid))
+        })
+        .collect();
+    let posted: Vec<(ParticipantId, f64, bool)> = transcript
+        .reveals
+        .iter()
+        .map(|rev| {
+            let amount = recorded_collateral(recorded, &rev.participant);
+            (rev.participant.clone(), amount, rev.revealed)
+        })
+        .collect();
+    let invalid_collateral: f64 = posted
+        .iter()
+        .filter(|(_, _, revealed)| !revealed)
+        .map(|(_, amount, _)| amount)
+        .sum();
+    let (winner, winning_bid, payment) = resolve_winner(
+        &valid_bids,
+        reserve,
+        reserve_comparison,
+        allocation_rule,
+        tie_break,
+    );
+    let (transferred_collateral, forfeited_to_auctioneer) = if valid_bids.is_empty() {
+        (0.0, invalid_collateral)
+    } else {
+        (invalid_collateral, 0.0)
+    };
+    let settlements = settle(&posted, &valid_bids, winner.as_ref(), payment, tie_break);
+    AuctionOutcome {
+        reserve,
+        collateral,
+        winner,
+        winning_bid,
+        payment,
+        transferred_collateral,
+        forfeited_to_auctioneer,
+        auctioneer_penalty: recorded.map_or(0.0, |o| o.auctioneer_penalty),
+        reserve_comparison,
+        allocation_rule,
+        tie_break,
+        settlements,
+        valid_bids,
+        sizes: recorded.map(|o| o.sizes.clone()).unwrap_or_default(),
+    }
+}
+
+/// Name of the first settlement field where `recomputed` disagrees with `recorded`, if any.
+fn outcome_mismatch(
+    recorded: &AuctionOutcome,
+    recomputed: &AuctionOutcome,
+) -> Option<&'static str> {
+    let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0);
+    if recorded.winner != recomputed.winner {
+        Some("winner")
+    } else if !close(recorded.winning_bid, recomputed.winning_bid) {
+        Some("winning_bid")
+    } else if !close(recorded.payment, recomputed.payment) {
+        Some("payment")
+    } else if !close(
+        recorded.transferred_collateral,
+        recomputed.transferred_collateral,
+    ) {
+        Some("transferred_collateral")
+    } else if !close(
+        recorded.forfeited_to_auctioneer,
+        recomputed.forfeited_to_auctioneer,
+    ) {
+        Some("forfeited_to_auctioneer")
+    } else {
+        None
+    }
+}
+
+/// Where two recorded runs diverge. Each field pairs the first run's value with the second's
+/// and is empty when they agree; timestamps are compared event by event, in transcript order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunDiff {
+    pub reserve: Option<(f64, f64)>,
+    pub collateral: Option<(f64, f64)>,
+    pub winner: Option<(Option<ParticipantId>, Option<ParticipantId>)>,
+    pub payment: Option<(f64, f64)>,
+    /// `(position, first, second)` for each commitment stamped differently or missing from one run.
+    pub commitment_timestamps: Vec<(usize, Option<u64>, Option<u64>)>,
+    pub reveal_timestamps: Vec<(usize, Option<u64>, Option<u64>)>,
+    pub broadcast_timestamps: Vec<(usize, Option<u64>, Option<u64>)>,
+    pub broadcast_count: Option<(usize, usize)>,
+}
+
+impl RunDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == RunDiff::default()
+    }
+}
+
+/// Compare two `(outcome, transcript)` snapshots field by field; see [`RunDiff`].
+pub fn diff_runs(a: &(AuctionOutcome, Transcript), b: &(AuctionOutcome, Transcript)) -> RunDiff {
+    fn differ<T: PartialEq + Clone>(x: &T, y: &T) -> Option<(T, T)> {
+        (x != y).then(|| (x.clone(), y.clone()))
+    }
+    fn stamps(first: &[u64], second: &[u64]) -> Vec<(usize, Option<u64>, Option<u64>)> {
+        (0..first.len().max(second.len()))
+            .filter_map(|k| {
+                let (x, y) = (first.get(k).copied(), second.get(k).copied());
+                (x != y).then_some((k, x, y))
+            })
+            .collect()
+    }
+    let ((oa, ta), (ob, tb)) = (a, b);
+    let commit_stamps = |t: &Transcript| t.commitments.iter().map(|e| e.timestamp).collect();
+    let reveal_stamps = |t: &Transcript| t.reveals.iter().map(|e| e.timestamp).collect();
+    let broadcast_stamps = |t: &Transcript| t.broadcasts.iter().map(|e| e.timestamp).collect();
+    let pair = |f: fn(&Transcript) -> Vec<u64>| stamps(&f(ta), &f(tb));
+    RunDiff {
+        reserve: differ(&oa.reserve, &ob.reserve),
+        collateral: differ(&oa.collateral, &ob.collateral),
+        winner: differ(&oa.winner, &ob.winner),
+        payment: differ(&oa.payment, &ob.payment),
+        commitment_timestamps: pair(commit_stamps),
+        reveal_timestamps: pair(reveal_stamps),
+        broadcast_timestamps: pair(broadcast_stamps),
+        broadcast_count: differ(&ta.broadcasts.len(), &tb.broadcasts.len()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commitment::{
+        BID_BYTES, BidEncoding, BidEncodingError, BulletproofsCommitment, SALT_BYTES,
+    };
     use crate::distribution::{Exponential, Uniform, ValueDistribution};
 
     #[test]
@@ -342,6 +1169,20 @@ mod tests {
         assert!(outcome.forfeited_to_auctioneer > 0.0 || outcome.transferred_collateral > 0.0);
     }
 
+    #[test]
+    fn several_false_bids_raise_the_posted_collateral() {
+        let dra = PublicBroadcastDRA::new(Exponential::new(1.0), 0.5);
+        let shill = FalseBid {
+            bid: 3.0,
+            reveal: false,
+        };
+        let single = dra.run_with_false_bids(&[2.0, 1.0], std::slice::from_ref(&shill), Some(4));
+        assert_eq!(single.collateral, dra.collateral(2));
+        let pair = dra.run_with_false_bids(&[2.0, 1.0], &[shill.clone(), shill], Some(4));
+        assert_eq!(pair.collateral, dra.collateral_with_shills(2, 2));
+        assert!(pair.collateral > single.collateral);
+    }
+
     #[test]
     fn tie_breaks_lexicographically() {
         let dist = Uniform::new(0.0, 20.0);
@@ -373,7 +1214,7 @@ mod tests {
             reveal: false,
         };
         let outcome_sale =
-            dra.run_with_false_bids(&[dist.reserve_price() + 5.0], &[false_bid.clone()], Some(7));
+            dra.run_with_false_bids(&[dist.reserve_price() + 5.0], std::slice::from_ref(&false_bid), Some(7));
         assert!(outcome_sale.winner.is_some());
         assert!((outcome_sale.transferred_collateral - dra.collateral(1)).abs() < 1e-9);
         assert_eq!(outcome_sale.forfeited_to_auctioneer, 0.0);
@@ -385,6 +1226,21 @@ mod tests {
         assert_eq!(outcome_nosale.forfeited_to_auctioneer, 0.0);
     }
 
+    #[test]
+    fn equal_revenue_bid_at_scale_sells_only_when_inclusive() {
+        use crate::distribution::EqualRevenue;
+        let dist = EqualRevenue::new(1.0);
+        let at_reserve = [dist.reserve_price()];
+        let strict = PublicBroadcastDRA::new(dist.clone(), 0.5);
+        let outcome = strict.run_with_false_bids(&at_reserve, &[], Some(1));
+        assert!(outcome.winner.is_none());
+
+        let inclusive = strict.with_reserve_comparison(ReserveComparison::Inclusive);
+        let outcome = inclusive.run_with_false_bids(&at_reserve, &[], Some(1));
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+        assert_eq!(outcome.payment, dist.reserve_price());
+    }
+
     #[test]
     fn pedersen_backend_matches_sha_outcome() {
         use crate::commitment::NonMalleableShaCommitment;
@@ -405,21 +1261,844 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn validate_inputs_panic_on_zero_buyers() {
-        let dist = Uniform::new(0.0, 10.0);
+    fn audit_rejects_participant_committing_twice() {
+        let dist = Uniform::new(0.0, 20.0);
         let dra = PublicBroadcastDRA::new(dist, 1.0);
-        let _ = dra.run_with_false_bids(&[], &[], None);
+        let (_, mut transcript) =
+            dra.run_with_false_bids_with_transcript(&[12.0, 7.0], &[], Some(4));
+        let mut second = transcript.commitments[1].clone();
+        second.participant = ParticipantId::Real(0);
+        transcript.commitments[1] = second;
+        let mut scheme = NonMalleableShaCommitment;
+        assert!(matches!(
+            audit_transcript(&transcript, &mut scheme),
+            Err(AuditError::DuplicateCommitment(ParticipantId::Real(0)))
+        ));
     }
-}
-#[derive(Clone, Debug)]
-pub struct CommitmentEvent {
-    pub participant: ParticipantId,
-    pub commitment: Commitment,
-    pub timestamp: u64,
-}
 
-#[derive(Clone, Debug)]
+    #[test]
+    fn audit_rejects_cloned_commitment_bytes() {
+        let dist = Uniform::new(0.0, 20.0);
+        let dra = PublicBroadcastDRA::new(dist, 1.0);
+        let (_, mut transcript) =
+            dra.run_with_false_bids_with_transcript(&[12.0, 7.0], &[], Some(4));
+        transcript.commitments[1].commitment = transcript.commitments[0].commitment.clone();
+        let mut scheme = NonMalleableShaCommitment;
+        assert!(matches!(
+            audit_transcript(&transcript, &mut scheme),
+            Err(AuditError::ClonedCommitment {
+                original: ParticipantId::Real(0),
+                clone: ParticipantId::Real(1),
+            })
+        ));
+    }
+
+    #[test]
+    fn audit_catches_tampered_payment() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        let withheld = [FalseBid {
+            bid: 9.0,
+            reveal: false,
+        }];
+        let (outcome, transcript) =
+            dra.run_with_false_bids_with_transcript(&[12.0, 7.0], &withheld, Some(4));
+        let recomputed = recompute_outcome(&transcript, outcome.reserve);
+        assert_eq!(recomputed.winner, outcome.winner);
+        assert!((recomputed.payment - outcome.payment).abs() < 1e-9);
+        assert!((recomputed.transferred_collateral - dra.collateral(1)).abs() < 1e-9);
+
+        let (_, mut transcript) =
+            dra.run_with_false_bids_with_transcript(&[12.0, 7.0], &[], Some(4));
+        let mut scheme = NonMalleableShaCommitment;
+        assert!(audit_transcript(&transcript, &mut scheme).is_ok());
+        transcript.outcome.as_mut().unwrap().payment -= 1.0;
+        assert!(matches!(
+            audit_transcript(&transcript, &mut scheme),
+            Err(AuditError::OutcomeMismatch("payment"))
+        ));
+    }
+
+    #[test]
+    fn system_clock_commit_timestamps_increase() {
+        use crate::clock::SystemClock;
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0)
+            .with_clock(SystemClock::default());
+        let (_, transcript) =
+            dra.run_with_false_bids_with_transcript(&[12.0, 7.0, 3.0, 9.0], &[], Some(4));
+        let stamps: Vec<u64> = transcript.commitments.iter().map(|c| c.timestamp).collect();
+        // Wall time in ms, well after the logical counter's range.
+        assert!(stamps[0] > 1_600_000_000_000);
+        assert!(stamps.windows(2).all(|w| w[0] < w[1]));
+        assert!(transcript.timings.commit_deadline >= *stamps.last().unwrap());
+        assert!(audit_transcript(&transcript, &mut NonMalleableShaCommitment).is_ok());
+    }
+
+    #[test]
+    fn builder_runs_a_first_price_auction_without_reserve() {
+        let dra = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 20.0))
+            .alpha(1.0)
+            .reserve_override(0.0)
+            .allocation_rule(AllocationRule::FirstPrice)
+            .tie_break(TieBreak::HighestId)
+            .build()
+            .expect("complete builder");
+        let outcome = dra.run_with_false_bids(&[3.0, 1.0], &[], Some(2));
+        assert_eq!(outcome.reserve, 0.0);
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+        assert_eq!(outcome.payment, 3.0);
+        let tied = dra.run_with_false_bids(&[3.0, 3.0], &[], Some(2));
+        assert_eq!(tied.winner, Some(ParticipantId::Real(1)));
+        assert!(matches!(
+            PublicBroadcastDRA::<Uniform>::builder().alpha(1.0).build(),
+            Err(ValidationError::MissingParameter("distribution"))
+        ));
+    }
+
+    #[test]
+    fn min_winning_bid_just_tops_the_best_other_bid() {
+        let dra = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 20.0))
+            .alpha(1.0)
+            .reserve_override(5.0)
+            .build()
+            .expect("complete builder");
+        let threshold = dra.min_winning_bid(&[10.0]);
+        assert!(threshold > 10.0 && threshold < 10.0 + 1e-9);
+        let wins = dra.run_with_false_bids(&[10.0, threshold], &[], Some(1));
+        assert_eq!(wins.winner, Some(ParticipantId::Real(1)));
+        assert_eq!(wins.payment, 10.0);
+        let ties = dra.run_with_false_bids(&[10.0, 10.0], &[], Some(1));
+        assert_eq!(ties.winner, Some(ParticipantId::Real(0)));
+        assert!(dra.min_winning_bid(&[]) > 5.0);
+    }
+
+    #[test]
+    fn abstaining_buyers_post_and_forfeit_nothing() {
+        let dra = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 10.0))
+            .alpha(1.0)
+            .abstain_below(1.0)
+            .build()
+            .expect("complete builder");
+        let (outcome, transcript) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[0.0, 8.0, 0.5, 6.0],
+            &[],
+            Some(&[true, false, true, true]),
+            Some(4),
+            &mut NonMalleableShaCommitment,
+        );
+        let committed: Vec<_> = transcript
+            .commitments
+            .iter()
+            .map(|c| c.participant.clone())
+            .collect();
+        assert_eq!(
+            committed,
+            vec![ParticipantId::Real(1), ParticipantId::Real(3)]
+        );
+        assert_eq!(outcome.collateral, dra.collateral(2));
+        assert!(outcome.settlements.iter().all(|s| !matches!(
+            s.participant,
+            ParticipantId::Real(0) | ParticipantId::Real(2)
+        )));
+        // Only buyer 1's withheld collateral changes hands.
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(3)));
+        assert_eq!(outcome.transferred_collateral, outcome.collateral);
+        assert!(audit_transcript(&transcript, &mut NonMalleableShaCommitment).is_ok());
+
+        let idle = dra.run_with_false_bids(&[0.0, 0.0, 0.0], &[], Some(4));
+        assert!(idle.settlements.is_empty());
+        assert_eq!(idle.winner, None);
+        assert_eq!(idle.forfeited_to_auctioneer, 0.0);
+    }
+
+    #[test]
+    fn is_auctioneer_only_for_the_auctioneer() {
+        assert!(ParticipantId::Auctioneer.is_auctioneer());
+        assert!(!ParticipantId::Real(0).is_auctioneer());
+        assert!(!ParticipantId::False(0).is_auctioneer());
+    }
+
+    #[test]
+    fn is_real_only_for_real_buyers() {
+        assert!(ParticipantId::Real(3).is_real());
+        assert!(!ParticipantId::False(3).is_real());
+        assert!(!ParticipantId::Auctioneer.is_real());
+    }
+
+    #[test]
+    fn is_false_only_for_false_bids() {
+        assert!(ParticipantId::False(2).is_false());
+        assert!(!ParticipantId::Real(2).is_false());
+        assert!(!ParticipantId::Auctioneer.is_false());
+    }
+
+    #[test]
+    fn index_is_the_position_within_a_kind() {
+        assert_eq!(ParticipantId::Real(4).index(), Some(4));
+        assert_eq!(ParticipantId::False(1).index(), Some(1));
+        assert_eq!(ParticipantId::Auctioneer.index(), None);
+        assert_eq!(ParticipantId::False(1).kind(), ParticipantKind::False);
+    }
+
+    #[test]
+    fn audit_report_lists_every_violation() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        let mut scheme = NonMalleableShaCommitment;
+        let (_, mut transcript) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[15.0, 9.0, 11.0],
+            &[],
+            None,
+            Some(4),
+            &mut scheme,
+        );
+        let report = audit_transcript_report(&transcript, &mut scheme);
+        assert!(report.is_clean(), "{report:?}");
+
+        transcript.commitments[2].timestamp = transcript.timings.commit_deadline + 1;
+        transcript.broadcasts.push(BroadcastEvent {
+            timestamp: 0,
+            sender: ParticipantId::Real(1),
+            message: BroadcastMessage::CommitmentPublished,
+        });
+        let report = audit_transcript_report(&transcript, &mut scheme);
+        assert_eq!(report.checked_commitments, 3);
+        assert_eq!(report.checked_reveals, 3);
+        assert!(
+            matches!(
+                report.errors.as_slice(),
+                [
+                    AuditError::DeadlineViolation {
+                        participant: ParticipantId::Real(2),
+                        phase: Phase::Commit,
+                        ..
+                    },
+                    AuditError::UnorderedEvents("broadcasts"),
+                ]
+            ),
+            "{:?}",
+            report.errors
+        );
+        assert!(matches!(
+            audit_transcript(&transcript, &mut scheme),
+            Err(AuditError::DeadlineViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn every_committer_locks_collateral() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        let outcome = dra.run_with_false_bids(
+            &[15.0, 9.0, 11.0],
+            &[FalseBid {
+                bid: 12.0,
+                reveal: true,
+            }],
+            Some(2),
+        );
+        assert!((outcome.total_collateral_locked() - 4.0 * outcome.collateral).abs() < 1e-9);
+        assert_eq!(outcome.transferred_collateral, 0.0);
+    }
+
+    #[test]
+    fn fraction_of_bid_posts_a_tenth_of_each_bid() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0)
+            .with_collateral_policy(CollateralPolicy::FractionOfBid(0.1));
+        let mut scheme = NonMalleableShaCommitment;
+        let (outcome, transcript) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[15.0, 9.0],
+            &[FalseBid {
+                bid: 12.0,
+                reveal: false,
+            }],
+            None,
+            Some(3),
+            &mut scheme,
+        );
+        let posted: Vec<f64> = outcome
+            .settlements
+            .iter()
+            .map(|s| s.posted_collateral)
+            .collect();
+        assert_eq!(posted, vec![0.1 * 15.0, 0.1 * 9.0, 0.1 * 12.0]);
+        assert_eq!(outcome.collateral, 0.1 * 15.0);
+        assert_eq!(outcome.transferred_collateral, 0.1 * 12.0);
+        audit_transcript(&transcript, &mut scheme).expect("per-bid collateral audits");
+
+        let bad = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 20.0))
+            .alpha(1.0)
+            .collateral_policy(CollateralPolicy::FractionOfReserve(-0.5))
+            .build();
+        assert!(matches!(
+            bad,
+            Err(ValidationError::InvalidCollateralFraction(_))
+        ));
+    }
+
+    #[test]
+    fn collateral_override_replaces_the_theorem_21_amount() {
+        let dra = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 20.0))
+            .alpha(1.0)
+            .collateral_override(0.25)
+            .build()
+            .expect("valid override");
+        assert_eq!(dra.collateral(3), 0.25);
+        assert_eq!(dra.collateral_with_shills(3, 2), 0.25);
+        let false_bids = [
+            FalseBid {
+                bid: 12.0,
+                reveal: false,
+            },
+            FalseBid {
+                bid: 11.0,
+                reveal: false,
+            },
+        ];
+        let outcome = dra.run_with_false_bids(&[15.0, 9.0], &false_bids, Some(5));
+        assert!(
+            outcome
+                .settlements
+                .iter()
+                .all(|s| s.posted_collateral == 0.25)
+        );
+        assert!((outcome.transferred_collateral - 0.5).abs() < 1e-12);
+
+        let bad = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 20.0))
+            .alpha(1.0)
+            .collateral_override(f64::NAN)
+            .build();
+        assert!(matches!(bad, Err(ValidationError::InvalidCollateral(_))));
+    }
+
+    #[test]
+    fn collateral_override_on_a_built_auction_rejects_invalid_amounts() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        for bad in [f64::NAN, f64::INFINITY, -0.5] {
+            assert!(matches!(
+                dra.clone().with_collateral_override(bad),
+                Err(ValidationError::InvalidCollateral(_))
+            ));
+        }
+        let dra = dra.with_collateral_override(0.25).expect("valid override");
+        assert_eq!(dra.collateral(3), 0.25);
+    }
+
+    #[test]
+    fn median_quantile_reserve_is_the_median() {
+        let uniform = PublicBroadcastDRA::with_quantile_reserve(Uniform::new(0.0, 10.0), 1.0, 0.5);
+        assert!((uniform.reserve() - 5.0).abs() < 1e-9);
+        let exp = PublicBroadcastDRA::with_quantile_reserve(Exponential::new(2.0), 1.0, 0.5);
+        assert!((exp.reserve() - 2f64.ln() / 2.0).abs() < 1e-9);
+        let outcome = exp.run_with_false_bids(&[0.3, 0.2], &[], Some(1));
+        assert_eq!(outcome.reserve, exp.reserve());
+        assert_eq!(outcome.winner, None);
+    }
+
+    #[test]
+    fn tampered_opening_forfeits_like_a_withheld_reveal() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        let valuations = [15.0, 9.0, 11.0];
+        let mut scheme = NonMalleableShaCommitment;
+        let (withheld, _) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &valuations,
+            &[],
+            Some(&[true, false, true]),
+            Some(7),
+            &mut scheme,
+        );
+
+        let (commitment, mut opening) = scheme.commit(9.0, &mut StdRng::seed_from_u64(1));
+        opening.bid = 19.0;
+        let (tampered, transcript) = dra.run_with_injected_commitments(
+            &valuations,
+            &[],
+            None,
+            &[(ParticipantId::Real(1), commitment, opening)],
+            Some(7),
+            &mut scheme,
+        );
+        assert!(!transcript.reveals[1].revealed);
+        assert_eq!(tampered.winner, withheld.winner);
+        assert_eq!(tampered.payment, withheld.payment);
+        assert_eq!(tampered.valid_bids, withheld.valid_bids);
+        assert_eq!(tampered.transferred_collateral, withheld.collateral);
+        assert_eq!(
+            tampered.transferred_collateral,
+            withheld.transferred_collateral
+        );
+        assert_eq!(tampered.settlements, withheld.settlements);
+    }
+
+    #[test]
+    fn identical_seeded_runs_have_an_empty_diff() {
+        let dist = Uniform::new(0.0, 20.0);
+        let valuations = [15.0, 9.0, 11.0];
+        let run = |alpha: f64| {
+            PublicBroadcastDRA::new(dist.clone(), alpha).run_with_false_bids_with_transcript(
+                &valuations,
+                &[],
+                Some(5),
+            )
+        };
+        let baseline = run(1.0);
+        assert!(diff_runs(&baseline, &run(1.0)).is_empty());
+
+        let diff = diff_runs(&baseline, &run(0.5));
+        assert!(!diff.is_empty());
+        assert!(diff.collateral.is_some());
+        assert!(diff.winner.is_none());
+    }
+
+    #[test]
+    fn participant_id_display_round_trips() {
+        for id in [
+            ParticipantId::Auctioneer,
+            ParticipantId::Real(0),
+            ParticipantId::False(3),
+        ] {
+            let text = id.to_string();
+            assert_eq!(text.parse::<ParticipantId>().expect("parse"), id);
+        }
+        assert_eq!(ParticipantId::False(3).to_string(), "false:3");
+        assert!(matches!(
+            "buyer:1".parse::<ParticipantId>(),
+            Err(ParseParticipantIdError::UnknownKind(_))
+        ));
+        assert!(matches!(
+            "real:-1".parse::<ParticipantId>(),
+            Err(ParseParticipantIdError::InvalidIndex(_))
+        ));
+        let parse =
+            |s: &str| -> Result<ParticipantId, Box<dyn std::error::Error>> { Ok(s.parse()?) };
+        assert_eq!(
+            parse("real:x").unwrap_err().to_string(),
+            "invalid participant index \"x\""
+        );
+    }
+
+    #[test]
+    fn deterministic_auction_rejects_a_missing_seed() {
+        let dra = PublicBroadcastDRA::builder()
+            .distribution(Uniform::new(0.0, 10.0))
+            .alpha(1.0)
+            .deterministic(true)
+            .build()
+            .expect("complete builder");
+        assert!(matches!(
+            dra.try_run_with_false_bids(&[7.0, 4.0], &[], None),
+            Err(AuctionError::Validation(ValidationError::MissingSeed))
+        ));
+        let seeded = dra
+            .try_run_with_false_bids(&[7.0, 4.0], &[], Some(3))
+            .expect("seeded run");
+        assert_eq!(seeded.winner, Some(ParticipantId::Real(0)));
+    }
+
+    #[test]
+    fn audit_catches_underreported_forfeiture() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        let withheld = [FalseBid {
+            bid: 9.0,
+            reveal: false,
+        }];
+        // Nobody reveals, so both collaterals are forfeited to the auctioneer.
+        let (outcome, mut transcript) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[12.0],
+            &withheld,
+            Some(&[false]),
+            Some(4),
+            &mut NonMalleableShaCommitment,
+        );
+        assert!((outcome.forfeited_to_auctioneer - 2.0 * outcome.collateral).abs() < 1e-9);
+        assert!(audit_transcript(&transcript, &mut NonMalleableShaCommitment).is_ok());
+        transcript.outcome.as_mut().unwrap().forfeited_to_auctioneer = outcome.collateral;
+        assert!(matches!(
+            audit_transcript(&transcript, &mut NonMalleableShaCommitment),
+            Err(AuditError::CollateralMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn reveal_at_the_deadline_is_late_for_audit_and_session() {
+        use crate::protocol::ProtocolSession;
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 20.0), 1.0);
+        let (_, mut transcript) =
+            dra.run_with_false_bids_with_transcript(&[12.0, 7.0], &[], Some(4));
+        let deadline = transcript.timings.reveal_deadline;
+        transcript.reveals[1].timestamp = deadline;
+        assert!(matches!(
+            audit_transcript(&transcript, &mut NonMalleableShaCommitment),
+            Err(AuditError::DeadlineViolation {
+                phase: Phase::Reveal,
+                timestamp,
+                ..
+            }) if timestamp == deadline
+        ));
+
+        let schedule = PhaseTimings {
+            commit_deadline: 2,
+            reveal_deadline: 4,
+        };
+        let collateral = dra.collateral(1);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 1, schedule, Vec::new());
+        session.commit_real(0, 12.0, collateral).expect("commit");
+        session.advance_to(2).expect("enter reveal");
+        session.advance_to(4).expect("reach deadline");
+        assert!(session.reveal(ParticipantId::Real(0)).is_err());
+    }
+
+    #[test]
+    fn real_and_false_ranks_never_collide() {
+        let buyers = 60_000;
+        assert!(ParticipantId::Real(buyers - 1).tie_rank() < ParticipantId::False(0).tie_rank());
+        let mut valid_bids: Vec<(ParticipantId, f64)> =
+            (0..buyers).map(|i| (ParticipantId::Real(i), 1.0)).collect();
+        valid_bids[buyers - 1].1 = 5.0;
+        valid_bids.insert(0, (ParticipantId::False(0), 5.0));
+        let (winner, _, payment) = resolve_winner(
+            &valid_bids,
+            0.0,
+            ReserveComparison::Strict,
+            AllocationRule::SecondPrice,
+            TieBreak::LowestId,
+        );
+        assert_eq!(winner, Some(ParticipantId::Real(buyers - 1)));
+        assert_eq!(payment, 5.0);
+    }
+
+    #[test]
+    fn shuffling_bids_never_changes_the_resolution() {
+        use rand::{Rng, seq::SliceRandom};
+        let mut rng = StdRng::seed_from_u64(349);
+        for _ in 0..50 {
+            // Bids on a coarse grid, so ties between reals and false bids are common.
+            let ids = (0..6)
+                .map(ParticipantId::Real)
+                .chain((0..3).map(ParticipantId::False));
+            let mut bids: Vec<(ParticipantId, f64)> =
+                ids.map(|id| (id, rng.gen_range(0..8) as f64)).collect();
+            for tie_break in [TieBreak::LowestId, TieBreak::HighestId] {
+                let resolve = |bids: &[(ParticipantId, f64)]| {
+                    resolve_winner(
+                        bids,
+                        2.5,
+                        ReserveComparison::Strict,
+                        AllocationRule::SecondPrice,
+                        tie_break,
+                    )
+                };
+                let expected = resolve(&bids);
+                for _ in 0..10 {
+                    bids.shuffle(&mut rng);
+                    assert_eq!(resolve(&bids), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn try_run_reports_validation_failures() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        assert!(matches!(
+            dra.try_run_with_false_bids(&[], &[], Some(1)),
+            Err(AuctionError::Validation(ValidationError::InsufficientBuyers))
+        ));
+        let strict = PublicBroadcastDRA::new(Exponential::new(1.0), 3.0);
+        assert!(matches!(
+            strict.try_run_with_false_bids(&[1.0], &[], Some(1)),
+            Err(AuctionError::Validation(ValidationError::AlphaTooLarge { .. }))
+        ));
+    }
+
+    #[test]
+    fn try_run_reports_unencodable_bids() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        assert!(matches!(
+            dra.try_run_with_false_bids(&[3.0, f64::NAN], &[], Some(1)),
+            Err(AuctionError::InvalidBid {
+                participant: ParticipantId::Real(1),
+                reason: CommitError::Encoding(BidEncodingError::NonFinite),
+                ..
+            })
+        ));
+        let negative = FalseBid {
+            bid: -1.0,
+            reveal: true,
+        };
+        assert!(matches!(
+            dra.try_run_with_false_bids(&[3.0], &[negative], Some(1)),
+            Err(AuctionError::InvalidBid {
+                participant: ParticipantId::False(0),
+                reason: CommitError::Encoding(BidEncodingError::Negative),
+                ..
+            })
+        ));
+        assert!(matches!(
+            dra.try_run_with_false_bids(&[1e40], &[], Some(1)),
+            Err(AuctionError::InvalidBid {
+                reason: CommitError::Encoding(BidEncodingError::Overflow),
+                ..
+            })
+        ));
+        assert!(dra.try_run_with_false_bids(&[3.0], &[], Some(1)).is_ok());
+    }
+
+    #[test]
+    fn outcome_reports_wire_sizes_for_revealed_openings() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let hidden = FalseBid {
+            bid: 4.0,
+            reveal: false,
+        };
+        let outcome = dra.run_with_false_bids(&[7.0, 6.0], &[hidden], Some(11));
+        assert_eq!(outcome.sizes.commitment_bytes_total, 3 * 32);
+        assert_eq!(outcome.sizes.opening_bytes_total, 2 * (BID_BYTES + 2 * SALT_BYTES));
+        assert_eq!(
+            outcome.sizes.proof_bytes_total(),
+            outcome.sizes.commitment_bytes_total + outcome.sizes.opening_bytes_total
+        );
+    }
+
+    #[test]
+    fn aggregated_bulletproof_is_published_once_per_transcript() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let mut scheme = BulletproofsCommitment::new(32);
+        let (outcome, transcript) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[7.0, 6.0, 3.0, 8.0],
+            &[],
+            None,
+            Some(282),
+            &mut scheme,
+        );
+        assert_eq!(transcript.shared_proofs.len(), 1);
+        let shared = &transcript.shared_proofs[0];
+        for reveal in transcript.reveals.iter() {
+            let opening = reveal.opening.as_ref().expect("every bid revealed");
+            let proof = &opening.bulletproof.as_ref().expect("proof data").proof;
+            assert!(proof.is_empty());
+        }
+        assert_eq!(outcome.sizes.shared_proof_bytes_total, shared.wire_size());
+        assert!(outcome.sizes.opening_bytes_total < shared.proof.len());
+
+        // A fresh instance learns the proof from the transcript alone.
+        assert!(audit_transcript(&transcript, &mut BulletproofsCommitment::new(32)).is_ok());
+        let mut withheld = transcript.clone();
+        withheld.shared_proofs.clear();
+        assert!(matches!(
+            audit_transcript(&withheld, &mut BulletproofsCommitment::new(32)),
+            Err(AuditError::BadOpening(_))
+        ));
+    }
+
+    #[test]
+    fn seeded_bulletproof_auctions_are_byte_identical() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let run = || {
+            let mut scheme = BulletproofsCommitment::new(32);
+            dra.run_with_false_bids_using_scheme_with_transcript(
+                &[2.0, 3.5],
+                &[],
+                None,
+                Some(295),
+                &mut scheme,
+            )
+            .1
+        };
+        let (first, second) = (run(), run());
+        let commitments = |t: &Transcript| -> Vec<Commitment> {
+            t.commitments.iter().map(|c| c.commitment.clone()).collect()
+        };
+        assert_eq!(commitments(&first), commitments(&second));
+        let proofs = |t: &Transcript| -> Vec<Vec<u8>> {
+            t.reveals
+                .iter()
+                .filter_map(|r| r.opening.as_ref()?.bulletproof.as_ref())
+                .map(|bp| bp.proof.clone())
+                .collect()
+        };
+        assert_eq!(proofs(&first), proofs(&second));
+    }
+
+    #[test]
+    fn seeded_audited_auctions_on_clones_are_byte_identical() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let scheme = AuditedNonMalleableCommitment::default();
+        let run = || {
+            let transcript = dra
+                .run_with_false_bids_using_scheme_with_transcript(
+                    &[2.0, 3.5, 1.25],
+                    &[],
+                    None,
+                    Some(295),
+                    &mut scheme.clone(),
+                )
+                .1;
+            serde_json::to_string(&transcript).expect("transcript serializes")
+        };
+        assert_eq!(run(), run());
+        assert_eq!(scheme.published_root(), [0u8; 32]);
+    }
+
+    #[test]
+    fn try_run_reports_bids_beyond_bulletproof_range() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let mut scheme = BulletproofsCommitment::new(8);
+        let err = dra
+            .try_run_with_false_bids_using_scheme(&[0.0001, 1.0], &[], Some(1), &mut scheme)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AuctionError::InvalidBid {
+                participant: ParticipantId::Real(1),
+                reason: CommitError::OutOfRange { .. },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn protocol_and_audit_failures_surface_as_auction_errors() {
+        use crate::protocol::ProtocolSession;
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let collateral = dra.collateral(1);
+        let reveal_during_commit = || -> Result<(), AuctionError> {
+            let schedule = PhaseTimings {
+                commit_deadline: 4,
+                reveal_deadline: 8,
+            };
+            let mut session = ProtocolSession::new(
+                dra.clone(),
+                NonMalleableShaCommitment,
+                3,
+                schedule,
+                Vec::new(),
+            );
+            session.commit_real(0, 6.0, collateral)?;
+            session.reveal(ParticipantId::Real(0))?;
+            Ok(())
+        };
+        assert!(matches!(
+            reveal_during_commit(),
+            Err(AuctionError::Protocol(ProtocolError::WrongPhase))
+        ));
+
+        let audit_relabelled_bid = || -> Result<(), AuctionError> {
+            let (_, mut transcript) = dra.try_run_with_false_bids_using_scheme_with_transcript(
+                &[6.0, 2.0],
+                &[],
+                Some(7),
+                &mut NonMalleableShaCommitment,
+            )?;
+            let opening = transcript.reveals[1].opening.as_mut().expect("revealed");
+            opening.bid = 9.0;
+            opening.encoding = BidEncoding::new(9.0, &BidCodec::default());
+            audit_transcript(&transcript, &mut NonMalleableShaCommitment)?;
+            Ok(())
+        };
+        assert!(matches!(
+            audit_relabelled_bid(),
+            Err(AuctionError::Audit(AuditError::BadOpening(
+                ParticipantId::Real(1)
+            )))
+        ));
+    }
+
+    #[test]
+    fn offline_audit_checks_receipts_against_the_trusted_root() {
+        use crate::commitment::PedersenRistrettoCommitment;
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let mut issuer = AuditedNonMalleableCommitment::default();
+        let (_, audited) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[2.0, 6.0],
+            &[],
+            None,
+            Some(5),
+            &mut issuer,
+        );
+        let published = issuer.published_root();
+        assert!(audit_transcript(&audited, &mut PublishedRootAudit::new(published)).is_ok());
+
+        // A forger who rebuilds the ledger from scratch controls the roots in the receipts, but
+        // not the root the auctioneer published.
+        let mut forged = audited.clone();
+        let mut forger = AuditedNonMalleableCommitment::default();
+        let (_, rebuilt) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[2.0, 9.0],
+            &[],
+            None,
+            Some(5),
+            &mut forger,
+        );
+        forged.commitments = rebuilt.commitments;
+        forged.reveals = rebuilt.reveals;
+        assert!(matches!(
+            audit_transcript(&forged, &mut PublishedRootAudit::new(published)),
+            Err(AuditError::BadOpening(_))
+        ));
+
+        // The backend is the caller's choice, not the transcript's.
+        let (_, pedersen) = dra.run_with_false_bids_using_scheme_with_transcript(
+            &[2.0, 6.0],
+            &[],
+            None,
+            Some(5),
+            &mut PedersenRistrettoCommitment,
+        );
+        assert!(audit_transcript(&pedersen, &mut PedersenRistrettoCommitment).is_ok());
+        assert!(audit_transcript(&pedersen, &mut PublishedRootAudit::new(published)).is_err());
+    }
+
+    #[test]
+    fn errors_display_readable_messages_and_chain_their_source() {
+        use std::error::Error;
+        let err = AuctionError::from(ValidationError::AlphaTooLarge {
+            requested: 3.0,
+            supported: 1.0,
+        });
+        assert_eq!(
+            err.to_string(),
+            "invalid auction: alpha 3 exceeds the distribution's supported 1"
+        );
+        assert!(err.source().is_some());
+        let mut scheme = BulletproofsCommitment::new(8);
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let invalid = dra
+            .try_run_with_false_bids_using_scheme(&[0.0001, 1.0], &[], Some(1), &mut scheme)
+            .unwrap_err();
+        assert!(
+            invalid
+                .to_string()
+                .contains("exceeds the range proof's maximum")
+        );
+        assert!(invalid.source().is_some());
+        let audit = AuctionError::from(AuditError::BadOpening(ParticipantId::False(2)));
+        assert_eq!(
+            audit.to_string(),
+            "audit failed: opening from false:2 does not verify"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_inputs_panic_on_zero_buyers() {
+        let dist = Uniform::new(0.0, 10.0);
+        let dra = PublicBroadcastDRA::new(dist, 1.0);
+        let _ = dra.run_with_false_bids(&[], &[], None);
+    }
+}
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommitmentEvent {
+    pub participant: ParticipantId,
+    pub commitment: Commitment,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RevealEvent {
     pub participant: ParticipantId,
     pub revealed: bool,
@@ -427,33 +2106,28 @@ pub struct RevealEvent {
     pub timestamp: u64,
 }
 
-#[derive(Clone, Debug)]
+/// Phase deadlines, in the units of the clock stamping the transcript: ticks for a
+/// [`LogicalClock`], Unix-epoch milliseconds for a [`SystemClock`](crate::clock::SystemClock).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PhaseTimings {
     pub commit_deadline: u64,
     pub reveal_deadline: u64,
 }
 
-impl Default for PhaseTimings {
-    fn default() -> Self {
-        Self {
-            commit_deadline: 0,
-            reveal_deadline: 0,
-        }
-    }
-}
-
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PhaseTransitionReason {
     Manual,
     Deadline,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum BroadcastMessage {
     CommitmentPublished,
     RevealPublished {
         success: bool,
     },
+    /// The sender announced it will not open its commitment, forfeiting collateral.
+    Withheld,
     PhaseTransition {
         phase: Phase,
         reason: PhaseTransitionReason,
@@ -464,20 +2138,23 @@ pub enum BroadcastMessage {
     },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BroadcastEvent {
     pub timestamp: u64,
     pub sender: ParticipantId,
     pub message: BroadcastMessage,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Transcript {
     pub commitments: Vec<CommitmentEvent>,
     pub reveals: Vec<RevealEvent>,
     pub broadcasts: Vec<BroadcastEvent>,
     pub timings: PhaseTimings,
     pub outcome: Option<AuctionOutcome>,
+    /// Proofs the revealed openings refer to instead of embedding, each published once.
+    #[serde(default)]
+    pub shared_proofs: Vec<SharedRangeProof>,
 }
 
 #[derive(Debug)]
@@ -485,6 +2162,11 @@ pub enum AuditError {
     MissingOutcome,
     MissingTimings,
     RevealWithoutCommit(ParticipantId),
+    DuplicateCommitment(ParticipantId),
+    ClonedCommitment {
+        original: ParticipantId,
+        clone: ParticipantId,
+    },
     BadOpening(ParticipantId),
     DeadlineViolation {
         participant: ParticipantId,
@@ -492,99 +2174,208 @@ pub enum AuditError {
         timestamp: u64,
     },
     UnorderedEvents(&'static str),
+    /// A reveal notice whose success flag disagrees with the recorded reveal.
+    RevealFlagMismatch(ParticipantId),
+    /// Re-resolving the revealed bids disagrees with the recorded outcome on this field.
+    OutcomeMismatch(&'static str),
+    /// Forfeited plus transferred collateral differs from what the non-revealers posted.
+    CollateralMismatch {
+        expected: f64,
+        recorded: f64,
+    },
 }
 
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditError::MissingOutcome => f.write_str("transcript records no outcome"),
+            AuditError::MissingTimings => f.write_str("transcript records no phase timings"),
+            AuditError::RevealWithoutCommit(pid) => write!(f, "{pid} revealed without committing"),
+            AuditError::DuplicateCommitment(pid) => write!(f, "{pid} committed more than once"),
+            AuditError::ClonedCommitment { original, clone } => {
+                write!(f, "{clone} published the commitment of {original}")
+            }
+            AuditError::BadOpening(pid) => write!(f, "opening from {pid} does not verify"),
+            AuditError::DeadlineViolation {
+                participant,
+                phase,
+                timestamp,
+            } => write!(
+                f,
+                "{participant} acted at {timestamp}, after the {phase:?} deadline"
+            ),
+            AuditError::UnorderedEvents(what) => write!(f, "{what} are out of order"),
+            AuditError::RevealFlagMismatch(pid) => {
+                write!(
+                    f,
+                    "reveal notice for {pid} disagrees with its recorded reveal"
+                )
+            }
+            AuditError::OutcomeMismatch(field) => {
+                write!(f, "recorded {field} disagrees with the revealed bids")
+            }
+            AuditError::CollateralMismatch { expected, recorded } => write!(
+                f,
+                "non-revealers posted {expected} collateral but {recorded} was settled"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AuditError {}
+
 /// Audit a transcript against a commitment scheme to ensure the openings match commitments and
-/// every reveal references a committed party (Definition 8).
+/// every reveal references a committed party (Definition 8). Stops at the first violation; see
+/// [`audit_transcript_report`] for all of them.
 pub fn audit_transcript<S: CommitmentScheme>(
     transcript: &Transcript,
     scheme: &mut S,
 ) -> Result<(), AuditError> {
-    let outcome = transcript
-        .outcome
-        .as_ref()
-        .ok_or(AuditError::MissingOutcome)?;
-    if transcript.timings.reveal_deadline < transcript.timings.commit_deadline {
-        return Err(AuditError::MissingTimings);
+    let report = audit_transcript_report(transcript, scheme);
+    match report.errors.into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(()),
     }
+}
+
+/// Every violation found by a full audit pass, in the order [`audit_transcript`] checks them.
+#[derive(Debug)]
+pub struct AuditReport {
+    pub errors: Vec<AuditError>,
+    pub checked_commitments: usize,
+    pub checked_reveals: usize,
+}
+
+impl AuditReport {
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Run every check of [`audit_transcript`] and collect all violations instead of stopping at
+/// the first. Without a recorded outcome, the checks against it are skipped.
+pub fn audit_transcript_report<S: CommitmentScheme>(
+    transcript: &Transcript,
+    scheme: &mut S,
+) -> AuditReport {
     use std::collections::HashMap;
+    let mut errors = Vec::new();
+    let outcome = transcript.outcome.as_ref();
+    if outcome.is_none() {
+        errors.push(AuditError::MissingOutcome);
+    }
+    if transcript.timings.reveal_deadline < transcript.timings.commit_deadline {
+        errors.push(AuditError::MissingTimings);
+    }
+    scheme.import_shared_proofs(&transcript.shared_proofs);
     let mut commit_map: HashMap<ParticipantId, (&Commitment, u64)> = HashMap::new();
+    let mut owners: HashMap<&Commitment, ParticipantId> = HashMap::new();
     let mut last_ts = 0u64;
     for c in transcript.commitments.iter() {
         if c.timestamp < last_ts {
-            return Err(AuditError::UnorderedEvents("commitments"));
+            errors.push(AuditError::UnorderedEvents("commitments"));
         }
         last_ts = c.timestamp;
         if c.timestamp > transcript.timings.commit_deadline {
-            return Err(AuditError::DeadlineViolation {
+            errors.push(AuditError::DeadlineViolation {
                 participant: c.participant.clone(),
                 phase: Phase::Commit,
                 timestamp: c.timestamp,
             });
         }
+        if let Some(original) = owners.get(&c.commitment) {
+            errors.push(if *original == c.participant {
+                AuditError::DuplicateCommitment(c.participant.clone())
+            } else {
+                AuditError::ClonedCommitment {
+                    original: original.clone(),
+                    clone: c.participant.clone(),
+                }
+            });
+            continue;
+        }
+        if commit_map.contains_key(&c.participant) {
+            errors.push(AuditError::DuplicateCommitment(c.participant.clone()));
+            continue;
+        }
         commit_map.insert(c.participant.clone(), (&c.commitment, c.timestamp));
+        owners.insert(&c.commitment, c.participant.clone());
     }
     last_ts = transcript.timings.commit_deadline;
     for rev in transcript.reveals.iter() {
         if rev.timestamp < last_ts {
-            return Err(AuditError::UnorderedEvents("reveals"));
+            errors.push(AuditError::UnorderedEvents("reveals"));
         }
         last_ts = rev.timestamp;
-        if rev.timestamp > transcript.timings.reveal_deadline {
-            return Err(AuditError::DeadlineViolation {
+        // Openings must land strictly before the deadline, as `ProtocolSession::reveal`
+        // requires; a missed reveal is recorded at the deadline itself.
+        let late = if rev.revealed {
+            rev.timestamp >= transcript.timings.reveal_deadline
+        } else {
+            rev.timestamp > transcript.timings.reveal_deadline
+        };
+        if late {
+            errors.push(AuditError::DeadlineViolation {
                 participant: rev.participant.clone(),
                 phase: Phase::Reveal,
                 timestamp: rev.timestamp,
             });
         }
-        let (commit, commit_ts) = commit_map
-            .get(&rev.participant)
-            .ok_or_else(|| AuditError::RevealWithoutCommit(rev.participant.clone()))?;
+        let Some((commit, commit_ts)) = commit_map.get(&rev.participant) else {
+            errors.push(AuditError::RevealWithoutCommit(rev.participant.clone()));
+            continue;
+        };
         if rev.timestamp < *commit_ts {
-            return Err(AuditError::DeadlineViolation {
+            errors.push(AuditError::DeadlineViolation {
                 participant: rev.participant.clone(),
                 phase: Phase::Commit,
                 timestamp: rev.timestamp,
             });
         }
         if rev.revealed {
-            let opening = rev
+            let opens = rev
                 .opening
                 .as_ref()
-                .ok_or_else(|| AuditError::BadOpening(rev.participant.clone()))?;
-            if !scheme.verify(commit, opening) {
-                return Err(AuditError::BadOpening(rev.participant.clone()));
+                .is_some_and(|opening| scheme.verify(commit, opening));
+            let counted =
+                outcome.is_none_or(|o| o.valid_bids.iter().any(|(p, _)| p == &rev.participant));
+            if !(opens && counted) {
+                errors.push(AuditError::BadOpening(rev.participant.clone()));
             }
-            let _ = outcome
-                .valid_bids
-                .iter()
-                .find(|(p, _)| p == &rev.participant)
-                .ok_or_else(|| AuditError::BadOpening(rev.participant.clone()))?;
         }
     }
     last_ts = 0;
     for event in transcript.broadcasts.iter() {
         if event.timestamp < last_ts {
-            return Err(AuditError::UnorderedEvents("broadcasts"));
+            errors.push(AuditError::UnorderedEvents("broadcasts"));
         }
         last_ts = event.timestamp;
+        let late = |participant: &ParticipantId, phase: Phase| AuditError::DeadlineViolation {
+            participant: participant.clone(),
+            phase,
+            timestamp: event.timestamp,
+        };
         match &event.message {
             BroadcastMessage::CommitmentPublished => {
                 if event.timestamp > transcript.timings.commit_deadline {
-                    return Err(AuditError::DeadlineViolation {
-                        participant: event.sender.clone(),
-                        phase: Phase::Commit,
-                        timestamp: event.timestamp,
-                    });
+                    errors.push(late(&event.sender, Phase::Commit));
+                }
+            }
+            BroadcastMessage::RevealPublished { success } => {
+                if event.timestamp >= transcript.timings.reveal_deadline {
+                    errors.push(late(&event.sender, Phase::Reveal));
+                }
+                let recorded = transcript
+                    .reveals
+                    .iter()
+                    .find(|r| r.participant == event.sender);
+                if recorded.is_none_or(|r| r.revealed != *success) {
+                    errors.push(AuditError::RevealFlagMismatch(event.sender.clone()));
                 }
             }
-            BroadcastMessage::RevealPublished { .. } => {
-                if event.timestamp > transcript.timings.reveal_deadline {
-                    return Err(AuditError::DeadlineViolation {
-                        participant: event.sender.clone(),
-                        phase: Phase::Reveal,
-                        timestamp: event.timestamp,
-                    });
+            BroadcastMessage::Withheld => {
+                if event.timestamp >= transcript.timings.reveal_deadline {
+                    errors.push(late(&event.sender, Phase::Reveal));
                 }
             }
             BroadcastMessage::Timeout { phase, target } => {
@@ -593,41 +2384,196 @@ pub fn audit_transcript<S: CommitmentScheme>(
                     Phase::Reveal | Phase::Resolved => transcript.timings.reveal_deadline,
                 };
                 if event.timestamp < cutoff {
-                    return Err(AuditError::DeadlineViolation {
-                        participant: target.clone(),
-                        phase: *phase,
-                        timestamp: event.timestamp,
-                    });
+                    errors.push(late(target, *phase));
                 }
             }
-            BroadcastMessage::PhaseTransition { phase, .. } => match phase {
-                Phase::Commit => {}
-                Phase::Reveal => {
-                    if event.timestamp < transcript.timings.commit_deadline {
-                        return Err(AuditError::DeadlineViolation {
-                            participant: event.sender.clone(),
-                            phase: *phase,
-                            timestamp: event.timestamp,
-                        });
-                    }
-                }
-                Phase::Resolved => {
-                    if event.timestamp < transcript.timings.reveal_deadline {
-                        return Err(AuditError::DeadlineViolation {
-                            participant: event.sender.clone(),
-                            phase: *phase,
-                            timestamp: event.timestamp,
-                        });
-                    }
+            BroadcastMessage::PhaseTransition { phase, .. } => {
+                let cutoff = match phase {
+                    Phase::Commit => 0,
+                    Phase::Reveal => transcript.timings.commit_deadline,
+                    Phase::Resolved => transcript.timings.reveal_deadline,
+                };
+                if event.timestamp < cutoff {
+                    errors.push(late(&event.sender, *phase));
                 }
-            },
+            }
+        }
+    }
+    if let Some(outcome) = outcome {
+        let expected: f64 = transcript
+            .reveals
+            .iter()
+            .filter(|r| !r.revealed)
+            .map(|r| recorded_collateral(Some(outcome), &r.participant))
+            .sum();
+        let recorded = outcome.forfeited_to_auctioneer + outcome.transferred_collateral;
+        if (expected - recorded).abs() > 1e-9 * expected.abs().max(1.0) {
+            errors.push(AuditError::CollateralMismatch { expected, recorded });
         }
+        let recomputed = recompute_outcome(transcript, outcome.reserve);
+        if let Some(field) = outcome_mismatch(outcome, &recomputed) {
+            errors.push(AuditError::OutcomeMismatch(field));
+        }
+    }
+    AuditReport {
+        errors,
+        checked_commitments: transcript.commitments.len(),
+        checked_reveals: transcript.reveals.len(),
+    }
+}
+
+/// Audited backend for offline audits: receipts must verify against a root the auctioneer
+/// published out of band, never against the root a receipt carries itself.
+#[derive(Clone, Debug)]
+pub struct PublishedRootAudit {
+    scheme: AuditedNonMalleableCommitment,
+    root: [u8; 32],
+}
+
+impl PublishedRootAudit {
+    pub fn new(trusted_root: [u8; 32]) -> Self {
+        Self {
+            scheme: AuditedNonMalleableCommitment::default(),
+            root: trusted_root,
+        }
+    }
+}
+
+impl CommitmentScheme for PublishedRootAudit {
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
+        self.scheme.commit_with_codec(bid, codec, rng)
+    }
+
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        self.scheme
+            .verify_with_codec_and_root(commitment, opening, codec, Some(self.root))
+    }
+
+    fn import_shared_proofs(&self, proofs: &[SharedRangeProof]) {
+        self.scheme.import_shared_proofs(proofs)
     }
-    Ok(())
 }
 
 #[derive(Debug)]
 pub enum ValidationError {
     InsufficientBuyers,
-    AlphaTooLarge { requested: f64, supported: f64 },
+    AlphaTooLarge {
+        requested: f64,
+        supported: f64,
+    },
+    /// A required [`DraBuilder`] setting was never given.
+    MissingParameter(&'static str),
+    NonPositiveAlpha(f64),
+    /// A reserve override that is negative or not finite.
+    InvalidReserve(f64),
+    /// A deterministic auction was run with no RNG seed.
+    MissingSeed,
+    /// A collateral fraction that is negative or not finite.
+    InvalidCollateralFraction(f64),
+    /// A collateral override that is negative or not finite.
+    InvalidCollateral(f64),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InsufficientBuyers => f.write_str("at least one buyer is required"),
+            ValidationError::AlphaTooLarge {
+                requested,
+                supported,
+            } => write!(
+                f,
+                "alpha {requested} exceeds the distribution's supported {supported}"
+            ),
+            ValidationError::MissingParameter(name) => write!(f, "missing parameter: {name}"),
+            ValidationError::NonPositiveAlpha(alpha) => write!(f, "alpha {alpha} is not positive"),
+            ValidationError::InvalidReserve(reserve) => {
+                write!(f, "reserve {reserve} is negative or not finite")
+            }
+            ValidationError::MissingSeed => {
+                f.write_str("a deterministic auction needs an RNG seed")
+            }
+            ValidationError::InvalidCollateralFraction(fraction) => {
+                write!(
+                    f,
+                    "collateral fraction {fraction} is negative or not finite"
+                )
+            }
+            ValidationError::InvalidCollateral(collateral) => {
+                write!(f, "collateral {collateral} is negative or not finite")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Crate-wide error wrapping the per-module failure types.
+#[derive(Debug)]
+pub enum AuctionError {
+    Validation(ValidationError),
+    InvalidBid {
+        participant: ParticipantId,
+        bid: f64,
+        reason: CommitError,
+    },
+    Protocol(ProtocolError),
+    Audit(AuditError),
+}
+
+impl fmt::Display for AuctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuctionError::Validation(err) => write!(f, "invalid auction: {err}"),
+            AuctionError::InvalidBid {
+                participant,
+                bid,
+                reason,
+            } => write!(
+                f,
+                "bid {bid} from {participant} cannot be committed: {reason}"
+            ),
+            AuctionError::Protocol(err) => write!(f, "protocol error: {err}"),
+            AuctionError::Audit(err) => write!(f, "audit failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AuctionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AuctionError::Validation(err) => Some(err),
+            AuctionError::InvalidBid { reason, .. } => Some(reason),
+            AuctionError::Protocol(err) => Some(err),
+            AuctionError::Audit(err) => Some(err),
+        }
+    }
+}
+
+impl From<ValidationError> for AuctionError {
+    fn from(err: ValidationError) -> Self {
+        AuctionError::Validation(err)
+    }
+}
+
+impl From<ProtocolError> for AuctionError {
+    fn from(err: ProtocolError) -> Self {
+        AuctionError::Protocol(err)
+    }
+}
+
+impl From<AuditError> for AuctionError {
+    fn from(err: AuditError) -> Self {
+        AuctionError::Audit(err)
+    }
 }
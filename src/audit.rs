@@ -1,24 +1,87 @@
 use sha2::{Digest, Sha256};
 use std::fs;
+use std::path::Path;
 
-pub fn emit_provenance() -> Result<(), Box<dyn std::error::Error>> {
-    const BULLETPROOFS_SHA: &str = "012e2e5f88332083bd4235d445ae78081c00b2558443821a9ca5adfe1070073d";
-    const BULLETPROOFS_VERSION: &str = "5.0.0";
-    let tex_path = "reference_material/Credible_Optimal_Auctions_public_broadcast_full.tex";
-    let tex_bytes = fs::read(tex_path)?;
-    let mut hasher = Sha256::new();
-    hasher.update(&tex_bytes);
-    let tex_hash = format!("{:x}", hasher.finalize());
-    let payload = serde_json::json!({
-        "bulletproofs_crate": {
-            "version": BULLETPROOFS_VERSION,
-            "sha256": BULLETPROOFS_SHA,
-        },
-        "paper_tex": {
-            "path": tex_path,
-            "sha256": tex_hash,
-        }
-    });
+/// Paper source hashed when no other path is given.
+pub const DEFAULT_TEX_PATH: &str =
+    "reference_material/Credible_Optimal_Auctions_public_broadcast_full.tex";
+
+// Pinned values, used only when Cargo.lock is not next to the manifest (e.g. a packaged crate).
+// Cargo.lock is not committed, so nothing checks these; update them by hand whenever the
+// locked `bulletproofs` version changes.
+const BULLETPROOFS_SHA: &str = "012e2e5f88332083bd4235d445ae78081c00b2558443821a9ca5adfe1070073d";
+const BULLETPROOFS_VERSION: &str = "5.0.0";
+
+pub fn emit_provenance(tex_path: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let payload = provenance(tex_path)?;
     println!("{}", serde_json::to_string_pretty(&payload)?);
     Ok(())
 }
+
+/// Provenance report: the locked `bulletproofs` version and checksum, plus the SHA-256 of the
+/// paper source, or `"paper_tex": null` when that file is absent.
+pub fn provenance(
+    tex_path: Option<&Path>,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let tex_path = tex_path.unwrap_or(Path::new(DEFAULT_TEX_PATH));
+    let paper_tex = match fs::read(tex_path) {
+        Ok(tex_bytes) => {
+            let mut hasher = Sha256::new();
+            hasher.update(&tex_bytes);
+            serde_json::json!({
+                "path": tex_path.display().to_string(),
+                "sha256": format!("{:x}", hasher.finalize()),
+            })
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => serde_json::Value::Null,
+        Err(err) => return Err(err.into()),
+    };
+    let lock = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.lock")).ok();
+    let (version, sha) = lock
+        .as_deref()
+        .and_then(|lock| locked_package(lock, "bulletproofs"))
+        .unwrap_or((BULLETPROOFS_VERSION, BULLETPROOFS_SHA));
+    Ok(serde_json::json!({
+        "bulletproofs_crate": {
+            "version": version,
+            "sha256": sha,
+        },
+        "paper_tex": paper_tex,
+    }))
+}
+
+/// Find `name`'s `(version, checksum)` in the text of a Cargo.lock.
+fn locked_package<'a>(lock: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    let entry = lock
+        .split("[[package]]")
+        .find(|entry| field(entry, "name") == Some(name))?;
+    Some((field(entry, "version")?, field(entry, "checksum")?))
+}
+
+fn field<'a>(entry: &'a str, key: &str) -> Option<&'a str> {
+    entry.lines().find_map(|line| {
+        let value = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+        Some(value.trim().trim_matches('"'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_tex_is_reported_as_null() {
+        let report = provenance(Some(Path::new("no/such/paper.tex"))).expect("provenance");
+        assert!(report["paper_tex"].is_null());
+        assert!(
+            report["bulletproofs_crate"]["sha256"]
+                .as_str()
+                .is_some_and(|sha| sha.len() == 64 && sha.bytes().all(|b| b.is_ascii_hexdigit()))
+        );
+        assert!(
+            report["bulletproofs_crate"]["version"]
+                .as_str()
+                .is_some_and(|v| v.starts_with("5."))
+        );
+    }
+}
@@ -1,5 +1,8 @@
+use std::path::PathBuf;
+
 use broadcast_dra::audit::emit_provenance;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    emit_provenance()
+    let tex_path = std::env::args().nth(1).map(PathBuf::from);
+    emit_provenance(tex_path.as_deref())
 }
@@ -0,0 +1,59 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of transcript timestamps. Readings never decrease, so the audit's deadline and
+/// ordering checks hold for any clock that stamps events in the order they happen.
+pub trait Clock: Clone {
+    /// Current reading; never less than an earlier one.
+    fn now(&mut self) -> u64;
+    /// Step past the current reading, so the next event is stamped strictly later.
+    fn tick(&mut self);
+}
+
+/// Counter starting at zero that moves only when ticked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LogicalClock {
+    time: u64,
+}
+
+impl LogicalClock {
+    pub fn starting_at(time: u64) -> Self {
+        Self { time }
+    }
+}
+
+impl Clock for LogicalClock {
+    fn now(&mut self) -> u64 {
+        self.time
+    }
+
+    fn tick(&mut self) {
+        self.time = self.time.saturating_add(1);
+    }
+}
+
+/// Wall time in milliseconds since the Unix epoch. Events within the same millisecond are
+/// pushed forward one millisecond per tick, and a system clock stepping backwards is ignored.
+/// Deadlines compared against it must be epoch milliseconds too.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock {
+    last: u64,
+}
+
+impl SystemClock {
+    fn wall_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&mut self) -> u64 {
+        self.last = self.last.max(Self::wall_ms());
+        self.last
+    }
+
+    fn tick(&mut self) {
+        self.last = self.now().saturating_add(1);
+    }
+}
@@ -1,37 +1,183 @@
+use crate::auction::ValidationError;
 use crate::distribution::ValueDistribution;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::Serialize;
 
 /// Collateral threshold \(f(n,D)\) from Theorem 21 that deters shill withholding for
 /// \(\alpha\)-strongly regular distributions.
+///
+/// # Panics
+/// If `n` is zero or `alpha` is not positive or exceeds the distribution's strong-regularity
+/// bound; see [`try_collateral_requirement`].
 pub fn collateral_requirement<D: ValueDistribution>(n: usize, dist: &D, alpha: f64) -> f64 {
-    assert!(n > 0, "number of buyers must be positive");
-    assert!(alpha > 0.0, "alpha must be positive");
-    let reserve = dist.reserve_price();
-    if alpha >= 1.0 {
-        return reserve;
+    collateral_breakdown(n, dist, alpha).total
+}
+
+/// Per-bid collateral deterring `shills` simultaneous false bids. Each withheld shill is weighed
+/// against the `n` real bids and the other `shills - 1` shills, so the Theorem 21 bound is taken
+/// at `n + shills - 1` bidders: for `alpha < 1` it grows like
+/// \((n+k-1)^{(1-\alpha)/\alpha}\), for `alpha >= 1` it stays at the reserve. With zero or one
+/// shill this is exactly [`collateral_requirement`].
+pub fn collateral_requirement_with_shills<D: ValueDistribution>(
+    n: usize,
+    shills: usize,
+    dist: &D,
+    alpha: f64,
+) -> f64 {
+    collateral_requirement(n + shills.saturating_sub(1), dist, alpha)
+}
+
+/// Fallible [`collateral_requirement`]: the bound only holds when the distribution is
+/// \(\alpha\)-strongly regular, so an `alpha` above its known regularity is rejected, as are
+/// zero buyers and a non-positive `alpha`.
+pub fn try_collateral_requirement<D: ValueDistribution>(
+    n: usize,
+    dist: &D,
+    alpha: f64,
+) -> Result<f64, ValidationError> {
+    checked_breakdown(n, dist, alpha).map(|breakdown| breakdown.total)
+}
+
+/// The factors of the Theorem 21 collateral
+/// \(r(D)\cdot(n/\alpha)^{(1-\alpha)/\alpha}\cdot(1/(1-\alpha))^{1/\alpha}\).
+/// For \(\alpha \ge 1\) the formula short-circuits to the reserve and both terms are 1.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct CollateralBreakdown {
+    pub reserve: f64,
+    pub n_term: f64,
+    pub hazard_term: f64,
+    pub total: f64,
+}
+
+/// Break [`collateral_requirement`] into its multiplicative factors.
+///
+/// # Panics
+/// Under the same conditions as [`collateral_requirement`].
+pub fn collateral_breakdown<D: ValueDistribution>(
+    n: usize,
+    dist: &D,
+    alpha: f64,
+) -> CollateralBreakdown {
+    checked_breakdown(n, dist, alpha).unwrap_or_else(|err| {
+        panic!("Theorem 21 collateral is undefined: {err}")
+    })
+}
+
+fn checked_breakdown<D: ValueDistribution>(
+    n: usize,
+    dist: &D,
+    alpha: f64,
+) -> Result<CollateralBreakdown, ValidationError> {
+    if n == 0 {
+        return Err(ValidationError::InsufficientBuyers);
     }
-    let n_term = (n as f64 / alpha).powf((1.0 - alpha) / alpha);
-    let hazard_term = (1.0 / (1.0 - alpha)).powf(1.0 / alpha);
-    reserve * n_term * hazard_term
+    if alpha.is_nan() || alpha <= 0.0 {
+        return Err(ValidationError::NonPositiveAlpha(alpha));
+    }
+    if let Some(supported) = dist.strong_regular_alpha()
+        && alpha > supported + f64::EPSILON
+    {
+        return Err(ValidationError::AlphaTooLarge {
+            requested: alpha,
+            supported,
+        });
+    }
+    let reserve = dist.reserve_price();
+    let (n_term, hazard_term) = if alpha >= 1.0 {
+        (1.0, 1.0)
+    } else {
+        (
+            (n as f64 / alpha).powf((1.0 - alpha) / alpha),
+            (1.0 / (1.0 - alpha)).powf(1.0 / alpha),
+        )
+    };
+    Ok(CollateralBreakdown {
+        reserve,
+        n_term,
+        hazard_term,
+        total: reserve * n_term * hazard_term,
+    })
 }
 
-/// Numerically approach the same collateral threshold using binary search.
-/// Useful when plugging in custom reserve-price or hazard computations.
+/// Valuation profiles sampled by [`numeric_collateral_search`].
+const SEARCH_PROFILES: usize = 20_000;
+/// Shill bids tried between the reserve and the largest sampled top bid.
+const SEARCH_BIDS: usize = 256;
+
+/// Independently search for the smallest collateral that makes shill withholding unprofitable
+/// for `dist` itself, by bisection on the deviation's expected profit.
+///
+/// The deviation commits a shill at `b`, reveals it when the top real bid reaches `b` (raising
+/// the price to `b`), and otherwise withholds it and forfeits the collateral `c`. Over a fixed
+/// sample of valuation profiles its best-case profit `max_b E[gain(b)] - c * P(top < b)` is
+/// non-increasing in `c`, so bisection finds the threshold. Theorem 21's closed form is a
+/// worst case over all \(\alpha\)-strongly regular distributions and seeds the upper bracket
+/// (at least 1, so a zero reserve still brackets), so the result never exceeds it for a valid
+/// `alpha`. Bracketing and bisection both stop after a fixed number of steps.
 pub fn numeric_collateral_search<D: ValueDistribution>(
     n: usize,
     dist: &D,
     alpha: f64,
     tol: f64,
 ) -> f64 {
-    let target = collateral_requirement(n, dist, alpha);
+    assert!(tol > 0.0, "tolerance must be positive");
     let reserve = dist.reserve_price();
-    let mut lo = reserve;
-    let mut hi = target.max(reserve);
+    let mut rng = StdRng::seed_from_u64(0x7421);
+    let profiles: Vec<(f64, f64)> = (0..SEARCH_PROFILES)
+        .map(|_| {
+            let (mut top, mut second) = (0.0_f64, 0.0_f64);
+            for _ in 0..n {
+                let v = dist.sample(&mut rng);
+                if v > top {
+                    second = top;
+                    top = v;
+                } else if v > second {
+                    second = v;
+                }
+            }
+            (top, second)
+        })
+        .collect();
+    let max_top = profiles.iter().map(|p| p.0).fold(reserve, f64::max);
+
+    // For each candidate shill bid: mean price uplift when revealed, and withholding probability.
+    let total = SEARCH_PROFILES as f64;
+    let candidates: Vec<(f64, f64)> = (1..=SEARCH_BIDS)
+        .map(|i| {
+            let bid = reserve + (max_top - reserve) * i as f64 / SEARCH_BIDS as f64;
+            let (mut uplift, mut withheld) = (0.0, 0usize);
+            for &(top, second) in &profiles {
+                if top >= bid {
+                    uplift += (bid - reserve.max(second)).max(0.0);
+                } else {
+                    withheld += 1;
+                }
+            }
+            (uplift / total, withheld as f64 / total)
+        })
+        .collect();
+    let profit = |collateral: f64| {
+        candidates
+            .iter()
+            .map(|(uplift, withheld)| uplift - collateral * withheld)
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+
+    let mut lo = 0.0;
+    let mut hi = collateral_requirement(n, dist, alpha).max(reserve).max(1.0);
     for _ in 0..64 {
-        let mid = 0.5 * (lo + hi);
-        if (mid - target).abs() <= tol {
-            return mid;
+        if profit(hi) <= 0.0 {
+            break;
+        }
+        hi *= 2.0;
+    }
+    for _ in 0..200 {
+        if hi - lo <= tol {
+            break;
         }
-        if mid < target {
+        let mid = 0.5 * (lo + hi);
+        if profit(mid) > 0.0 {
             lo = mid;
         } else {
             hi = mid;
@@ -40,16 +186,211 @@ pub fn numeric_collateral_search<D: ValueDistribution>(
     hi
 }
 
+/// Shill bids tried by [`safe_deviation_gain`], evenly spaced in quantile above the reserve.
+const BOUND_BIDS: usize = 2048;
+
+/// Expected profit of the best single-shill deviation against `collateral`, in closed form.
+///
+/// The deviation is the one [`numeric_collateral_search`] samples: a shill at `b >= r` is
+/// revealed when the top real bid reaches `b`, raising the price from `max(r, Y2)` to `b`, and
+/// is otherwise withheld at a cost of `c`. With `F` the value cdf its profit is
+/// \(\max_b\, n(1-F(b))\int_r^b F(t)^{n-1}\,dt - c\,F(b)^n\),
+/// integrated here over a quantile grid. Zero or below means no such shill pays.
+pub fn safe_deviation_gain<D: ValueDistribution>(n: usize, dist: &D, collateral: f64) -> f64 {
+    assert!(n > 0, "number of buyers must be positive");
+    let power = n as i32;
+    let reserve = dist.reserve_price();
+    let (mut bid, mut q) = (reserve, dist.cdf(reserve));
+    let q_reserve = q;
+    // Running \int_r^b F(t)^{n-1} dt.
+    let mut integral = 0.0;
+    let mut best = -collateral * q.powi(power);
+    for i in 1..BOUND_BIDS {
+        let target = q_reserve + (1.0 - q_reserve) * i as f64 / BOUND_BIDS as f64;
+        let next_bid = quantile_above(dist, target, bid);
+        let next_q = dist.cdf(next_bid);
+        integral += 0.5 * (q.powi(power - 1) + next_q.powi(power - 1)) * (next_bid - bid);
+        (bid, q) = (next_bid, next_q);
+        best = best.max(n as f64 * (1.0 - q) * integral - collateral * q.powi(power));
+    }
+    best
+}
+
+/// Lemma 18/21 check in closed form: [`safe_deviation_gain`] against the Theorem 21
+/// collateral. It is non-positive whenever that collateral deters every such shill.
+///
+/// # Panics
+/// Under the same conditions as [`collateral_requirement`].
+pub fn theoretical_safe_bound<D: ValueDistribution>(dist: &D, alpha: f64, buyers: usize) -> f64 {
+    safe_deviation_gain(buyers, dist, collateral_requirement(buyers, dist, alpha))
+}
+
+/// Smallest `x >= lo` with `F(x) >= q`, by bracketing and bisection.
+fn quantile_above<D: ValueDistribution>(dist: &D, q: f64, lo: f64) -> f64 {
+    let (mut lo, mut hi) = (lo, lo + lo.abs().max(1.0));
+    for _ in 0..64 {
+        if dist.cdf(hi) >= q {
+            break;
+        }
+        (lo, hi) = (hi, hi + 2.0 * (hi - lo));
+    }
+    for _ in 0..80 {
+        let mid = 0.5 * (lo + hi);
+        if dist.cdf(mid) >= q {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::distribution::Exponential;
+    use crate::distribution::{DynValueDistribution, Exponential, Pareto, Uniform};
+
+    /// Root of the closed-form [`safe_deviation_gain`]: the exact threshold for `dist`.
+    fn closed_form_threshold<D: ValueDistribution>(n: usize, dist: &D) -> f64 {
+        let (mut lo, mut hi) = (0.0, 100.0);
+        for _ in 0..60 {
+            let mid = 0.5 * (lo + hi);
+            if safe_deviation_gain(n, dist, mid) > 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        hi
+    }
 
     #[test]
-    fn numeric_search_matches_closed_form() {
+    fn numeric_search_matches_the_closed_form_threshold() {
+        // The sampled search must land on the threshold the closed-form profit integral gives
+        // for the distribution itself, and under Theorem 21's worst case, at every alpha.
+        for (dist, alphas) in [
+            (
+                Box::new(Exponential::new(1.0)) as Box<dyn DynValueDistribution>,
+                vec![0.25, 0.5, 0.75, 1.0],
+            ),
+            (Box::new(Uniform::new(0.0, 1.0)), vec![0.5, 1.0, 2.0]),
+        ] {
+            let exact = closed_form_threshold(3, &dist);
+            for alpha in alphas {
+                let searched = numeric_collateral_search(3, &dist, alpha, 1e-6);
+                assert!(
+                    (searched - exact).abs() <= 0.05 * exact,
+                    "alpha {alpha}: searched {searched}, closed form {exact}"
+                );
+                let theorem = collateral_requirement(3, &dist, alpha);
+                assert!(
+                    searched <= theorem + 1e-6,
+                    "alpha {alpha}: {searched} > {theorem}"
+                );
+            }
+        }
+    }
+
+    /// Uniform on [0, 1] with its reserve forced to zero, so Theorem 21 asks for no collateral.
+    #[derive(Clone)]
+    struct ZeroReserve;
+
+    impl ValueDistribution for ZeroReserve {
+        fn cdf(&self, x: f64) -> f64 {
+            x.clamp(0.0, 1.0)
+        }
+
+        fn pdf(&self, x: f64) -> f64 {
+            if (0.0..=1.0).contains(&x) { 1.0 } else { 0.0 }
+        }
+
+        fn reserve_price(&self) -> f64 {
+            0.0
+        }
+
+        fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+            rng.gen_range(0.0..1.0)
+        }
+    }
+
+    #[test]
+    fn numeric_search_terminates_when_the_closed_form_is_zero() {
+        assert_eq!(collateral_requirement(3, &ZeroReserve, 1.0), 0.0);
+        let searched = numeric_collateral_search(3, &ZeroReserve, 1.0, 1e-9);
+        assert!(searched.is_finite() && searched > 0.0);
+    }
+
+    #[test]
+    fn more_shills_never_need_less_collateral() {
         let dist = Exponential::new(1.0);
-        let closed = collateral_requirement(3, &dist, 0.75);
-        let numeric = numeric_collateral_search(3, &dist, 0.75, 1e-9);
-        assert!((closed - numeric).abs() < 1e-6);
+        for alpha in [0.5, 1.0] {
+            assert_eq!(
+                collateral_requirement_with_shills(3, 1, &dist, alpha),
+                collateral_requirement(3, &dist, alpha)
+            );
+            let per_count: Vec<f64> = (1..6)
+                .map(|k| collateral_requirement_with_shills(3, k, &dist, alpha))
+                .collect();
+            assert!(per_count.windows(2).all(|w| w[1] >= w[0]));
+        }
+        assert!(
+            collateral_requirement_with_shills(3, 4, &dist, 0.5)
+                > collateral_requirement(3, &dist, 0.5)
+        );
+    }
+
+    #[test]
+    fn alpha_beyond_strong_regularity_is_rejected() {
+        let heavy = Pareto::new(1.0, 0.5);
+        assert!(matches!(
+            try_collateral_requirement(2, &heavy, 1.0),
+            Err(ValidationError::AlphaTooLarge { requested, supported })
+                if requested == 1.0 && supported == 0.0
+        ));
+        let pareto = Pareto::new(1.0, 3.0);
+        let err = try_collateral_requirement(2, &pareto, 1.0).unwrap_err();
+        assert!(matches!(
+            err,
+            ValidationError::AlphaTooLarge { requested, .. } if requested == 1.0
+        ));
+        assert!(try_collateral_requirement(2, &pareto, 0.5).is_ok());
+        assert!(matches!(
+            try_collateral_requirement(0, &pareto, 0.5),
+            Err(ValidationError::InsufficientBuyers)
+        ));
+        assert!(matches!(
+            try_collateral_requirement(2, &pareto, 0.0),
+            Err(ValidationError::NonPositiveAlpha(_))
+        ));
+    }
+
+    #[test]
+    fn breakdown_factors_multiply_to_the_requirement() {
+        let dist = Exponential::new(0.5);
+        for alpha in [0.25, 0.5, 0.9] {
+            let parts = collateral_breakdown(4, &dist, alpha);
+            assert_eq!(parts.total, collateral_requirement(4, &dist, alpha));
+            assert!(parts.n_term > 1.0 && parts.hazard_term > 1.0);
+            assert_eq!(parts.reserve, dist.reserve_price());
+        }
+        let parts = collateral_breakdown(4, &dist, 1.0);
+        assert_eq!((parts.n_term, parts.hazard_term), (1.0, 1.0));
+        assert_eq!(parts.total, collateral_requirement(4, &dist, 1.0));
+    }
+
+    #[test]
+    fn theorem_21_collateral_leaves_no_profitable_shill() {
+        let uniform = Uniform::new(0.0, 10.0);
+        let exponential = Exponential::new(1.0);
+        for buyers in 1..5 {
+            let bound = theoretical_safe_bound(&uniform, 1.0, buyers);
+            assert!(bound <= 0.0, "uniform, {buyers} buyers: {bound}");
+            for alpha in [0.5, 1.0] {
+                let bound = theoretical_safe_bound(&exponential, alpha, buyers);
+                assert!(bound <= 0.0, "exponential, alpha {alpha}: {bound}");
+            }
+        }
+        // Without collateral, a shill revealed only when it raises the price is pure profit.
+        assert!(safe_deviation_gain(3, &uniform, 0.0) > 0.0);
     }
 }
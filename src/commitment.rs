@@ -1,30 +1,160 @@
-use std::{fmt, sync::{Arc, Mutex}};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 use blake3::Hasher;
-use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
+use bulletproofs::{BulletproofGens, PedersenGens, ProofError, RangeProof};
 use curve25519_dalek::{
     constants::RISTRETTO_BASEPOINT_POINT,
     ristretto::{CompressedRistretto, RistrettoPoint},
     scalar::Scalar,
+    traits::Identity,
 };
 use merlin::Transcript;
 use rand::{RngCore, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 pub const SALT_BYTES: usize = 32;
 pub const BID_BYTES: usize = 16;
 pub const BID_SCALE: f64 = 1_000_000.0;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Fixed-point codec mapping a bid to an integer: `round(bid * scale)`, which must fit in a
+/// signed integer of `bytes` bytes. The default keeps six decimal places in 16 bytes.
+///
+/// Signed codecs additionally accept negative bids (e.g. procurement costs). The integer is
+/// stored as a two's-complement `i128`, so when mapped into the scalar field a negative value
+/// `v` lands at the offset `2^128 + v`, which never collides with a non-negative encoding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BidCodec {
+    pub scale: f64,
+    pub bytes: usize,
+    pub signed: bool,
+}
+
+impl BidCodec {
+    pub fn new(scale: f64, bytes: usize) -> Self {
+        assert!(scale.is_finite() && scale > 0.0, "scale must be positive");
+        assert!(
+            (1..=BID_BYTES).contains(&bytes),
+            "codec width must be between 1 and {} bytes",
+            BID_BYTES
+        );
+        Self {
+            scale,
+            bytes,
+            signed: false,
+        }
+    }
+
+    /// Same as [`BidCodec::new`] but accepting negative bids.
+    pub fn signed(scale: f64, bytes: usize) -> Self {
+        Self {
+            signed: true,
+            ..Self::new(scale, bytes)
+        }
+    }
+
+    fn limit(&self) -> f64 {
+        2f64.powi((8 * self.bytes - 1) as i32)
+    }
+}
+
+impl Default for BidCodec {
+    fn default() -> Self {
+        Self::new(BID_SCALE, BID_BYTES)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BidEncoding([u8; BID_BYTES]);
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BidEncodingError {
+    NonFinite,
+    Negative,
+    Overflow,
+}
+
+/// Why a bid could not be committed under a particular scheme.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CommitError {
+    Encoding(BidEncodingError),
+    /// The bid encodes fine but exceeds what the scheme's range proof can cover.
+    OutOfRange {
+        bid: f64,
+        max: f64,
+    },
+}
+
+impl fmt::Display for BidEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BidEncodingError::NonFinite => f.write_str("bid is not finite"),
+            BidEncodingError::Negative => f.write_str("bid is negative"),
+            BidEncodingError::Overflow => f.write_str("bid is too large to encode"),
+        }
+    }
+}
+
+impl std::error::Error for BidEncodingError {}
+
+impl fmt::Display for CommitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitError::Encoding(err) => write!(f, "cannot encode bid: {err}"),
+            CommitError::OutOfRange { bid, max } => {
+                write!(f, "bid {bid} exceeds the range proof's maximum {max}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CommitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CommitError::Encoding(err) => Some(err),
+            CommitError::OutOfRange { .. } => None,
+        }
+    }
+}
+
+impl From<BidEncodingError> for CommitError {
+    fn from(err: BidEncodingError) -> Self {
+        CommitError::Encoding(err)
+    }
+}
+
 impl BidEncoding {
-    pub fn new(bid: f64) -> Self {
-        assert!(bid.is_finite(), "bid must be finite");
-        assert!(bid >= 0.0, "bid must be non-negative");
-        let scaled = (bid * BID_SCALE).round();
-        let scaled_i = scaled as i128;
-        BidEncoding(scaled_i.to_le_bytes())
+    pub fn new(bid: f64, codec: &BidCodec) -> Self {
+        match Self::try_new(bid, codec) {
+            Ok(encoding) => encoding,
+            Err(BidEncodingError::NonFinite) => panic!("bid must be finite"),
+            Err(BidEncodingError::Negative) => panic!("bid must be non-negative"),
+            Err(BidEncodingError::Overflow) => panic!("bid exceeds the encoding range"),
+        }
+    }
+
+    pub fn try_new(bid: f64, codec: &BidCodec) -> Result<Self, BidEncodingError> {
+        if !bid.is_finite() {
+            return Err(BidEncodingError::NonFinite);
+        }
+        if bid < 0.0 && !codec.signed {
+            return Err(BidEncodingError::Negative);
+        }
+        let scaled = (bid * codec.scale).round();
+        if scaled >= codec.limit() || scaled < -codec.limit() {
+            return Err(BidEncodingError::Overflow);
+        }
+        Ok(BidEncoding((scaled as i128).to_le_bytes()))
+    }
+
+    /// Recover the (rounded) bid this encoding represents under `codec`.
+    pub fn decode(&self, codec: &BidCodec) -> f64 {
+        self.as_i128() as f64 / codec.scale
     }
 
     pub fn as_bytes(&self) -> &[u8; BID_BYTES] {
@@ -36,23 +166,31 @@ impl BidEncoding {
     }
 
     pub fn as_u64(&self) -> u64 {
-        let value = self.as_i128();
-        assert!(
-            value >= 0,
-            "bid encoding must be non-negative to map into u64"
-        );
-        assert!(
-            value <= u64::MAX as i128,
-            "bid encoding exceeds u64 range for bulletproof backend"
-        );
-        value as u64
+        match self.try_as_u64() {
+            Ok(value) => value,
+            Err(BidEncodingError::Negative) => {
+                panic!("bid encoding must be non-negative to map into u64")
+            }
+            Err(_) => panic!("bid encoding exceeds u64 range for bulletproof backend"),
+        }
+    }
+
+    /// Map into the unsigned range used by the Bulletproofs backend, rejecting negatives.
+    pub fn try_as_u64(&self) -> Result<u64, BidEncodingError> {
+        u64::try_from(self.as_i128()).map_err(|_| {
+            if self.as_i128() < 0 {
+                BidEncodingError::Negative
+            } else {
+                BidEncodingError::Overflow
+            }
+        })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Commitment(pub [u8; 32]);
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Opening {
     pub bid: f64,
     pub encoding: BidEncoding,
@@ -63,25 +201,115 @@ pub struct Opening {
     pub bulletproof: Option<BulletproofProofData>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl Opening {
+    /// Bytes needed to transmit this opening: the encoded bid, salt and mask, plus whatever
+    /// proof material is attached.
+    pub fn wire_size(&self) -> usize {
+        let fischlin = self.proof.as_ref().map_or(0, |_| 3 * 32);
+        let receipt = self
+            .audit_receipt
+            .as_ref()
+            .map_or(0, |r| 2 * 8 + 32 + 32 + 32 * r.path.len());
+        let bulletproof = self.bulletproof.as_ref().map_or(0, |bp| {
+            let aggregate = bp.aggregate.as_ref().map_or(0, |_| 8 + 32);
+            bp.proof.len() + 32 + 8 + aggregate
+        });
+        BID_BYTES + 2 * SALT_BYTES + fischlin + receipt + bulletproof
+    }
+}
+
+/// On-wire cost of a run: every published commitment plus every opening that was revealed,
+/// and the shared proofs those openings refer to, each counted once.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeReport {
+    pub commitment_bytes_total: usize,
+    pub opening_bytes_total: usize,
+    #[serde(default)]
+    pub shared_proof_bytes_total: usize,
+}
+
+impl SizeReport {
+    /// Everything a run put on the wire for commitments and their proofs.
+    pub fn proof_bytes_total(&self) -> usize {
+        self.commitment_bytes_total + self.opening_bytes_total + self.shared_proof_bytes_total
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FischlinProof {
     pub challenge: [u8; 32],
     pub response_blind: [u8; 32],
     pub response_message: [u8; 32],
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AuditReceipt {
     pub index: usize,
+    /// Entries in the ledger when `root` and `path` were taken.
+    pub tree_size: usize,
     pub root: [u8; 32],
     pub entry_hash: [u8; 32],
+    pub path: Vec<[u8; 32]>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BulletproofProofData {
     pub proof: Vec<u8>,
     pub blinding: [u8; 32],
     pub range_bits: usize,
+    pub aggregate: Option<AggregateMembership>,
+}
+
+/// Position of a commitment inside an aggregated range proof, which is published once as a
+/// [`SharedRangeProof`] rather than copied into every opening.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateMembership {
+    pub proof_digest: [u8; 32],
+    pub position: usize,
+}
+
+/// One aggregated range proof shared by a batch of openings. `commitments` lists one point per
+/// bid; the zero-valued padding up to a power of two is implied.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedRangeProof {
+    pub proof: Vec<u8>,
+    pub range_bits: usize,
+    pub commitments: Vec<[u8; 32]>,
+}
+
+impl SharedRangeProof {
+    /// What member openings refer to this proof by.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"DRA-SHARED-RANGE-PROOF");
+        hasher.update((self.range_bits as u64).to_le_bytes());
+        hasher.update((self.commitments.len() as u64).to_le_bytes());
+        for commitment in self.commitments.iter() {
+            hasher.update(commitment);
+        }
+        hasher.update(&self.proof);
+        hasher.finalize().into()
+    }
+
+    pub fn wire_size(&self) -> usize {
+        self.proof.len() + 8 + 32 * self.commitments.len()
+    }
+}
+
+impl AuditReceipt {
+    /// Check the Merkle path against a published root without access to the ledger that
+    /// produced it. The root must be the ledger's at `tree_size` entries: a receipt from
+    /// [`AuditLedger::log_entry`] only matches the root at that moment, so re-issue it with
+    /// [`AuditLedger::reissue`] before checking it against a later one.
+    pub fn verify_against_root(&self, root: [u8; 32]) -> bool {
+        self.root == root
+            && merkle_root_from_path(
+                &merkle_leaf(&self.entry_hash),
+                self.index,
+                self.tree_size,
+                &self.path,
+            ) == Some(root)
+    }
 }
 
 impl FischlinProof {
@@ -109,18 +337,334 @@ impl BulletproofProofData {
 }
 
 pub trait CommitmentScheme {
-    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening);
-    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool;
+    /// Commit using a caller-chosen fixed-point codec.
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening);
+
+    /// Verify an opening produced under `codec`.
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool;
+
+    /// Check that `bid` can be committed under `codec` without panicking.
+    fn check_bid(&self, bid: f64, codec: &BidCodec) -> Result<BidEncoding, CommitError> {
+        Ok(BidEncoding::try_new(bid, codec)?)
+    }
+
+    fn try_commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Result<(Commitment, Opening), CommitError> {
+        self.check_bid(bid, codec)?;
+        Ok(self.commit_with_codec(bid, codec, rng))
+    }
+
+    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening) {
+        self.commit_with_codec(bid, &BidCodec::default(), rng)
+    }
+
+    /// Size in bytes of a published commitment.
+    fn commitment_size(&self) -> usize {
+        32
+    }
+
+    /// Bring proof material that depends on later commitments up to date just before
+    /// `opening` is revealed. Only the audited backend's ledger receipts need this.
+    fn refresh_opening(&self, _opening: &mut Opening) {}
+
+    /// Proofs that `openings` refer to instead of embedding, to be published once alongside
+    /// them. Only aggregated Bulletproofs openings have any.
+    fn shared_proofs(&self, _openings: &[&Opening]) -> Vec<SharedRangeProof> {
+        Vec::new()
+    }
+
+    /// Accept shared proofs published with a transcript, so a scheme instance that did not
+    /// produce the openings can verify them.
+    fn import_shared_proofs(&self, _proofs: &[SharedRangeProof]) {}
+
+    /// Size in bytes of `opening` as this scheme would transmit it.
+    fn opening_size(&self, opening: &Opening) -> usize {
+        opening.wire_size()
+    }
+
+    fn try_commit<R: RngCore>(
+        &self,
+        bid: f64,
+        rng: &mut R,
+    ) -> Result<(Commitment, Opening), CommitError> {
+        self.try_commit_with_codec(bid, &BidCodec::default(), rng)
+    }
+
+    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool {
+        self.verify_with_codec(commitment, opening, &BidCodec::default())
+    }
+
+    /// Commit to several bids at once under `codec`, in order. Schemes with aggregatable
+    /// proofs override this.
+    fn commit_batch_with_codec<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Vec<(Commitment, Opening)> {
+        bids.iter()
+            .map(|bid| self.commit_with_codec(*bid, codec, rng))
+            .collect()
+    }
+
+    fn commit_batch<R: RngCore>(&self, bids: &[f64], rng: &mut R) -> Vec<(Commitment, Opening)> {
+        self.commit_batch_with_codec(bids, &BidCodec::default(), rng)
+    }
+
+    /// Fallible [`CommitmentScheme::commit_batch_with_codec`]: every bid is checked before any
+    /// is committed.
+    fn try_commit_batch_with_codec<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Result<Vec<(Commitment, Opening)>, CommitError> {
+        for bid in bids {
+            self.check_bid(*bid, codec)?;
+        }
+        Ok(self.commit_batch_with_codec(bids, codec, rng))
+    }
+
+    fn try_commit_batch<R: RngCore>(
+        &self,
+        bids: &[f64],
+        rng: &mut R,
+    ) -> Result<Vec<(Commitment, Opening)>, CommitError> {
+        self.try_commit_batch_with_codec(bids, &BidCodec::default(), rng)
+    }
+
+    /// Verify every pair under `codec`; true only if all openings match their commitments.
+    fn verify_batch_with_codec(&self, pairs: &[(&Commitment, &Opening)], codec: &BidCodec) -> bool {
+        pairs
+            .iter()
+            .all(|(commitment, opening)| self.verify_with_codec(commitment, opening, codec))
+    }
+
+    fn verify_batch(&self, pairs: &[(&Commitment, &Opening)]) -> bool {
+        self.verify_batch_with_codec(pairs, &BidCodec::default())
+    }
+}
+
+/// Object-safe view of [`CommitmentScheme`] so backends can be chosen at runtime. Every
+/// cloneable, thread-safe scheme implements it; `Box<dyn DynCommitmentScheme>` is itself a
+/// `CommitmentScheme`, so it drops into the generic auction and simulation entry points.
+pub trait DynCommitmentScheme: fmt::Debug + Send + Sync {
+    fn commit_dyn(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut dyn RngCore,
+    ) -> (Commitment, Opening);
+    fn verify_dyn(&self, commitment: &Commitment, opening: &Opening, codec: &BidCodec) -> bool;
+    fn check_bid_dyn(&self, bid: f64, codec: &BidCodec) -> Result<BidEncoding, CommitError>;
+    fn commit_batch_dyn(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut dyn RngCore,
+    ) -> Vec<(Commitment, Opening)>;
+    fn verify_batch_dyn(&self, pairs: &[(&Commitment, &Opening)], codec: &BidCodec) -> bool;
+    fn commitment_size_dyn(&self) -> usize;
+    fn opening_size_dyn(&self, opening: &Opening) -> usize;
+    fn refresh_opening_dyn(&self, opening: &mut Opening);
+    fn shared_proofs_dyn(&self, openings: &[&Opening]) -> Vec<SharedRangeProof>;
+    fn import_shared_proofs_dyn(&self, proofs: &[SharedRangeProof]);
+    fn clone_box(&self) -> Box<dyn DynCommitmentScheme>;
+}
+
+impl<S: CommitmentScheme + Clone + fmt::Debug + Send + Sync + 'static> DynCommitmentScheme for S {
+    fn commit_dyn(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        mut rng: &mut dyn RngCore,
+    ) -> (Commitment, Opening) {
+        self.commit_with_codec(bid, codec, &mut rng)
+    }
+
+    fn verify_dyn(&self, commitment: &Commitment, opening: &Opening, codec: &BidCodec) -> bool {
+        self.verify_with_codec(commitment, opening, codec)
+    }
+
+    fn check_bid_dyn(&self, bid: f64, codec: &BidCodec) -> Result<BidEncoding, CommitError> {
+        self.check_bid(bid, codec)
+    }
+
+    fn commit_batch_dyn(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        mut rng: &mut dyn RngCore,
+    ) -> Vec<(Commitment, Opening)> {
+        self.commit_batch_with_codec(bids, codec, &mut rng)
+    }
+
+    fn verify_batch_dyn(&self, pairs: &[(&Commitment, &Opening)], codec: &BidCodec) -> bool {
+        self.verify_batch_with_codec(pairs, codec)
+    }
+
+    fn commitment_size_dyn(&self) -> usize {
+        self.commitment_size()
+    }
+
+    fn opening_size_dyn(&self, opening: &Opening) -> usize {
+        self.opening_size(opening)
+    }
+
+    fn refresh_opening_dyn(&self, opening: &mut Opening) {
+        self.refresh_opening(opening)
+    }
+
+    fn shared_proofs_dyn(&self, openings: &[&Opening]) -> Vec<SharedRangeProof> {
+        self.shared_proofs(openings)
+    }
+
+    fn import_shared_proofs_dyn(&self, proofs: &[SharedRangeProof]) {
+        self.import_shared_proofs(proofs)
+    }
+
+    fn clone_box(&self) -> Box<dyn DynCommitmentScheme> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynCommitmentScheme> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl CommitmentScheme for Box<dyn DynCommitmentScheme> {
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
+        self.as_ref().commit_dyn(bid, codec, rng)
+    }
+
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        self.as_ref().verify_dyn(commitment, opening, codec)
+    }
+
+    fn check_bid(&self, bid: f64, codec: &BidCodec) -> Result<BidEncoding, CommitError> {
+        self.as_ref().check_bid_dyn(bid, codec)
+    }
+
+    fn commit_batch_with_codec<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Vec<(Commitment, Opening)> {
+        self.as_ref().commit_batch_dyn(bids, codec, rng)
+    }
+
+    fn verify_batch_with_codec(&self, pairs: &[(&Commitment, &Opening)], codec: &BidCodec) -> bool {
+        self.as_ref().verify_batch_dyn(pairs, codec)
+    }
+
+    fn commitment_size(&self) -> usize {
+        self.as_ref().commitment_size_dyn()
+    }
+
+    fn opening_size(&self, opening: &Opening) -> usize {
+        self.as_ref().opening_size_dyn(opening)
+    }
+
+    fn refresh_opening(&self, opening: &mut Opening) {
+        self.as_ref().refresh_opening_dyn(opening)
+    }
+
+    fn shared_proofs(&self, openings: &[&Opening]) -> Vec<SharedRangeProof> {
+        self.as_ref().shared_proofs_dyn(openings)
+    }
+
+    fn import_shared_proofs(&self, proofs: &[SharedRangeProof]) {
+        self.as_ref().import_shared_proofs_dyn(proofs)
+    }
+}
+
+pub type SchemeFactory = fn() -> Box<dyn DynCommitmentScheme>;
+
+/// Name-indexed constructors for commitment backends. The default registry knows every
+/// built-in scheme; callers add their own with [`SchemeRegistry::register`].
+#[derive(Clone, Debug)]
+pub struct SchemeRegistry {
+    factories: BTreeMap<String, SchemeFactory>,
+}
+
+impl SchemeRegistry {
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Add or replace the backend known as `name`.
+    pub fn register(&mut self, name: &str, factory: SchemeFactory) {
+        self.factories.insert(name.to_string(), factory);
+    }
+
+    pub fn create(&self, name: &str) -> Option<Box<dyn DynCommitmentScheme>> {
+        self.factories.get(name).map(|factory| factory())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+impl Default for SchemeRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("sha", || Box::new(NonMalleableShaCommitment));
+        registry.register("blake3", || Box::new(Blake3Commitment));
+        registry.register("pedersen", || Box::new(PedersenRistrettoCommitment));
+        registry.register("fischlin", || Box::new(RealNonMalleableCommitment));
+        registry.register("audited", || {
+            Box::new(AuditedNonMalleableCommitment::default())
+        });
+        registry.register("bulletproofs", || {
+            Box::new(BulletproofsCommitment::default())
+        });
+        registry
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct NonMalleableShaCommitment;
 
 impl CommitmentScheme for NonMalleableShaCommitment {
-    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening) {
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
         let salt = random_bytes(rng);
         let mask = random_bytes(rng);
-        let encoding = BidEncoding::new(bid);
+        let encoding = BidEncoding::new(bid, codec);
         let commitment = hash_commitment(&encoding, &salt, &mask);
         (
             commitment,
@@ -136,10 +680,54 @@ impl CommitmentScheme for NonMalleableShaCommitment {
         )
     }
 
-    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool {
-        let encoding = BidEncoding::new(opening.bid);
-        encoding == opening.encoding
-            && *commitment == hash_commitment(&encoding, &opening.salt, &opening.mask)
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        BidEncoding::try_new(opening.bid, codec).ok() == Some(opening.encoding)
+            && *commitment == hash_commitment(&opening.encoding, &opening.salt, &opening.mask)
+    }
+}
+
+/// Hash commitment over BLAKE3 instead of SHA-256, friendlier to later circuit embedding.
+#[derive(Clone, Debug, Default)]
+pub struct Blake3Commitment;
+
+impl CommitmentScheme for Blake3Commitment {
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
+        let salt = random_bytes(rng);
+        let mask = random_bytes(rng);
+        let encoding = BidEncoding::new(bid, codec);
+        let commitment = blake3_commitment(&encoding, &salt, &mask);
+        (
+            commitment,
+            Opening {
+                bid,
+                encoding,
+                salt,
+                mask,
+                proof: None,
+                audit_receipt: None,
+                bulletproof: None,
+            },
+        )
+    }
+
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        BidEncoding::try_new(opening.bid, codec).ok() == Some(opening.encoding)
+            && *commitment == blake3_commitment(&opening.encoding, &opening.salt, &opening.mask)
     }
 }
 
@@ -147,10 +735,15 @@ impl CommitmentScheme for NonMalleableShaCommitment {
 pub struct PedersenRistrettoCommitment;
 
 impl CommitmentScheme for PedersenRistrettoCommitment {
-    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening) {
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
         let salt = random_bytes(rng);
         let mask = random_bytes(rng);
-        let encoding = BidEncoding::new(bid);
+        let encoding = BidEncoding::new(bid, codec);
         let point = pedersen_point(&encoding, &salt, &mask);
         (
             Commitment(point.compress().to_bytes()),
@@ -166,8 +759,13 @@ impl CommitmentScheme for PedersenRistrettoCommitment {
         )
     }
 
-    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool {
-        if BidEncoding::new(opening.bid) != opening.encoding {
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        if BidEncoding::try_new(opening.bid, codec).ok() != Some(opening.encoding) {
             return false;
         }
         let Some(point) = decompress_point(commitment) else {
@@ -182,14 +780,19 @@ impl CommitmentScheme for PedersenRistrettoCommitment {
 pub struct RealNonMalleableCommitment;
 
 impl CommitmentScheme for RealNonMalleableCommitment {
-    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening) {
-        let encoding = BidEncoding::new(bid);
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
+        let encoding = BidEncoding::new(bid, codec);
         let salt = random_bytes(rng);
         let mask = random_bytes(rng);
         let blind = hash_to_scalar(&salt);
         let message_scalar = scalar_from_encoding(&encoding);
         let point = blind * RISTRETTO_BASEPOINT_POINT + message_scalar * derive_h_point();
-        let proof = build_fischlin_proof(&point, blind, message_scalar, &mask, &encoding);
+        let proof = build_fischlin_proof(&point, blind, message_scalar, &salt, &mask, &encoding);
         (
             Commitment(point.compress().to_bytes()),
             Opening {
@@ -204,8 +807,13 @@ impl CommitmentScheme for RealNonMalleableCommitment {
         )
     }
 
-    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool {
-        if BidEncoding::new(opening.bid) != opening.encoding {
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        if BidEncoding::try_new(opening.bid, codec).ok() != Some(opening.encoding) {
             return false;
         }
         let Some(proof) = opening.proof.as_ref() else {
@@ -214,21 +822,75 @@ impl CommitmentScheme for RealNonMalleableCommitment {
         let Some(point) = decompress_point(commitment) else {
             return false;
         };
+        // The transcript alone decides: it proves knowledge of the blind for the revealed
+        // bid, and its challenge binds the revealed salt and mask.
         verify_fischlin_proof(
             &point,
             proof,
             &opening.encoding,
-            hash_to_scalar(&opening.salt),
-            scalar_from_encoding(&opening.encoding),
+            &opening.salt,
+            &opening.mask,
         )
     }
 }
 
-#[derive(Clone)]
 pub struct BulletproofsCommitment {
     pedersen: PedersenGens,
     generators: BulletproofGens,
     range_bits: usize,
+    shared: Mutex<SharedProofCache>,
+}
+
+/// Clones start with an empty proof cache: a copy used as an independent verifier learns
+/// shared proofs only through [`CommitmentScheme::import_shared_proofs`].
+impl Clone for BulletproofsCommitment {
+    fn clone(&self) -> Self {
+        Self {
+            pedersen: self.pedersen,
+            generators: self.generators.clone(),
+            range_bits: self.range_bits,
+            shared: Mutex::default(),
+        }
+    }
+}
+
+/// Shared proofs kept by one scheme instance.
+const SHARED_PROOF_CAPACITY: usize = 64;
+
+/// A published aggregate proof, verified at most once however many openings refer to it.
+#[derive(Debug)]
+struct SharedProofEntry {
+    proof: SharedRangeProof,
+    verified: Option<bool>,
+}
+
+/// The shared proofs a scheme has made or imported. Once [`SHARED_PROOF_CAPACITY`] are held
+/// the oldest is evicted, so a long simulation does not grow it without bound.
+#[derive(Debug, Default)]
+struct SharedProofCache {
+    entries: BTreeMap<[u8; 32], SharedProofEntry>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl SharedProofCache {
+    fn insert(&mut self, digest: [u8; 32], proof: SharedRangeProof) {
+        if self.entries.contains_key(&digest) {
+            return;
+        }
+        if self.order.len() == SHARED_PROOF_CAPACITY
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(
+            digest,
+            SharedProofEntry {
+                proof,
+                verified: None,
+            },
+        );
+        self.order.push_back(digest);
+    }
 }
 
 impl BulletproofsCommitment {
@@ -242,10 +904,272 @@ impl BulletproofsCommitment {
             pedersen: PedersenGens::default(),
             generators: BulletproofGens::new(range_bits, 1),
             range_bits,
+            shared: Mutex::default(),
+        }
+    }
+
+    /// Fallible constructor for user-supplied ranges; the proving system supports 8, 16, 32,
+    /// or 64 bits.
+    pub fn try_new(range_bits: usize) -> Result<Self, InvalidRangeBits> {
+        if !range_bits.is_power_of_two() || !(8..=64).contains(&range_bits) {
+            return Err(InvalidRangeBits {
+                requested: range_bits,
+            });
+        }
+        Ok(Self::new(range_bits))
+    }
+
+    /// Preallocate generators so up to `max_parties` bids share one aggregated range proof.
+    pub fn new_aggregated(range_bits: usize, max_parties: usize) -> Self {
+        assert!(
+            max_parties.is_power_of_two(),
+            "party capacity must be a power of two"
+        );
+        let mut scheme = Self::new(range_bits);
+        scheme.generators = BulletproofGens::new(range_bits, max_parties);
+        scheme
+    }
+
+    pub fn max_parties(&self) -> usize {
+        self.generators.party_capacity
+    }
+
+    /// Prove every bid in a single range proof using the preallocated party capacity.
+    pub fn commit_aggregated<R: RngCore>(
+        &self,
+        bids: &[f64],
+        rng: &mut R,
+    ) -> Result<AggregatedRangeProof, AggregationError> {
+        self.commit_aggregated_with_codec(bids, &BidCodec::default(), rng)
+    }
+
+    /// [`Self::commit_aggregated`] with bids encoded under `codec`.
+    pub fn commit_aggregated_with_codec<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Result<AggregatedRangeProof, AggregationError> {
+        if bids.is_empty() {
+            return Err(AggregationError::Empty);
+        }
+        if bids.len() > self.max_parties() {
+            return Err(AggregationError::TooManyParties {
+                requested: bids.len(),
+                capacity: self.max_parties(),
+            });
+        }
+        self.prove_aggregate(bids, codec, &self.generators, rng)
+    }
+
+    /// Check the shared proof once, then bind each opening to its commitment point. Every
+    /// point must come with an opening, so no committed bid can be withheld.
+    pub fn verify_aggregated(&self, aggregate: &AggregatedRangeProof) -> bool {
+        self.verify_aggregated_with_codec(aggregate, &BidCodec::default())
+    }
+
+    /// [`Self::verify_aggregated`] for bids encoded under `codec`.
+    pub fn verify_aggregated_with_codec(
+        &self,
+        aggregate: &AggregatedRangeProof,
+        codec: &BidCodec,
+    ) -> bool {
+        let Some(proof) = RangeProof::from_bytes(&aggregate.proof).ok() else {
+            return false;
+        };
+        let commitments: Vec<[u8; 32]> = aggregate.points.iter().map(|c| c.0).collect();
+        if aggregate.openings.len() != aggregate.points.len()
+            || !self.verify_aggregate(&proof, &commitments, aggregate.range_bits)
+        {
+            return false;
+        }
+        aggregate
+            .openings
+            .iter()
+            .zip(aggregate.points.iter())
+            .all(|(opening, commitment)| {
+                let (Some(bp), Some(point)) =
+                    (opening.bulletproof.as_ref(), decompress_point(commitment))
+                else {
+                    return false;
+                };
+                BidEncoding::try_new(opening.bid, codec).ok() == Some(opening.encoding)
+                    && self.binds(&point, opening, bp)
+            })
+    }
+
+    fn prove_aggregate<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        generators: &BulletproofGens,
+        rng: &mut R,
+    ) -> Result<AggregatedRangeProof, AggregationError> {
+        let parties = bids.len().next_power_of_two();
+        let encodings = bids
+            .iter()
+            .map(|bid| self.check_bid(*bid, codec))
+            .collect::<Result<Vec<BidEncoding>, CommitError>>()
+            .map_err(AggregationError::Bid)?;
+        let mut values: Vec<u64> = encodings.iter().map(BidEncoding::as_u64).collect();
+        values.resize(parties, 0);
+        let mut proof_rng = proving_rng(rng, b"DRA-BULLETPROOF-BATCH");
+        // Padding commits to zero with zero blinding, so verifiers can rebuild it themselves.
+        let mut blindings: Vec<Scalar> = (0..bids.len())
+            .map(|_| scalar_from_rng(&mut proof_rng))
+            .collect();
+        blindings.resize(parties, Scalar::ZERO);
+        let mut transcript = Transcript::new(b"DRA-BULLETPROOF-BATCH");
+        let (proof, points) = RangeProof::prove_multiple_with_rng(
+            generators,
+            &self.pedersen,
+            &mut transcript,
+            &values,
+            &blindings,
+            self.range_bits,
+            &mut proof_rng,
+        )
+        .map_err(AggregationError::Proving)?;
+        let shared = SharedRangeProof {
+            proof: proof.to_bytes(),
+            range_bits: self.range_bits,
+            commitments: points[..bids.len()].iter().map(|p| p.to_bytes()).collect(),
+        };
+        let proof_digest = self.publish_shared(shared.clone());
+        let openings = bids
+            .iter()
+            .zip(encodings)
+            .enumerate()
+            .map(|(position, (bid, encoding))| Opening {
+                bid: *bid,
+                encoding,
+                salt: [0u8; SALT_BYTES],
+                mask: [0u8; SALT_BYTES],
+                proof: None,
+                audit_receipt: None,
+                bulletproof: Some(BulletproofProofData {
+                    proof: Vec::new(),
+                    blinding: blindings[position].to_bytes(),
+                    range_bits: self.range_bits,
+                    aggregate: Some(AggregateMembership {
+                        proof_digest,
+                        position,
+                    }),
+                }),
+            })
+            .collect();
+        Ok(AggregatedRangeProof {
+            proof: shared.proof,
+            range_bits: self.range_bits,
+            points: shared.commitments.into_iter().map(Commitment).collect(),
+            openings,
+        })
+    }
+}
+
+/// One range proof covering a whole batch of bids. The proof bytes are stored once here;
+/// `points` holds one commitment per bid. The proof also covers zero-valued padding up to the
+/// next power of two, which is implied rather than stored.
+#[derive(Clone, Debug)]
+pub struct AggregatedRangeProof {
+    pub proof: Vec<u8>,
+    pub range_bits: usize,
+    pub points: Vec<Commitment>,
+    pub openings: Vec<Opening>,
+}
+
+impl AggregatedRangeProof {
+    /// Split into `(Commitment, Opening)` pairs. The openings refer to the proof by digest; the
+    /// scheme that made them already holds it, and others get it from [`Self::shared`].
+    pub fn into_entries(self) -> Vec<(Commitment, Opening)> {
+        self.points.into_iter().zip(self.openings).collect()
+    }
+
+    /// The proof in the form openings refer to, for publishing alongside them.
+    pub fn shared(&self) -> SharedRangeProof {
+        SharedRangeProof {
+            proof: self.proof.clone(),
+            range_bits: self.range_bits,
+            commitments: self.points.iter().map(|c| c.0).collect(),
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidRangeBits {
+    pub requested: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AggregationError {
+    Empty,
+    TooManyParties { requested: usize, capacity: usize },
+    Bid(CommitError),
+    Proving(ProofError),
+}
+
+impl BulletproofsCommitment {
+    fn generators_for(&self, parties: usize) -> Cow<'_, BulletproofGens> {
+        if parties <= self.generators.party_capacity {
+            Cow::Borrowed(&self.generators)
+        } else {
+            Cow::Owned(BulletproofGens::new(self.range_bits, parties))
+        }
+    }
+
+    fn binds(&self, point: &RistrettoPoint, opening: &Opening, bp: &BulletproofProofData) -> bool {
+        let Ok(value) = opening.encoding.try_as_u64() else {
+            return false;
+        };
+        self.pedersen.commit(Scalar::from(value), bp.blinding_scalar()) == *point
+    }
+
+    fn verify_aggregate(&self, proof: &RangeProof, commitments: &[[u8; 32]], bits: usize) -> bool {
+        let mut points: Vec<CompressedRistretto> = commitments
+            .iter()
+            .map(|bytes| CompressedRistretto(*bytes))
+            .collect();
+        points.resize(
+            commitments.len().next_power_of_two(),
+            CompressedRistretto::identity(),
+        );
+        let mut transcript = Transcript::new(b"DRA-BULLETPROOF-BATCH");
+        proof
+            .verify_multiple(
+                &self.generators_for(points.len()),
+                &self.pedersen,
+                &mut transcript,
+                &points,
+                bits,
+            )
+            .is_ok()
+    }
+
+    fn publish_shared(&self, proof: SharedRangeProof) -> [u8; 32] {
+        let digest = proof.digest();
+        let mut shared = self.shared.lock().expect("shared proofs poisoned");
+        shared.insert(digest, proof);
+        digest
+    }
+
+    /// The commitment `agg` points at, provided its shared proof is known and verifies. The
+    /// proof itself is checked only the first time any opening refers to it.
+    fn shared_member(&self, agg: &AggregateMembership) -> Option<[u8; 32]> {
+        let mut shared = self.shared.lock().expect("shared proofs poisoned");
+        let entry = shared.entries.get_mut(&agg.proof_digest)?;
+        let verified = *entry.verified.get_or_insert_with(|| {
+            let proof = &entry.proof;
+            RangeProof::from_bytes(&proof.proof).is_ok_and(|range_proof| {
+                self.verify_aggregate(&range_proof, &proof.commitments, proof.range_bits)
+            })
+        });
+        if !verified {
+            return None;
+        }
+        entry.proof.commitments.get(agg.position).copied()
+    }
+}
+
 impl Default for BulletproofsCommitment {
     fn default() -> Self {
         Self::new(64)
@@ -261,10 +1185,32 @@ impl fmt::Debug for BulletproofsCommitment {
 }
 
 impl CommitmentScheme for BulletproofsCommitment {
-    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening) {
-        let encoding = BidEncoding::new(bid);
+    fn check_bid(&self, bid: f64, codec: &BidCodec) -> Result<BidEncoding, CommitError> {
+        let encoding = BidEncoding::try_new(bid, codec)?;
+        let max = u64::MAX >> (64 - self.range_bits);
+        if !(0..=max as i128).contains(&encoding.as_i128()) {
+            return Err(CommitError::OutOfRange {
+                bid,
+                max: max as f64 / codec.scale,
+            });
+        }
+        Ok(encoding)
+    }
+
+    /// Salt and mask are always zero here, so they are not sent.
+    fn opening_size(&self, opening: &Opening) -> usize {
+        opening.wire_size() - 2 * SALT_BYTES
+    }
+
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
+        let encoding = BidEncoding::new(bid, codec);
         let mut transcript = Transcript::new(b"DRA-BULLETPROOF");
-        let mut proof_rng = StdRng::from_seed(random_bytes(rng));
+        let mut proof_rng = proving_rng(rng, b"DRA-BULLETPROOF");
         let blinding = scalar_from_rng(&mut proof_rng);
         let (proof, commitment_point) = RangeProof::prove_single_with_rng(
             &self.generators,
@@ -275,7 +1221,7 @@ impl CommitmentScheme for BulletproofsCommitment {
             self.range_bits,
             &mut proof_rng,
         )
-        .expect("bulletproof proving should succeed for valid bids");
+        .expect("bid outside the proof range; use try_commit to handle this");
         (
             Commitment(commitment_point.to_bytes()),
             Opening {
@@ -289,27 +1235,88 @@ impl CommitmentScheme for BulletproofsCommitment {
                     proof: proof.to_bytes(),
                     blinding: blinding.to_bytes(),
                     range_bits: self.range_bits,
+                    aggregate: None,
                 }),
             },
         )
     }
 
-    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool {
-        if BidEncoding::new(opening.bid) != opening.encoding {
+    fn commit_batch_with_codec<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Vec<(Commitment, Opening)> {
+        if bids.len() <= 1 {
+            return bids
+                .iter()
+                .map(|bid| self.commit_with_codec(*bid, codec, rng))
+                .collect();
+        }
+        let generators = self.generators_for(bids.len().next_power_of_two());
+        self.prove_aggregate(bids, codec, &generators, rng)
+            .unwrap_or_else(|err| panic!("{err:?}; use try_commit_batch to handle this"))
+            .into_entries()
+    }
+
+    fn shared_proofs(&self, openings: &[&Opening]) -> Vec<SharedRangeProof> {
+        let shared = self.shared.lock().expect("shared proofs poisoned");
+        let mut digests: Vec<[u8; 32]> = Vec::new();
+        for opening in openings {
+            let Some(agg) = opening
+                .bulletproof
+                .as_ref()
+                .and_then(|bp| bp.aggregate.as_ref())
+            else {
+                continue;
+            };
+            if !digests.contains(&agg.proof_digest) {
+                digests.push(agg.proof_digest);
+            }
+        }
+        digests
+            .iter()
+            .filter_map(|digest| shared.entries.get(digest).map(|entry| entry.proof.clone()))
+            .collect()
+    }
+
+    fn import_shared_proofs(&self, proofs: &[SharedRangeProof]) {
+        for proof in proofs {
+            self.publish_shared(proof.clone());
+        }
+    }
+
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        if BidEncoding::try_new(opening.bid, codec).ok() != Some(opening.encoding) {
             return false;
         }
         let Some(bp) = opening.bulletproof.as_ref() else {
             return false;
         };
+        if !is_canonical(opening) {
+            return false;
+        }
         let Some(point) = decompress_point(commitment) else {
             return false;
         };
+        // Bind the opened value to this exact point before trusting any range proof over it.
+        if !self.binds(&point, opening, bp) {
+            return false;
+        }
+        if let Some(agg) = bp.aggregate.as_ref() {
+            return self.shared_member(agg) == Some(commitment.0);
+        }
         let mut transcript = Transcript::new(b"DRA-BULLETPROOF");
         let proof = match bp.range_proof() {
             Some(p) => p,
             None => return false,
         };
-        if proof
+        proof
             .verify_single(
                 &self.generators,
                 &self.pedersen,
@@ -317,71 +1324,201 @@ impl CommitmentScheme for BulletproofsCommitment {
                 &CompressedRistretto(commitment.0),
                 bp.range_bits,
             )
-            .is_err()
-        {
-            return false;
-        }
-        let expected = self
-            .pedersen
-            .commit(
-                Scalar::from(opening.encoding.as_u64()),
-                bp.blinding_scalar(),
-            );
-        expected == point
+            .is_ok()
     }
 }
 
-#[derive(Clone, Debug)]
+/// Bulletproofs openings carry no salt or mask; anything else is a malleated opening.
+fn is_canonical(opening: &Opening) -> bool {
+    opening.salt == [0u8; SALT_BYTES] && opening.mask == [0u8; SALT_BYTES]
+}
+
+/// Append-only ledger of commitment entries arranged as a Merkle tree. Each receipt carries
+/// the authentication path from its leaf to the root recorded when it was logged.
+#[derive(Clone, Debug, Default)]
 pub struct AuditLedger {
-    entries: Arc<Mutex<Vec<[u8; 32]>>>,
+    state: Arc<Mutex<LedgerState>>,
+}
+
+/// Every level of the tree, leaves first. A node without a sibling is promoted to the next
+/// level unchanged, so appending a leaf only rehashes the nodes on its path to the root.
+#[derive(Clone, Debug, Default)]
+struct LedgerState {
+    levels: Vec<Vec<[u8; 32]>>,
+    roots: Vec<[u8; 32]>,
+}
+
+impl LedgerState {
+    fn append(&mut self, leaf: [u8; 32]) -> usize {
+        if self.levels.is_empty() {
+            self.levels.push(Vec::new());
+        }
+        self.levels[0].push(leaf);
+        let index = self.levels[0].len() - 1;
+        let mut idx = index;
+        let mut depth = 0;
+        while self.levels[depth].len() > 1 {
+            let level = &self.levels[depth];
+            let left = idx & !1;
+            let node = match level.get(left + 1) {
+                Some(right) => merkle_node(&level[left], right),
+                None => level[left],
+            };
+            idx /= 2;
+            depth += 1;
+            if self.levels.len() == depth {
+                self.levels.push(Vec::new());
+            }
+            let parent = &mut self.levels[depth];
+            if idx < parent.len() {
+                parent[idx] = node;
+            } else {
+                parent.push(node);
+            }
+        }
+        let root = self.levels[depth][0];
+        self.roots.push(root);
+        index
+    }
+
+    /// Sibling path for `index` in the current tree.
+    fn path(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut idx = index;
+        let mut path = Vec::new();
+        for level in self.levels.iter().take_while(|level| level.len() > 1) {
+            if let Some(sibling) = level.get(idx ^ 1) {
+                path.push(*sibling);
+            }
+            idx /= 2;
+        }
+        path
+    }
 }
 
 impl AuditLedger {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An independent copy holding the entries logged so far. Cloning an `AuditLedger` shares
+    /// it instead.
+    pub fn fork(&self) -> Self {
+        let state = self.state.lock().expect("ledger poisoned").clone();
         Self {
-            entries: Arc::new(Mutex::new(Vec::new())),
+            state: Arc::new(Mutex::new(state)),
         }
     }
 
     pub fn log_entry(&self, entry_hash: [u8; 32]) -> AuditReceipt {
-        let mut guard = self.entries.lock().expect("ledger poisoned");
-        guard.push(entry_hash);
-        let root = aggregate_root(&guard[..]);
+        let mut guard = self.state.lock().expect("ledger poisoned");
+        let index = guard.append(merkle_leaf(&entry_hash));
         AuditReceipt {
-            index: guard.len() - 1,
-            root,
+            index,
+            tree_size: index + 1,
+            root: guard.roots[index],
             entry_hash,
+            path: guard.path(index),
         }
     }
 
-    pub fn verify(&self, receipt: &AuditReceipt) -> bool {
-        let guard = self.entries.lock().expect("ledger poisoned");
-        if receipt.index >= guard.len() {
-            return false;
+    /// Root over every entry logged so far; all-zero while the ledger is empty.
+    pub fn root(&self) -> [u8; 32] {
+        let guard = self.state.lock().expect("ledger poisoned");
+        guard.roots.last().copied().unwrap_or([0u8; 32])
+    }
+
+    /// Re-issue `receipt` against the current tree, so it verifies against [`AuditLedger::root`].
+    /// `None` if the ledger holds no such entry at that index.
+    pub fn reissue(&self, receipt: &AuditReceipt) -> Option<AuditReceipt> {
+        let guard = self.state.lock().expect("ledger poisoned");
+        let leaves = guard.levels.first()?;
+        if leaves.get(receipt.index) != Some(&merkle_leaf(&receipt.entry_hash)) {
+            return None;
         }
-        guard[receipt.index] == receipt.entry_hash
-            && aggregate_root(&guard[..=receipt.index]) == receipt.root
+        Some(AuditReceipt {
+            index: receipt.index,
+            tree_size: leaves.len(),
+            root: *guard.roots.last()?,
+            entry_hash: receipt.entry_hash,
+            path: guard.path(receipt.index),
+        })
+    }
+
+    /// Check `receipt` against the root this ledger had at the receipt's `tree_size`.
+    pub fn verify(&self, receipt: &AuditReceipt) -> bool {
+        let guard = self.state.lock().expect("ledger poisoned");
+        receipt
+            .tree_size
+            .checked_sub(1)
+            .and_then(|last| guard.roots.get(last))
+            .is_some_and(|root| receipt.verify_against_root(*root))
     }
 }
 
-fn aggregate_root(entries: &[[u8; 32]]) -> [u8; 32] {
-    let mut acc = [0u8; 32];
-    for entry in entries {
-        let mut hasher = Hasher::new();
-        hasher.update(b"DRA-AUDIT-ROOT");
-        hasher.update(&acc);
-        hasher.update(entry);
-        acc.copy_from_slice(hasher.finalize().as_bytes());
+fn merkle_leaf(entry_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(b"DRA-AUDIT-LEAF");
+    hasher.update(entry_hash);
+    *hasher.finalize().as_bytes()
+}
+
+fn merkle_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(b"DRA-AUDIT-NODE");
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Fold a sibling path for the leaf at `index` in a tree of `size` leaves back to its root.
+fn merkle_root_from_path(
+    leaf: &[u8; 32],
+    index: usize,
+    size: usize,
+    path: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+    if index >= size {
+        return None;
+    }
+    let mut acc = *leaf;
+    let mut idx = index;
+    let mut width = size;
+    let mut siblings = path.iter();
+    while width > 1 {
+        if idx ^ 1 < width {
+            let sibling = siblings.next()?;
+            acc = if idx & 1 == 0 {
+                merkle_node(&acc, sibling)
+            } else {
+                merkle_node(sibling, &acc)
+            };
+        }
+        idx /= 2;
+        width = width.div_ceil(2);
     }
-    acc
+    if siblings.next().is_some() {
+        return None;
+    }
+    Some(acc)
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct AuditedNonMalleableCommitment {
     inner: BulletproofsCommitment,
     ledger: AuditLedger,
 }
 
+/// Clones fork the ledger, so runs on separate clones (in parallel trials, say) log their
+/// entries independently and reproducibly. Use [`Self::with_ledger`] to share one on purpose.
+impl Clone for AuditedNonMalleableCommitment {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            ledger: self.ledger.fork(),
+        }
+    }
+}
+
 impl Default for AuditedNonMalleableCommitment {
     fn default() -> Self {
         Self {
@@ -398,48 +1535,218 @@ impl AuditedNonMalleableCommitment {
             ledger,
         }
     }
+
+    /// The ledger's current root, for an auditor to check revealed receipts against once
+    /// every commitment is in.
+    pub fn published_root(&self) -> [u8; 32] {
+        self.ledger.root()
+    }
+
+    /// Verify against a trusted published root when given one, otherwise against the ledger.
+    pub fn verify_with_root(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        trusted_root: Option<[u8; 32]>,
+    ) -> bool {
+        self.verify_with_codec_and_root(commitment, opening, &BidCodec::default(), trusted_root)
+    }
+
+    /// [`Self::verify_with_root`] for an opening produced under `codec`.
+    pub fn verify_with_codec_and_root(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+        trusted_root: Option<[u8; 32]>,
+    ) -> bool {
+        let Some(receipt) = opening.audit_receipt.as_ref() else {
+            return false;
+        };
+        let included = match trusted_root {
+            Some(root) => receipt.verify_against_root(root),
+            None => self.ledger.verify(receipt),
+        };
+        self.inner.verify_with_codec(commitment, opening, codec)
+            && entry_hash_matches(receipt, commitment, opening)
+            && included
+    }
 }
 
 impl CommitmentScheme for AuditedNonMalleableCommitment {
-    fn commit<R: RngCore>(&self, bid: f64, rng: &mut R) -> (Commitment, Opening) {
-        let (commitment, mut opening) = self.inner.commit(bid, rng);
+    fn check_bid(&self, bid: f64, codec: &BidCodec) -> Result<BidEncoding, CommitError> {
+        self.inner.check_bid(bid, codec)
+    }
+
+    fn commit_with_codec<R: RngCore>(
+        &self,
+        bid: f64,
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> (Commitment, Opening) {
+        let (commitment, mut opening) = self.inner.commit_with_codec(bid, codec, rng);
         let entry_hash = audit_entry_hash(&commitment, &opening);
         let receipt = self.ledger.log_entry(entry_hash);
         opening.audit_receipt = Some(receipt);
         (commitment, opening)
     }
 
-    fn verify(&self, commitment: &Commitment, opening: &Opening) -> bool {
-        let Some(receipt) = opening.audit_receipt.as_ref() else {
-            return false;
-        };
-        self.inner.verify(commitment, opening)
-            && entry_hash_matches(receipt, commitment, opening)
-            && self.ledger.verify(receipt)
+    fn commit_batch_with_codec<R: RngCore>(
+        &self,
+        bids: &[f64],
+        codec: &BidCodec,
+        rng: &mut R,
+    ) -> Vec<(Commitment, Opening)> {
+        let mut batch = self.inner.commit_batch_with_codec(bids, codec, rng);
+        for (commitment, opening) in batch.iter_mut() {
+            let entry_hash = audit_entry_hash(commitment, opening);
+            opening.audit_receipt = Some(self.ledger.log_entry(entry_hash));
+        }
+        batch
+    }
+
+    fn verify_with_codec(
+        &self,
+        commitment: &Commitment,
+        opening: &Opening,
+        codec: &BidCodec,
+    ) -> bool {
+        self.verify_with_codec_and_root(commitment, opening, codec, None)
+    }
+
+    fn shared_proofs(&self, openings: &[&Opening]) -> Vec<SharedRangeProof> {
+        self.inner.shared_proofs(openings)
+    }
+
+    fn import_shared_proofs(&self, proofs: &[SharedRangeProof]) {
+        self.inner.import_shared_proofs(proofs)
+    }
+
+    /// Re-issue the receipt against the ledger's current root, so every revealed opening of
+    /// one auction carries a path to the same published root.
+    fn refresh_opening(&self, opening: &mut Opening) {
+        if let Some(receipt) = opening
+            .audit_receipt
+            .as_ref()
+            .and_then(|receipt| self.ledger.reissue(receipt))
+        {
+            opening.audit_receipt = Some(receipt);
+        }
+    }
+}
+
+/// Commit to `bid`, then check that the honest opening verifies while every tampered variant
+/// (salt, mask, proof material, receipts, or a different bid under the same commitment) fails.
+pub fn check_non_malleable<S: CommitmentScheme>(scheme: &mut S, bid: f64, seed: u64) -> bool {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (commitment, opening) = scheme.commit(bid, &mut rng);
+    if !scheme.verify(&commitment, &opening) {
+        return false;
+    }
+    malleability_mutations(&opening)
+        .iter()
+        .all(|mutated| !scheme.verify(&commitment, mutated))
+}
+
+/// Commit to the same `bid` under each of `schemes`, returning one `(commitment, opening)` per
+/// scheme in order, for [`cross_verify`] to show that they all open to one bid.
+pub fn cross_commit(
+    bid: f64,
+    rng: &mut dyn RngCore,
+    schemes: &[&dyn DynCommitmentScheme],
+) -> Vec<(Commitment, Opening)> {
+    let codec = BidCodec::default();
+    schemes
+        .iter()
+        .map(|scheme| scheme.commit_dyn(bid, &codec, rng))
+        .collect()
+}
+
+/// Check a [`cross_commit`] result: one pair per scheme, each verifying under the scheme at the
+/// same position, and every opening revealing the same bid.
+pub fn cross_verify(
+    schemes: &[&dyn DynCommitmentScheme],
+    commitments: &[(Commitment, Opening)],
+) -> bool {
+    let codec = BidCodec::default();
+    schemes.len() == commitments.len()
+        && commitments
+            .windows(2)
+            .all(|pair| pair[0].1.bid == pair[1].1.bid)
+        && schemes
+            .iter()
+            .zip(commitments)
+            .all(|(scheme, (commitment, opening))| scheme.verify_dyn(commitment, opening, &codec))
+}
+
+fn malleability_mutations(opening: &Opening) -> Vec<Opening> {
+    let mut mutations = Vec::new();
+    let mut salt = opening.clone();
+    salt.salt[0] ^= 0x01;
+    mutations.push(salt);
+    let mut mask = opening.clone();
+    mask.mask[0] ^= 0x01;
+    mutations.push(mask);
+    if opening.proof.is_some() {
+        for field in 0..3 {
+            let mut mutated = opening.clone();
+            let proof = mutated.proof.as_mut().expect("proof present");
+            let bytes = match field {
+                0 => &mut proof.challenge,
+                1 => &mut proof.response_blind,
+                _ => &mut proof.response_message,
+            };
+            bytes[0] ^= 0x01;
+            mutations.push(mutated);
+        }
+    }
+    if opening.bulletproof.is_some() {
+        let mut proof = opening.clone();
+        proof.bulletproof.as_mut().expect("bulletproof present").proof[0] ^= 0x01;
+        mutations.push(proof);
+        let mut blinding = opening.clone();
+        blinding.bulletproof.as_mut().expect("bulletproof present").blinding[0] ^= 0x01;
+        mutations.push(blinding);
+    }
+    if opening.audit_receipt.is_some() {
+        let mut entry = opening.clone();
+        entry.audit_receipt.as_mut().expect("receipt present").entry_hash[0] ^= 0x01;
+        mutations.push(entry);
+        let mut root = opening.clone();
+        root.audit_receipt.as_mut().expect("receipt present").root[0] ^= 0x01;
+        mutations.push(root);
     }
+    let other_bid = opening.bid + 1.0;
+    let mut rebid = opening.clone();
+    rebid.bid = other_bid;
+    rebid.encoding = BidEncoding::new(other_bid, &BidCodec::default());
+    mutations.push(rebid);
+    let mut stale_encoding = opening.clone();
+    stale_encoding.bid = other_bid;
+    mutations.push(stale_encoding);
+    mutations
 }
 
 fn entry_hash_matches(receipt: &AuditReceipt, commitment: &Commitment, opening: &Opening) -> bool {
     receipt.entry_hash == audit_entry_hash(commitment, opening)
 }
 
+/// The message is public once the bid is revealed, so only the blind gets a nonce; the message
+/// response is `challenge·message`, which the verifier recomputes from the encoding.
 fn build_fischlin_proof(
     commitment: &RistrettoPoint,
     blind: Scalar,
     message_scalar: Scalar,
+    salt: &[u8; SALT_BYTES],
     mask: &[u8; SALT_BYTES],
     encoding: &BidEncoding,
 ) -> FischlinProof {
-    let mut seed = *mask;
-    let mut rng = StdRng::from_seed(seed);
+    let mut rng = StdRng::from_seed(*mask);
     let k_blind = scalar_from_rng(&mut rng);
-    rng.fill_bytes(&mut seed);
-    let mut seeded = StdRng::from_seed(seed);
-    let k_msg = scalar_from_rng(&mut seeded);
-    let witness_point = k_blind * RISTRETTO_BASEPOINT_POINT + k_msg * derive_h_point();
-    let challenge = derive_challenge(commitment, &witness_point, encoding);
+    let witness_point = k_blind * RISTRETTO_BASEPOINT_POINT;
+    let challenge = derive_challenge(commitment, &witness_point, encoding, salt, mask);
     let response_blind = k_blind + challenge * blind;
-    let response_msg = k_msg + challenge * message_scalar;
+    let response_msg = challenge * message_scalar;
 
     FischlinProof {
         challenge: challenge.to_bytes(),
@@ -448,38 +1755,44 @@ fn build_fischlin_proof(
     }
 }
 
+/// Fiat-Shamir Schnorr check that the prover knows a `blind` with
+/// `commitment = blind·G + message·H` for the `message` in `encoding`. Only the transcript is
+/// needed, not the blind: without a witness the prover cannot choose the responses before the
+/// challenge hash fixes them, and the hash covers `encoding`, `salt` and `mask`, so none of
+/// them can be changed under an honest proof.
 fn verify_fischlin_proof(
     commitment: &RistrettoPoint,
     proof: &FischlinProof,
     encoding: &BidEncoding,
-    blind_scalar: Scalar,
-    message_scalar: Scalar,
+    salt: &[u8; SALT_BYTES],
+    mask: &[u8; SALT_BYTES],
 ) -> bool {
     let challenge = proof.challenge_scalar();
     let resp_blind = proof.response_blind_scalar();
     let resp_msg = proof.response_message_scalar();
+    if resp_msg != challenge * scalar_from_encoding(encoding) {
+        return false;
+    }
     let lhs = resp_blind * RISTRETTO_BASEPOINT_POINT + resp_msg * derive_h_point();
     let rhs = challenge * commitment;
     let witness_point = lhs - rhs;
-    let recomputed = derive_challenge(commitment, &witness_point, encoding);
-    if recomputed != challenge {
-        return false;
-    }
-    let reconstructed =
-        blind_scalar * RISTRETTO_BASEPOINT_POINT + message_scalar * derive_h_point();
-    reconstructed == *commitment
+    derive_challenge(commitment, &witness_point, encoding, salt, mask) == challenge
 }
 
 fn derive_challenge(
     commitment: &RistrettoPoint,
     witness: &RistrettoPoint,
     encoding: &BidEncoding,
+    salt: &[u8; SALT_BYTES],
+    mask: &[u8; SALT_BYTES],
 ) -> Scalar {
     let mut hasher = Sha256::new();
     hasher.update(b"DRA-FISCHLIN-CHALLENGE");
     hasher.update(commitment.compress().as_bytes());
     hasher.update(witness.compress().as_bytes());
     hasher.update(encoding.as_bytes());
+    hasher.update(salt);
+    hasher.update(mask);
     let digest = hasher.finalize();
     let mut bytes = [0u8; 32];
     bytes.copy_from_slice(&digest);
@@ -502,12 +1815,35 @@ fn hash_commitment(
     Commitment(out)
 }
 
+fn blake3_commitment(
+    encoding: &BidEncoding,
+    salt: &[u8; SALT_BYTES],
+    mask: &[u8; SALT_BYTES],
+) -> Commitment {
+    let mut hasher = Hasher::new();
+    hasher.update(b"DRA-BID");
+    hasher.update(encoding.as_bytes());
+    hasher.update(salt);
+    hasher.update(mask);
+    Commitment(*hasher.finalize().as_bytes())
+}
+
 fn random_bytes<R: RngCore>(rng: &mut R) -> [u8; SALT_BYTES] {
     let mut bytes = [0u8; SALT_BYTES];
     rng.fill_bytes(&mut bytes);
     bytes
 }
 
+/// Blindings and proof nonces come from a `StdRng` seeded with
+/// `SHA-256(label || 32 bytes of rng)`, so each proof consumes exactly 32 bytes of the caller's
+/// stream and a seeded run reproduces byte-identical proofs.
+fn proving_rng<R: RngCore>(rng: &mut R, label: &[u8]) -> StdRng {
+    let mut hasher = Sha256::new();
+    hasher.update(label);
+    hasher.update(random_bytes(rng));
+    StdRng::from_seed(hasher.finalize().into())
+}
+
 fn scalar_from_rng<R: RngCore>(rng: &mut R) -> Scalar {
     let mut wide = [0u8; 64];
     rng.fill_bytes(&mut wide);
@@ -562,6 +1898,10 @@ fn audit_entry_hash(commitment: &Commitment, opening: &Opening) -> [u8; 32] {
         hasher.update(&bp.blinding);
         hasher.update(&(bp.range_bits as u64).to_le_bytes());
         hasher.update(&bp.proof);
+        if let Some(agg) = bp.aggregate.as_ref() {
+            hasher.update(&(agg.position as u64).to_le_bytes());
+            hasher.update(&agg.proof_digest);
+        }
     }
     *hasher.finalize().as_bytes()
 }
@@ -578,6 +1918,95 @@ mod tests {
         assert!(scheme.verify(&commitment, &opening));
     }
 
+    #[test]
+    fn cross_verify_rejects_openings_of_different_bids() {
+        let mut rng = StdRng::seed_from_u64(362);
+        let schemes: [&dyn DynCommitmentScheme; 2] =
+            [&NonMalleableShaCommitment, &PedersenRistrettoCommitment];
+        let both = cross_commit(10.0, &mut rng, &schemes);
+        assert!(cross_verify(&schemes, &both));
+
+        let sha = cross_commit(10.0, &mut rng, &schemes[..1]);
+        let pedersen = cross_commit(11.0, &mut rng, &schemes[1..]);
+        let mismatched = [sha[0].clone(), pedersen[0].clone()];
+        assert!(!cross_verify(&schemes, &mismatched));
+        // Relabelling the odd opening fails that scheme's own check instead.
+        let mut relabelled = mismatched.clone();
+        relabelled[1].1.bid = 10.0;
+        assert!(!cross_verify(&schemes, &relabelled));
+        assert!(!cross_verify(&schemes, &both[..1]));
+    }
+
+    #[test]
+    fn fischlin_proof_rejects_forged_challenges() {
+        let mut rng = StdRng::seed_from_u64(363);
+        let scheme = RealNonMalleableCommitment;
+        let (commitment, opening) = scheme.commit(10.0, &mut rng);
+        let point = decompress_point(&commitment).expect("valid point");
+        let proof = opening.proof.clone().expect("proof attached");
+        let verifies = |point: &RistrettoPoint, proof: &FischlinProof, opening: &Opening| {
+            verify_fischlin_proof(
+                point,
+                proof,
+                &opening.encoding,
+                &opening.salt,
+                &opening.mask,
+            )
+        };
+        assert!(verifies(&point, &proof, &opening));
+
+        // Simulated transcript: choose the challenge and responses first. Without the opening
+        // the challenge hash cannot be made to agree.
+        let forged = FischlinProof {
+            challenge: scalar_from_rng(&mut rng).to_bytes(),
+            response_blind: scalar_from_rng(&mut rng).to_bytes(),
+            response_message: scalar_from_rng(&mut rng).to_bytes(),
+        };
+        assert!(!verifies(&point, &forged, &opening));
+        let mut forged_opening = opening.clone();
+        forged_opening.proof = Some(forged);
+        assert!(!scheme.verify(&commitment, &forged_opening));
+
+        // Nor does the honest proof carry over to a related commitment nobody can open.
+        let shifted = point + derive_h_point();
+        assert!(!verifies(&shifted, &proof, &opening));
+    }
+
+    #[test]
+    fn fischlin_proof_binds_the_opening_without_rederiving_it() {
+        let mut rng = StdRng::seed_from_u64(3630);
+        let scheme = RealNonMalleableCommitment;
+        let (commitment, opening) = scheme.commit(10.0, &mut rng);
+
+        // A blind that no salt hashes to still verifies: the check uses the transcript only.
+        let blind = scalar_from_rng(&mut rng);
+        let message = scalar_from_encoding(&opening.encoding);
+        let point = blind * RISTRETTO_BASEPOINT_POINT + message * derive_h_point();
+        let mut detached = opening.clone();
+        detached.proof = Some(build_fischlin_proof(
+            &point,
+            blind,
+            message,
+            &opening.salt,
+            &opening.mask,
+            &opening.encoding,
+        ));
+        assert!(scheme.verify(&Commitment(point.compress().to_bytes()), &detached));
+
+        // Each field the challenge covers is bound: change one under the honest proof and the
+        // opening is rejected.
+        let mut salt = opening.clone();
+        salt.salt[5] ^= 0x10;
+        assert!(!scheme.verify(&commitment, &salt));
+        let mut mask = opening.clone();
+        mask.mask[5] ^= 0x10;
+        assert!(!scheme.verify(&commitment, &mask));
+        let mut rebid = opening.clone();
+        rebid.bid = 11.0;
+        rebid.encoding = BidEncoding::new(11.0, &BidCodec::default());
+        assert!(!scheme.verify(&commitment, &rebid));
+    }
+
     #[test]
     fn sha_commit_rejects_wrong_bid() {
         let mut rng = rand::thread_rng();
@@ -587,6 +2016,126 @@ mod tests {
         assert!(!scheme.verify(&commitment, &opening));
     }
 
+    #[test]
+    fn non_malleability_self_test_passes_for_every_backend() {
+        assert!(check_non_malleable(&mut NonMalleableShaCommitment, 10.0, 1));
+        assert!(check_non_malleable(&mut Blake3Commitment, 10.0, 2));
+        assert!(check_non_malleable(&mut PedersenRistrettoCommitment, 10.0, 3));
+        assert!(check_non_malleable(&mut RealNonMalleableCommitment, 10.0, 4));
+        assert!(check_non_malleable(
+            &mut AuditedNonMalleableCommitment::default(),
+            10.0,
+            5
+        ));
+        assert!(check_non_malleable(
+            &mut BulletproofsCommitment::default(),
+            10.0,
+            6
+        ));
+    }
+
+    #[test]
+    fn blake3_commit_round_trip() {
+        let mut rng = rand::thread_rng();
+        let scheme = Blake3Commitment;
+        let (commitment, opening) = scheme.commit(10.0, &mut rng);
+        assert!(scheme.verify(&commitment, &opening));
+    }
+
+    #[test]
+    fn blake3_commit_rejects_wrong_bid() {
+        let mut rng = rand::thread_rng();
+        let scheme = Blake3Commitment;
+        let (commitment, mut opening) = scheme.commit(10.0, &mut rng);
+        opening.bid = 11.0;
+        assert!(!scheme.verify(&commitment, &opening));
+    }
+
+    #[test]
+    fn blake3_commit_rejects_modified_salt() {
+        let mut rng = rand::thread_rng();
+        let scheme = Blake3Commitment;
+        let (commitment, mut opening) = scheme.commit(10.0, &mut rng);
+        opening.salt[0] ^= 0xFF;
+        assert!(!scheme.verify(&commitment, &opening));
+    }
+
+    #[test]
+    fn high_scale_codec_preserves_four_decimals() {
+        let mut rng = rand::thread_rng();
+        let codec = BidCodec::new(1e10, 16);
+        let scheme = PedersenRistrettoCommitment;
+        let (commitment, opening) = scheme.commit_with_codec(12.3456, &codec, &mut rng);
+        assert!(scheme.verify_with_codec(&commitment, &opening, &codec));
+        assert!((opening.encoding.decode(&codec) - 12.3456).abs() < 1e-9);
+
+        let coarse = BidCodec::new(100.0, 16);
+        assert!((BidEncoding::new(12.3456, &coarse).decode(&coarse) - 12.35).abs() < 1e-9);
+        assert_eq!(
+            BidEncoding::try_new(400.0, &BidCodec::new(1.0, 1)),
+            Err(BidEncodingError::Overflow)
+        );
+    }
+
+    #[test]
+    fn unencodable_revealed_bid_fails_verification_without_panicking() {
+        let mut rng = StdRng::seed_from_u64(284);
+        let registry = SchemeRegistry::default();
+        for name in registry.names() {
+            let scheme = registry.create(name).expect("registered");
+            let (commitment, mut opening) = scheme.commit(3.0, &mut rng);
+            for bid in [f64::NAN, 1e300, -1.0] {
+                opening.bid = bid;
+                assert!(
+                    !scheme.verify(&commitment, &opening),
+                    "{name} accepted {bid}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn batches_honour_the_caller_codec() {
+        let mut rng = StdRng::seed_from_u64(284);
+        let codec = BidCodec::new(1e4, 16);
+        let scheme = BulletproofsCommitment::new_aggregated(32, 4);
+        let batch = scheme.commit_batch_with_codec(&[1.2345, 6.789, 0.5], &codec, &mut rng);
+        let pairs: Vec<(&Commitment, &Opening)> = batch.iter().map(|(c, o)| (c, o)).collect();
+        assert!(scheme.verify_batch_with_codec(&pairs, &codec));
+        assert!(!scheme.verify_batch(&pairs));
+        assert!((batch[0].1.encoding.decode(&codec) - 1.2345).abs() < 1e-9);
+
+        let aggregate = scheme
+            .commit_aggregated_with_codec(&[2.5, 4.0], &codec, &mut rng)
+            .expect("two bids fit");
+        assert!(scheme.verify_aggregated_with_codec(&aggregate, &codec));
+        assert!(!scheme.verify_aggregated(&aggregate));
+    }
+
+    #[test]
+    fn signed_codec_round_trips_negative_bids() {
+        let mut rng = rand::thread_rng();
+        let codec = BidCodec::signed(BID_SCALE, BID_BYTES);
+        let scheme = NonMalleableShaCommitment;
+        let (commitment, opening) = scheme.commit_with_codec(-5.0, &codec, &mut rng);
+        assert!(scheme.verify_with_codec(&commitment, &opening, &codec));
+        assert_eq!(opening.encoding.decode(&codec), -5.0);
+        assert_ne!(opening.encoding, BidEncoding::new(5.0, &codec));
+
+        let fischlin = RealNonMalleableCommitment;
+        let (commitment, opening) = fischlin.commit_with_codec(-2.5, &codec, &mut rng);
+        assert!(fischlin.verify_with_codec(&commitment, &opening, &codec));
+
+        assert_eq!(
+            opening.encoding.try_as_u64(),
+            Err(BidEncodingError::Negative)
+        );
+        assert_eq!(
+            BidEncoding::try_new(-5.0, &BidCodec::default()),
+            Err(BidEncodingError::Negative)
+        );
+    }
+
     #[test]
     fn pedersen_commit_round_trip() {
         let mut rng = rand::thread_rng();
@@ -639,6 +2188,100 @@ mod tests {
         assert!(!scheme.verify(&commitment, &opening));
     }
 
+    #[test]
+    fn merkle_receipt_rejects_tampered_sibling() {
+        let ledger = AuditLedger::new();
+        let receipts: Vec<AuditReceipt> = (0u8..5).map(|i| ledger.log_entry([i; 32])).collect();
+        for receipt in receipts.iter() {
+            assert!(ledger.verify(receipt));
+        }
+        let mut tampered = receipts[4].clone();
+        assert!(!tampered.path.is_empty());
+        tampered.path[0][0] ^= 0x01;
+        assert!(!ledger.verify(&tampered));
+        let mut truncated = receipts[3].clone();
+        truncated.path.pop();
+        assert!(!ledger.verify(&truncated));
+    }
+
+    #[test]
+    fn incremental_ledger_matches_a_rebuilt_tree() {
+        let ledger = AuditLedger::new();
+        let mut leaves = Vec::new();
+        for i in 0u8..17 {
+            let receipt = ledger.log_entry([i; 32]);
+            leaves.push(merkle_leaf(&[i; 32]));
+            let mut level = leaves.clone();
+            while level.len() > 1 {
+                level = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => merkle_node(left, right),
+                        _ => pair[0],
+                    })
+                    .collect();
+            }
+            assert_eq!(receipt.root, level[0], "root after {} entries", i + 1);
+            assert!(ledger.verify(&receipt));
+        }
+    }
+
+    #[test]
+    fn receipt_verifies_against_published_root_without_ledger() {
+        let mut rng = rand::thread_rng();
+        let (entries, published) = {
+            let issuer = AuditedNonMalleableCommitment::default();
+            let mut entries = vec![issuer.commit(2.0, &mut rng), issuer.commit(6.0, &mut rng)];
+            let published = issuer.published_root();
+            // The first receipt was issued against a one-entry tree, not the final one.
+            let stale = entries[0].1.audit_receipt.clone().expect("receipt present");
+            assert!(!stale.verify_against_root(published));
+            for (_, opening) in entries.iter_mut() {
+                issuer.refresh_opening(opening);
+            }
+            (entries, published)
+        };
+        let auditor = AuditedNonMalleableCommitment::default();
+        for (commitment, opening) in entries.iter() {
+            let receipt = opening.audit_receipt.as_ref().expect("receipt present");
+            assert!(receipt.verify_against_root(published));
+            assert!(auditor.verify_with_root(commitment, opening, Some(published)));
+            assert!(!auditor.verify_with_root(commitment, opening, None));
+            let mut forged = published;
+            forged[0] ^= 0x01;
+            assert!(!auditor.verify_with_root(commitment, opening, Some(forged)));
+        }
+    }
+
+    #[test]
+    fn published_root_verification_decodes_with_the_callers_codec() {
+        let mut rng = rand::thread_rng();
+        let codec = BidCodec::new(100.0, 8);
+        let issuer = AuditedNonMalleableCommitment::default();
+        let (commitment, mut opening) = issuer.commit_with_codec(2.5, &codec, &mut rng);
+        issuer.refresh_opening(&mut opening);
+        let published = issuer.published_root();
+        let auditor = AuditedNonMalleableCommitment::default();
+        assert!(auditor.verify_with_codec_and_root(&commitment, &opening, &codec, Some(published)));
+        assert!(!auditor.verify_with_root(&commitment, &opening, Some(published)));
+    }
+
+    #[test]
+    fn reissued_receipt_verifies_against_the_final_root() {
+        let ledger = AuditLedger::new();
+        let receipts: Vec<AuditReceipt> = (0u8..6).map(|i| ledger.log_entry([i; 32])).collect();
+        let root = ledger.root();
+        assert!(!receipts[1].verify_against_root(root));
+        let reissued = ledger.reissue(&receipts[1]).expect("entry is logged");
+        assert_eq!(reissued.tree_size, 6);
+        assert!(reissued.verify_against_root(root));
+        assert!(ledger.verify(&reissued) && ledger.verify(&receipts[1]));
+
+        let mut unknown = receipts[1].clone();
+        unknown.entry_hash[0] ^= 0x01;
+        assert!(ledger.reissue(&unknown).is_none());
+    }
+
     #[test]
     fn bulletproof_commit_round_trip() {
         let mut rng = rand::thread_rng();
@@ -647,6 +2290,245 @@ mod tests {
         assert!(scheme.verify(&commitment, &opening));
     }
 
+    #[test]
+    fn bulletproof_rejects_opening_paired_with_swapped_point() {
+        let mut rng = rand::thread_rng();
+        let scheme = BulletproofsCommitment::default();
+        let (commitment_a, opening_a) = scheme.commit(5.0, &mut rng);
+        let (commitment_b, opening_b) = scheme.commit(9.0, &mut rng);
+        assert!(!scheme.verify(&commitment_a, &opening_b));
+        assert!(!scheme.verify(&commitment_b, &opening_a));
+
+        // Alter the bid (keeping the encoding consistent) but keep A's point and proof.
+        let mut altered = opening_a.clone();
+        altered.bid = 9.0;
+        altered.encoding = opening_b.encoding;
+        assert!(!scheme.verify(&commitment_a, &altered));
+        assert!(scheme.verify(&commitment_a, &opening_a));
+    }
+
+    #[test]
+    fn bulletproof_batch_verifies_and_rejects_single_tampered_bid() {
+        let mut rng = rand::thread_rng();
+        let scheme = BulletproofsCommitment::default();
+        let batch = scheme.commit_batch(&[3.0, 8.5, 12.0], &mut rng);
+        let pairs: Vec<(&Commitment, &Opening)> = batch.iter().map(|(c, o)| (c, o)).collect();
+        assert!(scheme.verify_batch(&pairs));
+        for (commitment, opening) in batch.iter() {
+            assert!(scheme.verify(commitment, opening));
+        }
+
+        let mut tampered = batch.clone();
+        tampered[1].1.bid = 9.0;
+        tampered[1].1.encoding = BidEncoding::new(9.0, &BidCodec::default());
+        let pairs: Vec<(&Commitment, &Opening)> = tampered.iter().map(|(c, o)| (c, o)).collect();
+        assert!(!scheme.verify_batch(&pairs));
+    }
+
+    #[test]
+    fn batch_openings_refer_to_one_shared_proof() {
+        let mut rng = StdRng::seed_from_u64(282);
+        let scheme = BulletproofsCommitment::default();
+        let batch = scheme.commit_batch(&[3.0, 8.5, 12.0, 1.0], &mut rng);
+        let openings: Vec<&Opening> = batch.iter().map(|(_, o)| o).collect();
+        let shared = scheme.shared_proofs(&openings);
+        assert_eq!(shared.len(), 1);
+        for (_, opening) in batch.iter() {
+            let proof = &opening.bulletproof.as_ref().expect("proof data").proof;
+            assert!(proof.is_empty());
+            assert!(scheme.opening_size(opening) < shared[0].proof.len());
+        }
+
+        let auditor = BulletproofsCommitment::default();
+        assert!(!auditor.verify(&batch[0].0, &batch[0].1));
+        auditor.import_shared_proofs(&shared);
+        for (commitment, opening) in batch.iter() {
+            assert!(auditor.verify(commitment, opening));
+        }
+        let mut forged = shared[0].clone();
+        forged.proof[0] ^= 0x01;
+        let fooled = BulletproofsCommitment::default();
+        fooled.import_shared_proofs(&[forged]);
+        assert!(!fooled.verify(&batch[0].0, &batch[0].1));
+    }
+
+    #[test]
+    fn shared_proof_cache_is_per_instance_and_bounded() {
+        let mut rng = StdRng::seed_from_u64(283);
+        let scheme = BulletproofsCommitment::default();
+        let batch = scheme.commit_batch(&[3.0, 8.5], &mut rng);
+        let (commitment, opening) = &batch[0];
+        assert!(scheme.verify(commitment, opening));
+        let openings: Vec<&Opening> = batch.iter().map(|(_, o)| o).collect();
+        let shared = scheme.shared_proofs(&openings);
+
+        // A clone does not inherit the proofs its original holds.
+        let copy = scheme.clone();
+        assert!(!copy.verify(commitment, opening));
+        copy.import_shared_proofs(&shared);
+        assert!(copy.verify(commitment, opening));
+
+        // Filling the cache evicts the oldest proof.
+        let filler: Vec<SharedRangeProof> = (0..SHARED_PROOF_CAPACITY as u32)
+            .map(|i| SharedRangeProof {
+                proof: i.to_le_bytes().to_vec(),
+                range_bits: 64,
+                commitments: Vec::new(),
+            })
+            .collect();
+        scheme.import_shared_proofs(&filler);
+        assert!(!scheme.verify(commitment, opening));
+        assert!(scheme.shared_proofs(&openings).is_empty());
+        scheme.import_shared_proofs(&shared);
+        assert!(scheme.verify(commitment, opening));
+    }
+
+    #[test]
+    fn aggregated_proof_is_smaller_than_individual_proofs() {
+        let mut rng = rand::thread_rng();
+        let bids = [1.0, 2.5, 3.0, 4.25, 5.0, 6.5, 7.0, 8.75];
+        let scheme = BulletproofsCommitment::new_aggregated(32, 8);
+        let aggregate = scheme
+            .commit_aggregated(&bids, &mut rng)
+            .expect("eight bids fit");
+        assert!(scheme.verify_aggregated(&aggregate));
+        // Single-value proofs have a fixed size for a given range, so one sample suffices.
+        let (_, single) = scheme.commit(bids[0], &mut rng);
+        let individual = bids.len() * single.bulletproof.expect("proof present").proof.len();
+        assert!(
+            aggregate.proof.len() < individual,
+            "aggregated {} vs individual {}",
+            aggregate.proof.len(),
+            individual
+        );
+        let mut withheld = aggregate.clone();
+        withheld.openings.pop();
+        assert!(!scheme.verify_aggregated(&withheld));
+        let entries = aggregate.into_entries();
+        assert!(scheme.verify(&entries[3].0, &entries[3].1));
+        assert_eq!(
+            scheme.commit_aggregated(&[1.0; 9], &mut rng).unwrap_err(),
+            AggregationError::TooManyParties {
+                requested: 9,
+                capacity: 8
+            }
+        );
+    }
+
+    #[test]
+    fn aggregated_proof_cannot_hide_a_bid_as_padding() {
+        let mut rng = StdRng::seed_from_u64(283);
+        let scheme = BulletproofsCommitment::new_aggregated(32, 8);
+        let aggregate = scheme
+            .commit_aggregated(&[1.0, 2.0, 3.0, 4.0, 5.0], &mut rng)
+            .expect("five bids fit");
+        assert!(scheme.verify_aggregated(&aggregate));
+        let mut truncated = aggregate.clone();
+        truncated.points.pop();
+        truncated.openings.pop();
+        assert!(!scheme.verify_aggregated(&truncated));
+        assert!(matches!(
+            scheme.commit_aggregated(&[1.0, 1e15], &mut rng),
+            Err(AggregationError::Bid(CommitError::OutOfRange { .. }))
+        ));
+    }
+
+    #[test]
+    fn default_batch_matches_individual_commitments() {
+        let mut rng = rand::thread_rng();
+        let scheme = NonMalleableShaCommitment;
+        let batch = scheme.commit_batch(&[1.0, 2.0], &mut rng);
+        let pairs: Vec<(&Commitment, &Opening)> = batch.iter().map(|(c, o)| (c, o)).collect();
+        assert!(scheme.verify_batch(&pairs));
+    }
+
+    #[test]
+    fn bulletproof_openings_outweigh_hash_openings() {
+        let mut rng = StdRng::seed_from_u64(294);
+        let (_, sha_opening) = NonMalleableShaCommitment.commit(2.5, &mut rng);
+        let bulletproofs = BulletproofsCommitment::new(32);
+        let (_, bp_opening) = bulletproofs.commit(2.5, &mut rng);
+        let sha_size = NonMalleableShaCommitment.opening_size(&sha_opening);
+        assert_eq!(sha_size, BID_BYTES + 2 * SALT_BYTES);
+        assert!(bulletproofs.opening_size(&bp_opening) > sha_size);
+        assert_eq!(bulletproofs.commitment_size(), 32);
+
+        let boxed: Box<dyn DynCommitmentScheme> = Box::new(bulletproofs.clone());
+        assert_eq!(
+            boxed.opening_size(&bp_opening),
+            bulletproofs.opening_size(&bp_opening)
+        );
+    }
+
+    #[test]
+    fn registry_selects_backend_by_name() {
+        let registry = SchemeRegistry::default();
+        assert!(registry.names().any(|name| name == "blake3"));
+        assert!(registry.create("unknown").is_none());
+
+        let scheme = registry.create("pedersen").expect("pedersen registered");
+        let mut rng = StdRng::seed_from_u64(293);
+        let (commitment, opening) = scheme.commit(4.5, &mut rng);
+        assert!(scheme.verify(&commitment, &opening));
+        assert!(PedersenRistrettoCommitment.verify(&commitment, &opening));
+        assert!(!NonMalleableShaCommitment.verify(&commitment, &opening));
+
+        let mut custom = SchemeRegistry::empty();
+        custom.register("bp8", || Box::new(BulletproofsCommitment::new(8)));
+        let bp8 = custom.create("bp8").expect("custom backend");
+        assert!(matches!(
+            bp8.clone().try_commit(1.0, &mut rng),
+            Err(CommitError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn bulletproof_try_commit_rejects_bids_beyond_range() {
+        let scheme = BulletproofsCommitment::new(32);
+        let mut rng = StdRng::seed_from_u64(292);
+        let bid = 2f64.powi(64) / BID_SCALE;
+        assert_eq!(
+            scheme.try_commit(bid, &mut rng).unwrap_err(),
+            CommitError::OutOfRange {
+                bid,
+                max: u32::MAX as f64 / BID_SCALE,
+            }
+        );
+        assert!(matches!(
+            scheme.try_commit_batch(&[1.0, bid], &mut rng),
+            Err(CommitError::OutOfRange { .. })
+        ));
+        assert_eq!(
+            scheme.try_commit(f64::NAN, &mut rng).unwrap_err(),
+            CommitError::Encoding(BidEncodingError::NonFinite)
+        );
+        let (commitment, opening) = scheme.try_commit(1.0, &mut rng).expect("in range");
+        assert!(scheme.verify(&commitment, &opening));
+    }
+
+    #[test]
+    fn audited_try_commit_applies_the_inner_range_check() {
+        let scheme = AuditedNonMalleableCommitment::default();
+        let mut rng = StdRng::seed_from_u64(292);
+        assert!(matches!(
+            scheme.try_commit_with_codec(1e15, &BidCodec::default(), &mut rng),
+            Err(CommitError::OutOfRange { .. })
+        ));
+        let (commitment, opening) = scheme.try_commit(1.0, &mut rng).expect("in range");
+        assert!(scheme.verify(&commitment, &opening));
+    }
+
+    #[test]
+    fn bulletproof_try_new_rejects_unsupported_ranges() {
+        assert!(BulletproofsCommitment::try_new(16).is_ok());
+        for bits in [0, 4, 12, 128] {
+            assert_eq!(
+                BulletproofsCommitment::try_new(bits).unwrap_err(),
+                InvalidRangeBits { requested: bits }
+            );
+        }
+    }
+
     #[test]
     fn bulletproof_commit_rejects_tampering() {
         let mut rng = rand::thread_rng();
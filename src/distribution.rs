@@ -1,4 +1,6 @@
-use rand::Rng;
+use std::fmt;
+
+use rand::{Rng, RngCore};
 use rand_distr::{Distribution as RandDistribution, Exp, LogNormal as RandLogNormal};
 use statrs::distribution::{ContinuousCDF, Normal};
 
@@ -45,6 +47,28 @@ pub trait ValueDistribution: Clone {
         hi
     }
 
+    /// Smallest value with F(x) >= p, found by bisection over the non-negative support.
+    fn quantile(&self, p: f64) -> f64 {
+        assert!((0.0..1.0).contains(&p), "quantile level must be in [0, 1)");
+        let mut lo = 0.0_f64;
+        let mut hi = 1.0_f64;
+        for _ in 0..64 {
+            if self.cdf(hi) >= p {
+                break;
+            }
+            hi *= 2.0;
+        }
+        for _ in 0..96 {
+            let mid = 0.5 * (lo + hi);
+            if self.cdf(mid) >= p {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        hi
+    }
+
     /// α such that the distribution is α-strongly regular, if known.
     fn strong_regular_alpha(&self) -> Option<f64> {
         None
@@ -54,6 +78,41 @@ pub trait ValueDistribution: Clone {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64;
 }
 
+/// A distribution parameter its constructor would reject.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DistributionError {
+    /// A parameter that must be positive, named as in the constructor.
+    NonPositive { name: &'static str, value: f64 },
+    /// A uniform whose `low` is not below its `high`.
+    EmptyRange { low: f64, high: f64 },
+}
+
+impl fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistributionError::NonPositive { name, value } => {
+                write!(f, "{name} must be positive, got {value}")
+            }
+            DistributionError::EmptyRange { low, high } => {
+                write!(
+                    f,
+                    "uniform requires low < high, got low {low} and high {high}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DistributionError {}
+
+fn positive(name: &'static str, value: f64) -> Result<f64, DistributionError> {
+    if value > 0.0 {
+        Ok(value)
+    } else {
+        Err(DistributionError::NonPositive { name, value })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Exponential {
     pub lambda: f64,
@@ -61,8 +120,13 @@ pub struct Exponential {
 
 impl Exponential {
     pub fn new(lambda: f64) -> Self {
-        assert!(lambda > 0.0, "lambda must be positive");
-        Self { lambda }
+        Self::try_new(lambda).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(lambda: f64) -> Result<Self, DistributionError> {
+        Ok(Self {
+            lambda: positive("lambda", lambda)?,
+        })
     }
 }
 
@@ -109,8 +173,15 @@ pub struct Uniform {
 
 impl Uniform {
     pub fn new(low: f64, high: f64) -> Self {
-        assert!(low < high, "uniform requires low < high");
-        Self { low, high }
+        Self::try_new(low, high).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(low: f64, high: f64) -> Result<Self, DistributionError> {
+        if low < high {
+            Ok(Self { low, high })
+        } else {
+            Err(DistributionError::EmptyRange { low, high })
+        }
     }
 }
 
@@ -160,9 +231,14 @@ pub struct Pareto {
 
 impl Pareto {
     pub fn new(scale: f64, shape: f64) -> Self {
-        assert!(scale > 0.0, "scale must be positive");
-        assert!(shape > 0.0, "shape must be positive");
-        Self { scale, shape }
+        Self::try_new(scale, shape).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(scale: f64, shape: f64) -> Result<Self, DistributionError> {
+        Ok(Self {
+            scale: positive("scale", scale)?,
+            shape: positive("shape", shape)?,
+        })
     }
 }
 
@@ -194,11 +270,8 @@ impl ValueDistribution for Pareto {
     }
 
     fn strong_regular_alpha(&self) -> Option<f64> {
-        if self.shape > 1.0 {
-            Some(1.0 - 1.0 / self.shape)
-        } else {
-            None
-        }
+        // φ'(x) = 1 - 1/shape; for shape <= 1 that is not positive, so no positive α holds.
+        Some((1.0 - 1.0 / self.shape).max(0.0))
     }
 
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
@@ -215,14 +288,21 @@ pub struct LogNormal {
 
 impl LogNormal {
     pub fn new(mu: f64, sigma: f64) -> Self {
-        assert!(sigma > 0.0, "sigma must be positive");
-        Self { mu, sigma }
+        Self::try_new(mu, sigma).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, DistributionError> {
+        Ok(Self {
+            mu,
+            sigma: positive("sigma", sigma)?,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use statrs::distribution::Continuous;
 
     #[test]
     fn pareto_virtual_value_matches_formula() {
@@ -238,7 +318,7 @@ mod tests {
         let mut rng = rand::thread_rng();
         let u = Uniform::new(1.0, 2.0);
         let x = u.sample(&mut rng);
-        assert!(x >= 1.0 && x <= 2.0);
+        assert!((1.0..=2.0).contains(&x));
 
         let e = Exponential::new(1.0);
         assert!(e.sample(&mut rng) >= 0.0);
@@ -249,6 +329,94 @@ mod tests {
         let ln = LogNormal::new(0.0, 1.0);
         assert!(ln.sample(&mut rng) > 0.0);
     }
+
+    #[test]
+    fn truncated_normal_samples_are_non_negative_with_the_truncated_mean() {
+        use rand::{SeedableRng, rngs::StdRng};
+        let dist = TruncatedNormal::new(1.0, 2.0);
+        let mut rng = StdRng::seed_from_u64(17);
+        let samples: Vec<f64> = (0..50_000).map(|_| dist.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0));
+        // E[X | X >= 0] = mean + sd * φ(a) / (1 - Φ(a)) with a = -mean / sd.
+        let standard = Normal::new(0.0, 1.0).expect("standard normal");
+        let a = -dist.mean / dist.sd;
+        let expected = dist.mean + dist.sd * standard.pdf(a) / (1.0 - standard.cdf(a));
+        let empirical = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!(
+            (empirical - expected).abs() < 0.03,
+            "empirical {empirical} vs {expected}"
+        );
+        assert!((dist.cdf(1e6) - 1.0).abs() < 1e-12);
+        let reserve = dist.reserve_price();
+        assert!(dist.virtual_value(reserve).abs() < 1e-6);
+    }
+
+    #[test]
+    fn try_new_rejects_what_new_would_panic_on() {
+        assert_eq!(
+            Uniform::try_new(5.0, 1.0).unwrap_err(),
+            DistributionError::EmptyRange {
+                low: 5.0,
+                high: 1.0
+            }
+        );
+        assert!(matches!(
+            TruncatedNormal::try_new(1.0, 0.0),
+            Err(DistributionError::NonPositive { name: "sd", .. })
+        ));
+        assert!(matches!(
+            Pareto::try_new(1.0, f64::NAN),
+            Err(DistributionError::NonPositive { name: "shape", .. })
+        ));
+        assert!(Exponential::try_new(-1.0).is_err());
+        assert!(LogNormal::try_new(0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn truncated_normal_samples_far_tails_without_rejection() {
+        use rand::{SeedableRng, rngs::StdRng};
+        let mut rng = StdRng::seed_from_u64(358);
+        // Under 1e-23 of the mass lies above zero, so rejection sampling would never return.
+        let dist = TruncatedNormal::new(-10.0, 1.0);
+        let samples: Vec<f64> = (0..20_000).map(|_| dist.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x.is_finite() && x >= 0.0));
+        let standard = Normal::new(0.0, 1.0).expect("standard normal");
+        let expected = dist.mean + dist.sd * standard.pdf(10.0) / standard.cdf(-10.0);
+        let empirical = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!(
+            (empirical - expected).abs() < 0.05 * expected,
+            "empirical {empirical} vs {expected}"
+        );
+
+        let beyond = TruncatedNormal::new(-100.0, 1.0);
+        let samples: Vec<f64> = (0..20_000).map(|_| beyond.sample(&mut rng)).collect();
+        let empirical = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((empirical - 0.01).abs() < 0.001, "empirical {empirical}");
+    }
+
+    #[test]
+    fn truncated_normal_far_tail_reserve_is_finite() {
+        // P(X >= 0) underflows to zero here; the tail above zero is close to Exp(rate 40).
+        let dist = TruncatedNormal::new(-40.0, 1.0);
+        let reserve = dist.reserve_price();
+        // Solve x = 1/(x + 40) for the exponential-tail approximation.
+        let approx = (-40.0 + (1600.0_f64 + 4.0).sqrt()) / 2.0;
+        assert!((reserve - approx).abs() < 1e-4, "reserve {reserve}");
+        assert!(dist.virtual_value(reserve).abs() < 1e-9);
+        assert!((dist.pdf(0.0) - 40.0).abs() < 0.1);
+        let cdf = dist.cdf(reserve);
+        assert!(
+            (cdf - (1.0 - (-40.0 * reserve).exp())).abs() < 1e-3,
+            "cdf {cdf}"
+        );
+
+        // Away from the tail the log-space forms match the direct ones.
+        let near = TruncatedNormal::new(1.0, 2.0);
+        let normal = Normal::new(1.0, 2.0).expect("valid normal");
+        let kept = 1.0 - normal.cdf(0.0);
+        assert!((near.cdf(3.0) - (normal.cdf(3.0) - normal.cdf(0.0)) / kept).abs() < 1e-12);
+        assert!((near.pdf(3.0) - normal.pdf(3.0) / kept).abs() < 1e-12);
+    }
 }
 
 impl ValueDistribution for LogNormal {
@@ -283,8 +451,13 @@ pub struct EqualRevenue {
 
 impl EqualRevenue {
     pub fn new(scale: f64) -> Self {
-        assert!(scale > 0.0, "scale must be positive");
-        Self { scale }
+        Self::try_new(scale).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(scale: f64) -> Result<Self, DistributionError> {
+        Ok(Self {
+            scale: positive("scale", scale)?,
+        })
     }
 }
 
@@ -322,3 +495,219 @@ impl ValueDistribution for EqualRevenue {
         self.scale / (1.0 - u)
     }
 }
+
+/// Normal(mean, sd) conditioned on being non-negative: F(x)=(Φ(x)-Φ(0))/(1-Φ(0)) for x>=0.
+#[derive(Clone, Debug)]
+pub struct TruncatedNormal {
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl TruncatedNormal {
+    pub fn new(mean: f64, sd: f64) -> Self {
+        Self::try_new(mean, sd).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(mean: f64, sd: f64) -> Result<Self, DistributionError> {
+        Ok(Self {
+            mean,
+            sd: positive("sd", sd)?,
+        })
+    }
+
+    /// `(x - mean) / sd`.
+    fn standardized(&self, x: f64) -> f64 {
+        (x - self.mean) / self.sd
+    }
+
+    /// ln P(X >= 0) for the untruncated normal, which underflows to -inf only in log space.
+    fn ln_kept(&self) -> f64 {
+        ln_upper_tail(self.standardized(0.0))
+    }
+}
+
+/// Beyond this the normal upper tail is taken from its asymptotic series.
+const TAIL_SERIES_FROM: f64 = 30.0;
+
+/// ln φ(t) for the standard normal density.
+fn ln_standard_pdf(t: f64) -> f64 {
+    -0.5 * t * t - 0.5 * (2.0 * std::f64::consts::PI).ln()
+}
+
+/// ln(1 - Φ(t)), the standard normal upper tail, accurate where the tail itself underflows.
+fn ln_upper_tail(t: f64) -> f64 {
+    if t < TAIL_SERIES_FROM {
+        Normal::new(0.0, 1.0).expect("standard normal").cdf(-t).ln()
+    } else {
+        // Mills ratio (1 - Φ(t)) / φ(t) = (1/t)(1 - 1/t² + 3/t⁴ - 15/t⁶ + 105/t⁸ - ...).
+        let inv = 1.0 / (t * t);
+        let mills = (1.0 - inv * (1.0 - 3.0 * inv * (1.0 - 5.0 * inv * (1.0 - 7.0 * inv)))) / t;
+        ln_standard_pdf(t) + mills.ln()
+    }
+}
+
+impl ValueDistribution for TruncatedNormal {
+    /// Both tails are normalised in log space by P(X >= 0), so a mean far below zero gives
+    /// finite values where `1 - Φ(0)` would round to zero.
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        -(ln_upper_tail(self.standardized(x)) - self.ln_kept()).exp_m1()
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        (ln_standard_pdf(self.standardized(x)) - self.ln_kept()).exp() / self.sd
+    }
+
+    /// φ(x) = x - sd·(1 - Φ(z))/φ(z) with `z = (x - mean) / sd`: the truncation cancels out of
+    /// the hazard rate.
+    fn virtual_value(&self, x: f64) -> f64 {
+        let z = self.standardized(x);
+        x - self.sd * (ln_upper_tail(z) - ln_standard_pdf(z)).exp()
+    }
+
+    fn strong_regular_alpha(&self) -> Option<f64> {
+        // The density is log-concave, so the hazard rate is monotone.
+        Some(1.0)
+    }
+
+    /// Inverse-cdf sampling of the tail `Z >= -mean / sd` of a standard normal, so no draw is
+    /// rejected however little mass lies above zero. This replaces the rejection sampler first
+    /// asked for, which never returns once almost all the mass is negative.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let standard = Normal::new(0.0, 1.0).expect("standard normal");
+        // P(X >= 0), taken from the lower tail so it keeps its precision when tiny.
+        let kept = standard.cdf(self.mean / self.sd);
+        let u: f64 = rng.gen_range(0.0..1.0);
+        if kept == 0.0 {
+            // Beyond f64 range the tail above zero is exponential with rate -mean / sd^2.
+            return -(1.0 - u).ln() * self.sd * self.sd / -self.mean;
+        }
+        let z = -standard.inverse_cdf(kept * (1.0 - u));
+        (self.mean + self.sd * z).max(0.0)
+    }
+}
+
+/// Object-safe view of [`ValueDistribution`], so buyers drawing from different families can
+/// share one collection. Every cloneable, thread-safe distribution implements it, and
+/// `Box<dyn DynValueDistribution>` is itself a `ValueDistribution`.
+pub trait DynValueDistribution: fmt::Debug + Send + Sync {
+    fn cdf_dyn(&self, x: f64) -> f64;
+    fn pdf_dyn(&self, x: f64) -> f64;
+    fn virtual_value_dyn(&self, x: f64) -> f64;
+    fn reserve_price_dyn(&self) -> f64;
+    fn quantile_dyn(&self, p: f64) -> f64;
+    fn strong_regular_alpha_dyn(&self) -> Option<f64>;
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> f64;
+    fn clone_box(&self) -> Box<dyn DynValueDistribution>;
+}
+
+impl<D: ValueDistribution + fmt::Debug + Send + Sync + 'static> DynValueDistribution for D {
+    fn cdf_dyn(&self, x: f64) -> f64 {
+        self.cdf(x)
+    }
+
+    fn pdf_dyn(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn virtual_value_dyn(&self, x: f64) -> f64 {
+        self.virtual_value(x)
+    }
+
+    fn reserve_price_dyn(&self) -> f64 {
+        self.reserve_price()
+    }
+
+    fn quantile_dyn(&self, p: f64) -> f64 {
+        self.quantile(p)
+    }
+
+    fn strong_regular_alpha_dyn(&self) -> Option<f64> {
+        self.strong_regular_alpha()
+    }
+
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> f64 {
+        self.sample(rng)
+    }
+
+    fn clone_box(&self) -> Box<dyn DynValueDistribution> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynValueDistribution> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl ValueDistribution for Box<dyn DynValueDistribution> {
+    fn cdf(&self, x: f64) -> f64 {
+        self.as_ref().cdf_dyn(x)
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        self.as_ref().pdf_dyn(x)
+    }
+
+    fn virtual_value(&self, x: f64) -> f64 {
+        self.as_ref().virtual_value_dyn(x)
+    }
+
+    fn reserve_price(&self) -> f64 {
+        self.as_ref().reserve_price_dyn()
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        self.as_ref().quantile_dyn(p)
+    }
+
+    fn strong_regular_alpha(&self) -> Option<f64> {
+        self.as_ref().strong_regular_alpha_dyn()
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> f64 {
+        self.as_ref().sample_dyn(&mut rng)
+    }
+}
+
+/// Equal-weight mixture: the value of a buyer picked uniformly at random from a heterogeneous
+/// pool. A mixture of regular distributions need not be regular, so no α is reported.
+#[derive(Clone, Debug)]
+pub struct Mixture {
+    pub components: Vec<Box<dyn DynValueDistribution>>,
+}
+
+impl Mixture {
+    pub fn new(components: Vec<Box<dyn DynValueDistribution>>) -> Self {
+        assert!(
+            !components.is_empty(),
+            "mixture needs at least one component"
+        );
+        Self { components }
+    }
+
+    fn mean_of(&self, f: impl Fn(&Box<dyn DynValueDistribution>) -> f64) -> f64 {
+        self.components.iter().map(f).sum::<f64>() / self.components.len() as f64
+    }
+}
+
+impl ValueDistribution for Mixture {
+    fn cdf(&self, x: f64) -> f64 {
+        self.mean_of(|d| d.cdf(x))
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        self.mean_of(|d| d.pdf(x))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let pick = rng.gen_range(0..self.components.len());
+        self.components[pick].sample(rng)
+    }
+}
@@ -1,5 +1,6 @@
 pub mod auction;
 pub mod centralized;
+pub mod clock;
 pub mod collateral;
 pub mod commitment;
 pub mod distribution;
@@ -8,29 +9,56 @@ pub mod protocol;
 pub mod simulation;
 
 pub use auction::{
-    AuctionOutcome, AuditError, CommitmentEvent, FalseBid, ParticipantId, PublicBroadcastDRA,
-    RevealEvent, Transcript, audit_transcript,
+    AllocationRule, AuctionError, AuctionOutcome, AuditError, AuditReport, CollateralPolicy,
+    CommitmentEvent, DraBuilder, FalseBid, ParseParticipantIdError, ParticipantId, ParticipantKind,
+    PublicBroadcastDRA, PublishedRootAudit, ReserveComparison, RevealEvent, RunDiff, TieBreak,
+    Transcript, ValidationError, audit_transcript, audit_transcript_report, diff_runs,
+    recompute_outcome,
 };
 pub use centralized::{
     AdaptiveReserveDeviationReport, CentralizedDeviationResult, CentralizedProtocolDriver,
-    adaptive_reserve_deviation, scripted_adaptive_reserve_run,
+    SCRIPTED_SCHEDULE, adaptive_reserve_deviation, adaptive_revenue, scripted_adaptive_reserve_run,
+    scripted_adaptive_reserve_run_n, scripted_adaptive_reserve_run_with_seeds,
+    sweep_adaptive_threshold,
 };
 
 pub mod audit;
 pub fn run_audit() {
-    audit::emit_provenance().expect("audit run");
+    audit::emit_provenance(None).expect("audit run");
 }
-pub use collateral::collateral_requirement;
+pub use clock::{Clock, LogicalClock, SystemClock};
+pub use collateral::{
+    CollateralBreakdown, collateral_breakdown, collateral_requirement,
+    collateral_requirement_with_shills, safe_deviation_gain, theoretical_safe_bound,
+    try_collateral_requirement,
+};
 pub use commitment::{
-    AuditLedger, AuditReceipt, AuditedNonMalleableCommitment, BulletproofProofData,
-    BulletproofsCommitment, Commitment, CommitmentScheme, NonMalleableShaCommitment,
-    PedersenRistrettoCommitment, RealNonMalleableCommitment,
+    AggregateMembership, AggregatedRangeProof, AggregationError, AuditLedger, AuditReceipt,
+    AuditedNonMalleableCommitment, BidCodec, BidEncodingError, Blake3Commitment,
+    BulletproofProofData, BulletproofsCommitment, CommitError, Commitment, CommitmentScheme,
+    DynCommitmentScheme, InvalidRangeBits, NonMalleableShaCommitment, PedersenRistrettoCommitment,
+    RealNonMalleableCommitment, SchemeFactory, SchemeRegistry, SharedRangeProof, SizeReport,
+    check_non_malleable, cross_commit, cross_verify,
+};
+pub use distribution::{
+    DistributionError, DynValueDistribution, EqualRevenue, Exponential, LogNormal, Mixture,
+    Pareto, TruncatedNormal, Uniform, ValueDistribution,
 };
-pub use distribution::{EqualRevenue, Exponential, LogNormal, Pareto, Uniform, ValueDistribution};
 pub use auction::PhaseTimings;
-pub use protocol::{Phase, ProtocolError, ProtocolSession};
+pub use protocol::{
+    Action, EventObserver, NetworkPartition, Phase, ProtocolError, ProtocolSession,
+    SessionCheckpoint,
+};
 pub use simulation::{
-    Backend, DeviationModel, RevenueStats, SafeDeviationStats, SimulationResult,
-    TimedSimulationReport, simulate_deviation, simulate_deviation_with_scheme,
-    simulate_false_bid_impact, simulate_safe_deviation_bound, simulate_timed_protocol,
+    AdaptiveFalseBids, AllocationChangeBreakdown, Backend, CensorshipSimulationResult,
+    DeviationModel, Histogram, RevenueStats, SafeDeviationStats, SimulationResult,
+    TimedSimulationReport, TrialRecord, ViolationWitness, WinnerTally, bidder_regret,
+    collateral_for_deterrence, deviation_trials, empirical_optimal_reserve, optimize_false_bid,
+    revenue_curve, simulate_censorship_deviation, simulate_deviation,
+    simulate_deviation_asymmetric, simulate_deviation_histogram, simulate_deviation_parallel,
+    simulate_deviation_records, simulate_deviation_with_order_stats,
+    simulate_deviation_with_records, simulate_deviation_with_scheme, simulate_false_bid_impact,
+    simulate_safe_deviation_bound, simulate_safe_deviation_bound_with_collateral, simulate_sweep,
+    simulate_sweep_parallel, simulate_timed_protocol, simulate_timed_protocol_with_reveal_failures,
+    vcg_outcome, write_records_csv, write_records_jsonl,
 };
@@ -1,17 +1,19 @@
 use std::fs::File;
-use std::io::{self, Read};
-use std::path::PathBuf;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
 
 use clap::{Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
 
 use broadcast_dra::{
-    AdaptiveReserveDeviationReport, AuditedNonMalleableCommitment, BulletproofsCommitment,
-    CentralizedDeviationResult, CentralizedProtocolDriver, DeviationModel, EqualRevenue, Exponential, FalseBid, LogNormal,
-    NonMalleableShaCommitment, Pareto, ParticipantId, PedersenRistrettoCommitment,
-    PhaseTimings, PublicBroadcastDRA, RealNonMalleableCommitment, SafeDeviationStats,
-    SimulationResult, Uniform, ValueDistribution,
-    scripted_adaptive_reserve_run, simulate_deviation_with_scheme, simulate_safe_deviation_bound,
+    AdaptiveReserveDeviationReport, AuditError, BulletproofsCommitment, CentralizedDeviationResult,
+    CentralizedProtocolDriver, DeviationModel, DistributionError, DynCommitmentScheme,
+    EqualRevenue, Exponential, FalseBid, LogNormal, NonMalleableShaCommitment, Pareto,
+    ParticipantId, ParticipantKind, PhaseTimings, PublicBroadcastDRA, PublishedRootAudit,
+    SafeDeviationStats, SchemeRegistry, SimulationResult, Transcript, TruncatedNormal, Uniform,
+    ValidationError, ValueDistribution, audit_transcript, scripted_adaptive_reserve_run,
+    simulate_deviation_with_records, simulate_deviation_with_scheme, simulate_safe_deviation_bound,
+    write_records_csv, write_records_jsonl,
 };
 use broadcast_dra::network::CentralizedChannel;
 
@@ -27,6 +29,10 @@ struct CliArgs {
     #[arg(long, value_enum)]
     backend: Option<CommitmentBackendSpec>,
 
+    /// Range size in bits for the Bulletproofs backend (overrides JSON).
+    #[arg(long)]
+    range_bits: Option<usize>,
+
     /// If set, run a simulation instead of a single auction.
     #[arg(long)]
     simulate: bool,
@@ -35,6 +41,27 @@ struct CliArgs {
     #[arg(long, default_value_t = 500)]
     trials: usize,
 
+    /// With `--simulate`, also write per-trial records to this path (JSON Lines if it ends in
+    /// `.jsonl`, CSV otherwise).
+    #[arg(long)]
+    records_out: Option<PathBuf>,
+
+    /// Write the JSON result to this path instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Include the commitment/reveal/broadcast transcript in each auction response.
+    #[arg(long)]
+    with_transcript: bool,
+
+    /// Check the request(s) for problems and print a report instead of running anything.
+    #[arg(long)]
+    validate: bool,
+
+    /// Emit one compact JSON response per line instead of a pretty-printed document.
+    #[arg(long)]
+    jsonl: bool,
+
     /// Run a canned demonstration scenario instead of a free-form auction.
     #[arg(long, value_enum)]
     scenario: Option<ScenarioSpec>,
@@ -42,6 +69,20 @@ struct CliArgs {
     /// If set, emit an audit/provenance report (alias for `cargo audit` target).
     #[arg(long)]
     audit: bool,
+
+    /// Override `rng_seed` in every request and the fixed seeds of the canned scenarios.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Audit a saved JSON transcript offline with the `--backend` it should have used, and
+    /// print "OK" or the audit error.
+    #[arg(long)]
+    verify_transcript: Option<PathBuf>,
+
+    /// Ledger root the auctioneer published, as 64 hex digits. Required to verify a transcript
+    /// from the audited backend.
+    #[arg(long, value_parser = parse_root)]
+    trusted_root: Option<[u8; 32]>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,6 +92,8 @@ enum DistributionSpec {
     Uniform { low: f64, high: f64 },
     Pareto { scale: f64, shape: f64 },
     Lognormal { mu: f64, sigma: f64 },
+    #[serde(rename = "truncated_normal")]
+    TruncatedNormal { mean: f64, sd: f64 },
 }
 
 #[derive(Debug, Deserialize)]
@@ -70,12 +113,15 @@ struct AuctionRequest {
     rng_seed: Option<u64>,
     #[serde(default = "default_backend")]
     commitment_backend: CommitmentBackendSpec,
+    #[serde(default)]
+    bulletproof_range_bits: Option<usize>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 enum CommitmentBackendSpec {
     Sha,
+    Blake3,
     Pedersen,
     Audited,
     Fischlin,
@@ -103,10 +149,25 @@ struct AuctionResponse {
     payment: f64,
     transferred_collateral: f64,
     forfeited_to_auctioneer: f64,
-    valid_bids: Vec<(String, f64)>,
+    valid_bids: Vec<ValidBid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transcript: Option<Transcript>,
 }
 
-type Backend = broadcast_dra::Backend;
+#[derive(Debug, Serialize)]
+struct ValidBid {
+    kind: ParticipantKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    bid: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct ValidationReport {
+    valid: bool,
+    alpha: f64,
+    problems: Vec<String>,
+}
 
 fn main() -> io::Result<()> {
     let args = CliArgs::parse();
@@ -114,8 +175,30 @@ fn main() -> io::Result<()> {
         broadcast_dra::run_audit();
         return Ok(());
     }
+    if let Some(path) = args.verify_transcript {
+        let Some(spec) = args.backend.as_ref() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--verify-transcript needs the expected --backend",
+            ));
+        };
+        let backend = verification_backend(spec, args.range_bits, args.trusted_root)?;
+        return match verify_transcript(&path, backend)? {
+            Ok(()) => {
+                println!("OK");
+                Ok(())
+            }
+            Err(err) => {
+                println!("{err}");
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "transcript failed audit",
+                ))
+            }
+        };
+    }
     if let Some(scenario) = args.scenario {
-        return run_scenario(scenario);
+        return run_scenario(scenario, args.seed, args.output.as_deref());
     }
     let mut input = String::new();
     match args.input {
@@ -127,47 +210,317 @@ fn main() -> io::Result<()> {
             io::stdin().read_to_string(&mut input)?;
         }
     }
-    let mut req: AuctionRequest =
-        serde_json::from_str(&input).expect("Invalid JSON input for auction");
-    if let Some(b) = args.backend {
-        req.commitment_backend = b;
+    let (mut requests, batch) = parse_requests(&input)?;
+    for req in &mut requests {
+        if let Some(b) = args.backend.clone() {
+            req.commitment_backend = b;
+        }
+        if let Some(bits) = args.range_bits {
+            req.bulletproof_range_bits = Some(bits);
+        }
+        if let Some(seed) = args.seed {
+            req.rng_seed = Some(seed);
+        }
+    }
+    let output = args.output.as_deref();
+
+    if args.validate {
+        let reports: Vec<ValidationReport> = requests.iter().map(validate_request).collect();
+        if batch {
+            write_json(&reports, output)?;
+        } else {
+            write_json(&reports[0], output)?;
+        }
+        if reports.iter().any(|report| !report.valid) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "auction request failed validation",
+            ));
+        }
+        return Ok(());
     }
 
     if args.simulate {
-        run_simulation(req, args.trials)
+        let (Some(req), false) = (requests.pop(), batch) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--simulate takes a single auction request, not an array",
+            ));
+        };
+        run_simulation(req, args.trials, args.records_out.as_deref(), output)
     } else {
-        match req.distribution {
-            DistributionSpec::Exponential { lambda } => {
-                run_with_dist(Exponential::new(lambda), req)
-            }
-            DistributionSpec::Uniform { low, high } => run_with_dist(Uniform::new(low, high), req),
-            DistributionSpec::Pareto { scale, shape } => {
-                run_with_dist(Pareto::new(scale, shape), req)
-            }
-            DistributionSpec::Lognormal { mu, sigma } => {
-                run_with_dist(LogNormal::new(mu, sigma), req)
-            }
+        let responses = requests
+            .into_iter()
+            .map(|req| run_auction(req, args.with_transcript))
+            .collect::<io::Result<Vec<_>>>()?;
+        if args.jsonl {
+            write_json_lines(&responses, output)
+        } else if batch {
+            write_json(&responses, output)
+        } else {
+            write_json(&responses[0], output)
+        }
+    }
+}
+
+/// Parse either a single request or, when the input is a JSON array, a batch of them.
+fn parse_requests(input: &str) -> io::Result<(Vec<AuctionRequest>, bool)> {
+    let invalid = |err: serde_json::Error| json_input_error(input, err);
+    if input.trim_start().starts_with('[') {
+        let requests = serde_json::from_str(input).map_err(invalid)?;
+        Ok((requests, true))
+    } else {
+        let request = serde_json::from_str(input).map_err(invalid)?;
+        Ok((vec![request], false))
+    }
+}
+
+/// Turn a serde parse failure into an `InvalidData` error naming the byte offset.
+fn json_input_error(input: &str, err: serde_json::Error) -> io::Error {
+    let offset: usize = input
+        .split_inclusive('\n')
+        .take(err.line().saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>()
+        + err.column().saturating_sub(1);
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid auction request JSON at byte {offset}: {err}"),
+    )
+}
+
+/// Load a serialized [`Transcript`] and audit it with the caller's `backend`.
+fn verify_transcript(
+    path: &Path,
+    mut backend: Box<dyn DynCommitmentScheme>,
+) -> io::Result<Result<(), AuditError>> {
+    let json = std::fs::read_to_string(path)?;
+    let transcript: Transcript = serde_json::from_str(&json).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid transcript JSON: {err}"),
+        )
+    })?;
+    Ok(audit_transcript(&transcript, &mut backend))
+}
+
+/// Fresh backend for an offline audit. Audited receipts are only checked against the root the
+/// auctioneer published, so that backend needs `trusted_root`.
+fn verification_backend(
+    spec: &CommitmentBackendSpec,
+    range_bits: Option<usize>,
+    trusted_root: Option<[u8; 32]>,
+) -> io::Result<Box<dyn DynCommitmentScheme>> {
+    match (spec, trusted_root) {
+        (CommitmentBackendSpec::Audited, Some(root)) => Ok(Box::new(PublishedRootAudit::new(root))),
+        (CommitmentBackendSpec::Audited, None) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "verifying an audited transcript needs --trusted-root",
+        )),
+        _ => backend_for(spec, range_bits),
+    }
+}
+
+fn parse_root(hex: &str) -> Result<[u8; 32], String> {
+    let digits = hex.as_bytes();
+    if digits.len() != 64 {
+        return Err(format!("expected 64 hex digits, got {}", digits.len()));
+    }
+    let mut root = [0u8; 32];
+    for (byte, pair) in root.iter_mut().zip(digits.chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|err| err.to_string())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|err| format!("{pair:?}: {err}"))?;
+    }
+    Ok(root)
+}
+
+fn run_auction(req: AuctionRequest, with_transcript: bool) -> io::Result<AuctionResponse> {
+    match req.distribution {
+        DistributionSpec::Exponential { lambda } => {
+            run_with_dist(Exponential::try_new(lambda).map_err(invalid_distribution)?, req, with_transcript)
+        }
+        DistributionSpec::Uniform { low, high } => {
+            run_with_dist(Uniform::try_new(low, high).map_err(invalid_distribution)?, req, with_transcript)
+        }
+        DistributionSpec::Pareto { scale, shape } => {
+            run_with_dist(Pareto::try_new(scale, shape).map_err(invalid_distribution)?, req, with_transcript)
+        }
+        DistributionSpec::Lognormal { mu, sigma } => {
+            run_with_dist(LogNormal::try_new(mu, sigma).map_err(invalid_distribution)?, req, with_transcript)
+        }
+        DistributionSpec::TruncatedNormal { mean, sd } => {
+            run_with_dist(TruncatedNormal::try_new(mean, sd).map_err(invalid_distribution)?, req, with_transcript)
+        }
+    }
+}
+
+fn invalid_distribution(err: DistributionError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err)
+}
+
+fn build_backend(req: &AuctionRequest) -> io::Result<Box<dyn DynCommitmentScheme>> {
+    backend_for(&req.commitment_backend, req.bulletproof_range_bits)
+}
+
+fn backend_for(
+    spec: &CommitmentBackendSpec,
+    range_bits: Option<usize>,
+) -> io::Result<Box<dyn DynCommitmentScheme>> {
+    if let (CommitmentBackendSpec::Bulletproofs, Some(bits)) = (spec, range_bits) {
+        let scheme = BulletproofsCommitment::try_new(bits).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "bulletproof_range_bits must be 8, 16, 32, or 64 (got {})",
+                    err.requested
+                ),
+            )
+        })?;
+        return Ok(Box::new(scheme));
+    }
+    let spec = spec.to_possible_value();
+    let name = spec.as_ref().map_or("sha", |v| v.get_name());
+    SchemeRegistry::default().create(name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unknown commitment backend {}", name),
+        )
+    })
+}
+
+/// Pretty-print `value` as JSON to `output`, or to stdout when no path is given.
+fn write_json<T: Serialize>(value: &T, output: Option<&Path>) -> io::Result<()> {
+    match output {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(&mut writer, value)?;
+            writeln!(writer)?;
+            writer.flush()
+        }
+        None => {
+            serde_json::to_writer_pretty(io::stdout(), value)?;
+            println!();
+            Ok(())
+        }
+    }
+}
+
+fn validate_request(req: &AuctionRequest) -> ValidationReport {
+    match req.distribution {
+        DistributionSpec::Exponential { lambda } => {
+            validate_with_dist(Exponential::try_new(lambda), req)
+        }
+        DistributionSpec::Uniform { low, high } => {
+            validate_with_dist(Uniform::try_new(low, high), req)
+        }
+        DistributionSpec::Pareto { scale, shape } => {
+            validate_with_dist(Pareto::try_new(scale, shape), req)
+        }
+        DistributionSpec::Lognormal { mu, sigma } => {
+            validate_with_dist(LogNormal::try_new(mu, sigma), req)
+        }
+        DistributionSpec::TruncatedNormal { mean, sd } => {
+            validate_with_dist(TruncatedNormal::try_new(mean, sd), req)
         }
     }
 }
 
-fn run_with_dist<D: ValueDistribution + 'static>(dist: D, req: AuctionRequest) -> io::Result<()> {
+/// The request's alpha, or the distribution's strong-regularity bound when none is given.
+/// An explicit alpha beyond that bound is an error rather than being clamped to it, as is a
+/// distribution that supports no positive alpha at all.
+fn resolve_alpha<D: ValueDistribution>(req: &AuctionRequest, dist: &D) -> io::Result<f64> {
     let alpha = req
         .alpha
         .or_else(|| dist.strong_regular_alpha())
         .unwrap_or(1.0);
-    let dra = PublicBroadcastDRA::new(dist, alpha);
-    let mut backend = match req.commitment_backend {
-        CommitmentBackendSpec::Sha => Backend::Sha(NonMalleableShaCommitment),
-        CommitmentBackendSpec::Pedersen => Backend::Pedersen(PedersenRistrettoCommitment),
-        CommitmentBackendSpec::Audited => {
-            Backend::Audited(AuditedNonMalleableCommitment::default())
+    if alpha.is_nan() || alpha <= 0.0 {
+        let err = ValidationError::NonPositiveAlpha(alpha);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, err));
+    }
+    if let Some(supported) = dist.strong_regular_alpha()
+        && alpha > supported + f64::EPSILON
+    {
+        let err = ValidationError::AlphaTooLarge {
+            requested: alpha,
+            supported,
+        };
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, err));
+    }
+    Ok(alpha)
+}
+
+/// Collect every problem `run_with_dist` would hit, without running the auction. Alpha can
+/// only be checked against a distribution whose parameters are valid.
+fn validate_with_dist<D: ValueDistribution>(
+    dist: Result<D, DistributionError>,
+    req: &AuctionRequest,
+) -> ValidationReport {
+    let mut problems = Vec::new();
+    let alpha = match dist {
+        Ok(dist) => {
+            // An infeasible alpha is reported by `validate_inputs`, alongside any other problem.
+            let alpha = resolve_alpha(req, &dist).unwrap_or_else(|_| req.alpha.unwrap_or(1.0));
+            let checked = PublicBroadcastDRA::builder()
+                .distribution(dist)
+                .alpha(alpha)
+                .build()
+                .and_then(|dra| dra.validate_inputs(req.valuations.len()));
+            if let Err(err) = checked {
+                problems.push(err.to_string());
+            }
+            alpha
+        }
+        Err(err) => {
+            problems.push(err.to_string());
+            req.alpha.unwrap_or(1.0)
         }
-        CommitmentBackendSpec::Fischlin => Backend::Fischlin(RealNonMalleableCommitment),
-        CommitmentBackendSpec::Bulletproofs => {
-            Backend::Bulletproofs(BulletproofsCommitment::default())
+    };
+    for (i, v) in req.valuations.iter().enumerate() {
+        if !v.is_finite() || *v < 0.0 {
+            problems.push(format!(
+                "valuations[{i}] = {v} is not finite and non-negative"
+            ));
+        }
+    }
+    for (i, fb) in req.false_bids.iter().enumerate() {
+        if !fb.bid.is_finite() || fb.bid < 0.0 {
+            problems.push(format!(
+                "false_bids[{i}] = {} is not finite and non-negative",
+                fb.bid
+            ));
         }
+    }
+    if let Err(err) = build_backend(req) {
+        problems.push(err.to_string());
+    }
+    ValidationReport {
+        valid: problems.is_empty(),
+        alpha,
+        problems,
+    }
+}
+
+/// Write each value as one compact JSON line to `output`, or to stdout.
+fn write_json_lines<T: Serialize>(values: &[T], output: Option<&Path>) -> io::Result<()> {
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
     };
+    for value in values {
+        serde_json::to_writer(&mut writer, value)?;
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+fn run_with_dist<D: ValueDistribution + 'static>(
+    dist: D,
+    req: AuctionRequest,
+    with_transcript: bool,
+) -> io::Result<AuctionResponse> {
+    let alpha = resolve_alpha(&req, &dist)?;
+    let dra = PublicBroadcastDRA::new(dist, alpha);
+    let mut backend = build_backend(&req)?;
     let fbs: Vec<FalseBid> = req
         .false_bids
         .iter()
@@ -176,25 +529,16 @@ fn run_with_dist<D: ValueDistribution + 'static>(dist: D, req: AuctionRequest) -
             reveal: fb.reveal,
         })
         .collect();
-    let outcome = match &mut backend {
-        Backend::Sha(s) => {
-            dra.run_with_false_bids_using_scheme(&req.valuations, &fbs, req.rng_seed, s)
-        }
-        Backend::Pedersen(p) => {
-            dra.run_with_false_bids_using_scheme(&req.valuations, &fbs, req.rng_seed, p)
-        }
-        Backend::Audited(a) => {
-            dra.run_with_false_bids_using_scheme(&req.valuations, &fbs, req.rng_seed, a)
-        }
-        Backend::Fischlin(f) => {
-            dra.run_with_false_bids_using_scheme(&req.valuations, &fbs, req.rng_seed, f)
-        }
-        Backend::Bulletproofs(b) => {
-            dra.run_with_false_bids_using_scheme(&req.valuations, &fbs, req.rng_seed, b)
-        }
-    };
+    let (outcome, transcript) = dra
+        .try_run_with_false_bids_using_scheme_with_transcript(
+            &req.valuations,
+            &fbs,
+            req.rng_seed,
+            &mut backend,
+        )
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
 
-    let resp = AuctionResponse {
+    Ok(AuctionResponse {
         reserve: outcome.reserve,
         collateral: outcome.collateral,
         winner: outcome.winner.as_ref().map(|w| format!("{:?}", w)),
@@ -205,35 +549,62 @@ fn run_with_dist<D: ValueDistribution + 'static>(dist: D, req: AuctionRequest) -
         valid_bids: outcome
             .valid_bids
             .iter()
-            .map(|(id, b)| (format!("{:?}", id), *b))
+            .map(|(id, bid)| ValidBid {
+                kind: id.kind(),
+                index: id.index(),
+                bid: *bid,
+            })
             .collect(),
-    };
-
-    serde_json::to_writer_pretty(io::stdout(), &resp)?;
-    println!();
-    Ok(())
+        transcript: with_transcript.then_some(transcript),
+    })
 }
 
-fn run_simulation(req: AuctionRequest, trials: usize) -> io::Result<()> {
-    let buyers = req.valuations.len();
-    if buyers == 0 {
+fn run_simulation(
+    req: AuctionRequest,
+    trials: usize,
+    records_out: Option<&Path>,
+    output: Option<&Path>,
+) -> io::Result<()> {
+    if req.valuations.is_empty() {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
             "valuations must be non-empty to infer buyer count for simulation",
         ));
     }
-    let alpha = req.alpha.unwrap_or(1.0);
-    let backend = match req.commitment_backend {
-        CommitmentBackendSpec::Sha => Backend::Sha(NonMalleableShaCommitment),
-        CommitmentBackendSpec::Pedersen => Backend::Pedersen(PedersenRistrettoCommitment),
-        CommitmentBackendSpec::Audited => {
-            Backend::Audited(AuditedNonMalleableCommitment::default())
+    match req.distribution {
+        DistributionSpec::Exponential { lambda } => {
+            simulate_with_dist(Exponential::try_new(lambda).map_err(invalid_distribution)?, &req, trials, records_out, output)
         }
-        CommitmentBackendSpec::Fischlin => Backend::Fischlin(RealNonMalleableCommitment),
-        CommitmentBackendSpec::Bulletproofs => {
-            Backend::Bulletproofs(BulletproofsCommitment::default())
+        DistributionSpec::Uniform { low, high } => {
+            simulate_with_dist(Uniform::try_new(low, high).map_err(invalid_distribution)?, &req, trials, records_out, output)
         }
-    };
+        DistributionSpec::Pareto { scale, shape } => {
+            simulate_with_dist(Pareto::try_new(scale, shape).map_err(invalid_distribution)?, &req, trials, records_out, output)
+        }
+        DistributionSpec::Lognormal { mu, sigma } => {
+            simulate_with_dist(LogNormal::try_new(mu, sigma).map_err(invalid_distribution)?, &req, trials, records_out, output)
+        }
+        DistributionSpec::TruncatedNormal { mean, sd } => simulate_with_dist(
+            TruncatedNormal::try_new(mean, sd).map_err(invalid_distribution)?,
+            &req,
+            trials,
+            records_out,
+            output,
+        ),
+    }
+}
+
+fn simulate_with_dist<D: ValueDistribution + Clone>(
+    dist: D,
+    req: &AuctionRequest,
+    trials: usize,
+    records_out: Option<&Path>,
+    output: Option<&Path>,
+) -> io::Result<()> {
+    let buyers = req.valuations.len();
+    let alpha = resolve_alpha(req, &dist)?;
+    let seed = req.rng_seed.unwrap_or(1);
+    let backend = build_backend(req)?;
     let deviation = if req.false_bids.len() > 1 {
         DeviationModel::Multiple(
             req.false_bids
@@ -256,48 +627,20 @@ fn run_simulation(req: AuctionRequest, trials: usize) -> io::Result<()> {
         })
     };
 
-    let sims: SimulationResult = match req.distribution {
-        DistributionSpec::Exponential { lambda } => simulate_deviation_with_scheme(
-            Exponential::new(lambda),
-            alpha,
-            buyers,
-            trials,
-            deviation,
-            req.rng_seed.unwrap_or(1),
-            backend,
-        ),
-        DistributionSpec::Uniform { low, high } => simulate_deviation_with_scheme(
-            Uniform::new(low, high),
-            alpha,
-            buyers,
-            trials,
-            deviation,
-            req.rng_seed.unwrap_or(1),
-            backend,
-        ),
-        DistributionSpec::Pareto { scale, shape } => simulate_deviation_with_scheme(
-            Pareto::new(scale, shape),
-            alpha,
-            buyers,
-            trials,
-            deviation,
-            req.rng_seed.unwrap_or(1),
-            backend,
-        ),
-        DistributionSpec::Lognormal { mu, sigma } => simulate_deviation_with_scheme(
-            LogNormal::new(mu, sigma),
-            alpha,
-            buyers,
-            trials,
-            deviation,
-            req.rng_seed.unwrap_or(1),
-            backend,
-        ),
+    let Some(path) = records_out else {
+        let sims: SimulationResult =
+            simulate_deviation_with_scheme(dist, alpha, buyers, trials, deviation, seed, backend);
+        return write_json(&sims, output);
     };
-
-    serde_json::to_writer_pretty(io::stdout(), &sims)?;
-    println!();
-    Ok(())
+    let (sims, records) =
+        simulate_deviation_with_records(dist, alpha, buyers, trials, deviation, seed, backend);
+    let writer = BufWriter::new(File::create(path)?);
+    if path.extension().is_some_and(|ext| ext == "jsonl") {
+        write_records_jsonl(&records, writer)?;
+    } else {
+        write_records_csv(&records, writer)?;
+    }
+    write_json(&sims, output)
 }
 
 #[derive(Serialize)]
@@ -334,7 +677,7 @@ fn summarize_channel(channel: &CentralizedChannel) -> (Vec<String>, Vec<String>)
     (deliveries, omissions)
 }
 
-fn run_scenario(spec: ScenarioSpec) -> io::Result<()> {
+fn run_scenario(spec: ScenarioSpec, seed: Option<u64>, output: Option<&Path>) -> io::Result<()> {
     match spec {
         ScenarioSpec::Example1 => {
             let dist = Uniform::new(0.0, 20.0);
@@ -351,25 +694,31 @@ fn run_scenario(spec: ScenarioSpec) -> io::Result<()> {
             driver.commit_real(0, 10.0);
             driver.commit_real(1, 5.0);
             driver.forward_commit_to(ParticipantId::Real(1), &[ParticipantId::Real(0)]);
-            let (_outcome, _, channel) = driver.resolve(Some(77));
+            let (_outcome, _, channel) = driver.resolve(Some(seed.unwrap_or(77)));
             let (deliveries, omissions) = summarize_channel(&channel);
             let payload = ScenarioLog {
                 description: "Example 1 selective delivery",
                 deliveries,
                 omissions,
             };
-            serde_json::to_writer_pretty(io::stdout(), &payload)?;
+            write_json(&payload, output)
         }
         ScenarioSpec::Adaptive => {
-            let CentralizedDeviationResult { report, channel } =
-                scripted_adaptive_reserve_run(Exponential::new(0.01), 1.0, 150.0, 400.0, 120.0);
+            let CentralizedDeviationResult { report, channel } = scripted_adaptive_reserve_run(
+                Exponential::new(0.01),
+                1.0,
+                150.0,
+                400.0,
+                120.0,
+                seed,
+            );
             let (deliveries, omissions) = summarize_channel(&channel);
-            let output = AdaptiveScenarioOutput {
+            let payload = AdaptiveScenarioOutput {
                 report,
                 deliveries,
                 omissions,
             };
-            serde_json::to_writer_pretty(io::stdout(), &output)?;
+            write_json(&payload, output)
         }
         ScenarioSpec::Counterexample => {
             let dist = EqualRevenue::new(1.0);
@@ -384,26 +733,30 @@ fn run_scenario(spec: ScenarioSpec) -> io::Result<()> {
                     bid,
                     reveal_if_top_at_least: bid,
                 },
-                9001,
+                seed.unwrap_or(9001),
             );
             let payload = CounterexampleOutput { stats, bid };
-            serde_json::to_writer_pretty(io::stdout(), &payload)?;
+            write_json(&payload, output)
         }
         ScenarioSpec::Centralized => {
             // Script the centralized adaptive deviation with omitted delivery (Definition 23).
-            let CentralizedDeviationResult { report, channel } =
-                scripted_adaptive_reserve_run(Exponential::new(0.01), 1.0, 150.0, 400.0, 120.0);
+            let CentralizedDeviationResult { report, channel } = scripted_adaptive_reserve_run(
+                Exponential::new(0.01),
+                1.0,
+                150.0,
+                400.0,
+                120.0,
+                seed,
+            );
             let (deliveries, omissions) = summarize_channel(&channel);
             let payload = AdaptiveScenarioOutput {
                 report,
                 deliveries,
                 omissions,
             };
-            serde_json::to_writer_pretty(io::stdout(), &payload)?;
+            write_json(&payload, output)
         }
     }
-    println!();
-    Ok(())
 }
 
 #[cfg(test)]
@@ -422,8 +775,9 @@ mod tests {
             alpha: None,
             rng_seed: Some(7),
             commitment_backend: CommitmentBackendSpec::Sha,
+            bulletproof_range_bits: None,
         };
-        run_with_dist(Uniform::new(0.0, 10.0), req).expect("cli run");
+        run_with_dist(Uniform::new(0.0, 10.0), req, false).expect("cli run");
     }
 
     #[test]
@@ -441,7 +795,340 @@ mod tests {
             alpha: Some(1.0),
             rng_seed: Some(3),
             commitment_backend: CommitmentBackendSpec::Pedersen,
+            bulletproof_range_bits: None,
+        };
+        run_simulation(req, 10, None, None).expect("simulation run");
+    }
+
+    #[test]
+    fn run_simulation_rejects_alpha_beyond_uniform_regularity() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 10.0,
+            },
+            valuations: vec![0.0, 0.0],
+            false_bids: vec![],
+            alpha: Some(5.0),
+            rng_seed: Some(3),
+            commitment_backend: CommitmentBackendSpec::Sha,
+            bulletproof_range_bits: None,
+        };
+        let err = run_simulation(req, 10, None, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(
+            matches!(
+                err.get_ref()
+                    .and_then(|e| e.downcast_ref::<ValidationError>()),
+                Some(ValidationError::AlphaTooLarge { .. })
+            ),
+            "{err}"
+        );
+        assert!(err.to_string().starts_with("alpha 5 exceeds"), "{err}");
+    }
+
+    #[test]
+    fn records_out_writes_header_plus_one_row_per_trial() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Exponential { lambda: 1.0 },
+            valuations: vec![0.0, 0.0],
+            false_bids: vec![FalseBidSpec {
+                bid: 1.5,
+                reveal: true,
+            }],
+            alpha: Some(1.0),
+            rng_seed: Some(11),
+            commitment_backend: CommitmentBackendSpec::Sha,
+            bulletproof_range_bits: None,
+        };
+        let path = std::env::temp_dir().join(format!("dra-records-{}.csv", std::process::id()));
+        run_simulation(req, 25, Some(&path), None).expect("simulation with records");
+        let csv = std::fs::read_to_string(&path).expect("records file");
+        std::fs::remove_file(&path).ok();
+        assert_eq!(csv.lines().count(), 26);
+        assert!(csv.starts_with("trial,"));
+    }
+
+    #[test]
+    fn output_flag_writes_parseable_json_to_file() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 10.0,
+            },
+            valuations: vec![3.0, 8.0],
+            false_bids: vec![],
+            alpha: None,
+            rng_seed: Some(9),
+            commitment_backend: CommitmentBackendSpec::Sha,
+            bulletproof_range_bits: None,
+        };
+        let path = std::env::temp_dir().join(format!("dra-output-{}.json", std::process::id()));
+        let resp = run_with_dist(Uniform::new(0.0, 10.0), req, false).expect("cli run");
+        write_json(&resp, Some(&path)).expect("write output");
+        let json = std::fs::read_to_string(&path).expect("output file");
+        std::fs::remove_file(&path).ok();
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid json");
+        assert_eq!(value["winner"], "Real(1)");
+        assert_eq!(value["valid_bids"].as_array().map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn revealed_false_bid_is_listed_with_its_kind() {
+        let input = r#"{"distribution": {"type": "uniform", "low": 0.0, "high": 10.0},
+                        "valuations": [7.0], "false_bids": [{"bid": 6.0, "reveal": true}],
+                        "rng_seed": 5}"#;
+        let (mut requests, _) = parse_requests(input).expect("valid request");
+        let response = run_auction(requests.remove(0), false).expect("auction run");
+        let value = serde_json::to_value(&response).expect("serialize response");
+        assert_eq!(
+            value["valid_bids"],
+            serde_json::json!([
+                {"kind": "real", "index": 0, "bid": 7.0},
+                {"kind": "false", "index": 0, "bid": 6.0},
+            ])
+        );
+    }
+
+    #[test]
+    fn array_input_runs_every_request() {
+        let input = r#"[
+            {"distribution": {"type": "uniform", "low": 0.0, "high": 10.0},
+             "valuations": [3.0, 8.0], "rng_seed": 1},
+            {"distribution": {"type": "exponential", "lambda": 1.0},
+             "valuations": [0.5, 2.0, 1.5], "rng_seed": 2}
+        ]"#;
+        let (requests, batch) = parse_requests(input).expect("valid batch");
+        assert!(batch);
+        let responses = requests
+            .into_iter()
+            .map(|req| run_auction(req, false))
+            .collect::<io::Result<Vec<_>>>()
+            .expect("batch run");
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].valid_bids.len(), 2);
+        assert_eq!(responses[1].valid_bids.len(), 3);
+
+        let path = std::env::temp_dir().join(format!("dra-batch-{}.jsonl", std::process::id()));
+        write_json_lines(&responses, Some(&path)).expect("write jsonl");
+        let lines = std::fs::read_to_string(&path).expect("jsonl file");
+        std::fs::remove_file(&path).ok();
+        assert_eq!(lines.lines().count(), 2);
+    }
+
+    #[test]
+    fn truncated_normal_request_runs() {
+        let input = r#"{"distribution": {"type": "truncated_normal", "mean": 5.0, "sd": 2.0},
+                        "valuations": [9.0, 6.0], "rng_seed": 4}"#;
+        let (mut requests, _) = parse_requests(input).expect("valid request");
+        let response = run_auction(requests.remove(0), false).expect("auction run");
+        assert_eq!(response.valid_bids.len(), 2);
+    }
+
+    #[test]
+    fn validate_reports_alpha_beyond_uniform_regularity() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 10.0,
+            },
+            valuations: vec![3.0, -1.0],
+            false_bids: vec![],
+            alpha: Some(5.0),
+            rng_seed: None,
+            commitment_backend: CommitmentBackendSpec::Sha,
+            bulletproof_range_bits: None,
+        };
+        let report = validate_request(&req);
+        assert!(!report.valid);
+        assert_eq!(report.problems.len(), 2);
+        assert!(report.problems[0].starts_with("alpha 5 exceeds"));
+    }
+
+    #[test]
+    fn validate_reports_invalid_distribution_parameters() {
+        let input = r#"[{"distribution": {"type": "uniform", "low": 5.0, "high": 1.0},
+                         "valuations": [2.0, -3.0]},
+                        {"distribution": {"type": "truncated_normal", "mean": 1.0, "sd": 0.0},
+                         "valuations": [2.0]},
+                        {"distribution": {"type": "exponential", "lambda": 1.0},
+                         "valuations": [2.0], "alpha": -1.0}]"#;
+        let (requests, _) = parse_requests(input).expect("valid requests");
+        let uniform = validate_request(&requests[0]);
+        assert!(!uniform.valid);
+        assert_eq!(
+            uniform.problems[0],
+            "uniform requires low < high, got low 5 and high 1"
+        );
+        assert_eq!(uniform.problems.len(), 2);
+        let normal = validate_request(&requests[1]);
+        assert_eq!(normal.problems, ["sd must be positive, got 0"]);
+        let alpha = validate_request(&requests[2]);
+        assert_eq!(alpha.problems, ["alpha -1 is not positive"]);
+
+        let (mut requests, _) = parse_requests(input).expect("valid requests");
+        let err = run_auction(requests.remove(0), false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn validate_rejects_any_alpha_for_a_heavy_tailed_pareto() {
+        let input = r#"{"distribution": {"type": "pareto", "scale": 1.0, "shape": 0.5},
+                        "valuations": [2.0, 3.0], "alpha": 1.0}"#;
+        let (requests, _) = parse_requests(input).expect("valid request");
+        let report = validate_request(&requests[0]);
+        assert!(!report.valid);
+        assert!(report.problems[0].starts_with("alpha 1 exceeds"));
+
+        let input = r#"{"distribution": {"type": "pareto", "scale": 1.0, "shape": 0.5},
+                        "valuations": [2.0, 3.0], "rng_seed": 1}"#;
+        let (mut requests, _) = parse_requests(input).expect("valid request");
+        let err = run_auction(requests.remove(0), false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn garbage_json_is_a_readable_error() {
+        let input = "{\"valuations\": [1.0,\n  oops]}";
+        let Err(err) = parse_requests(input) else {
+            panic!("garbage input should not parse");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let message = err.to_string();
+        assert!(
+            message.starts_with("invalid auction request JSON at byte 23:"),
+            "{message}"
+        );
+        assert!(message.contains("line 2"), "{message}");
+    }
+
+    #[test]
+    fn with_transcript_emits_one_commitment_per_bid() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 10.0,
+            },
+            valuations: vec![3.0, 8.0, 6.0],
+            false_bids: vec![FalseBidSpec {
+                bid: 7.0,
+                reveal: true,
+            }],
+            alpha: None,
+            rng_seed: Some(4),
+            commitment_backend: CommitmentBackendSpec::Sha,
+            bulletproof_range_bits: None,
+        };
+        let resp = run_with_dist(Uniform::new(0.0, 10.0), req, true).expect("cli run");
+        let value = serde_json::to_value(&resp).expect("serializable response");
+        let commitments = value["transcript"]["commitments"]
+            .as_array()
+            .expect("commitments");
+        assert_eq!(commitments.len(), 4);
+        assert!(value["transcript"]["timings"].is_object());
+    }
+
+    #[test]
+    fn tampered_transcript_fails_offline_verification() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let (_, transcript) = dra.run_with_false_bids_with_transcript(&[3.0, 8.0], &[], Some(3));
+        let path = std::env::temp_dir().join(format!("dra-transcript-{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(&transcript).unwrap()).unwrap();
+        let sha = || verification_backend(&CommitmentBackendSpec::Sha, None, None).unwrap();
+        let verdict = verify_transcript(&path, sha()).expect("readable transcript");
+        assert!(verdict.is_ok(), "{verdict:?}");
+        let pedersen = verification_backend(&CommitmentBackendSpec::Pedersen, None, None).unwrap();
+        let verdict = verify_transcript(&path, pedersen).expect("readable transcript");
+        assert!(matches!(verdict, Err(AuditError::BadOpening(_))));
+
+        let mut value = serde_json::to_value(&transcript).unwrap();
+        value["reveals"][0]["opening"]["bid"] = serde_json::json!(9.5);
+        std::fs::write(&path, value.to_string()).unwrap();
+        let verdict = verify_transcript(&path, sha()).expect("readable transcript");
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(
+            verdict,
+            Err(AuditError::BadOpening(ParticipantId::Real(0)))
+        ));
+    }
+
+    #[test]
+    fn audited_verification_needs_a_trusted_root() {
+        let err = verification_backend(&CommitmentBackendSpec::Audited, None, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let root = parse_root(&"0a".repeat(32)).expect("valid hex");
+        assert_eq!(root, [0x0a; 32]);
+        assert!(verification_backend(&CommitmentBackendSpec::Audited, None, Some(root)).is_ok());
+        assert!(parse_root("0a").is_err());
+        assert!(parse_root(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn seed_flag_changes_scenario_output() {
+        let dir = std::env::temp_dir();
+        let run = |seed: u64| {
+            let path = dir.join(format!("dra-scenario-{}-{seed}.json", std::process::id()));
+            run_scenario(ScenarioSpec::Counterexample, Some(seed), Some(&path)).expect("scenario");
+            let json = std::fs::read_to_string(&path).expect("scenario output");
+            std::fs::remove_file(&path).ok();
+            json
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+
+    #[test]
+    fn run_with_dist_accepts_16_bit_bulletproof_range() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 0.06,
+            },
+            valuations: vec![0.01, 0.04],
+            false_bids: vec![],
+            alpha: None,
+            rng_seed: Some(5),
+            commitment_backend: CommitmentBackendSpec::Bulletproofs,
+            bulletproof_range_bits: Some(16),
+        };
+        run_with_dist(Uniform::new(0.0, 0.06), req, false).expect("16-bit range run");
+    }
+
+    #[test]
+    fn unsupported_bulletproof_range_is_invalid_input() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 10.0,
+            },
+            valuations: vec![1.0],
+            false_bids: vec![],
+            alpha: None,
+            rng_seed: None,
+            commitment_backend: CommitmentBackendSpec::Bulletproofs,
+            bulletproof_range_bits: Some(12),
+        };
+        let Err(err) = build_backend(&req) else {
+            panic!("12-bit range should be rejected");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn bid_beyond_bulletproof_range_is_invalid_input() {
+        let req = AuctionRequest {
+            distribution: DistributionSpec::Uniform {
+                low: 0.0,
+                high: 10.0,
+            },
+            valuations: vec![0.01, 5.0],
+            false_bids: vec![],
+            alpha: None,
+            rng_seed: Some(5),
+            commitment_backend: CommitmentBackendSpec::Bulletproofs,
+            bulletproof_range_bits: Some(16),
         };
-        run_simulation(req, 10).expect("simulation run");
+        let err = run_with_dist(Uniform::new(0.0, 10.0), req, false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
     }
 }
@@ -1,25 +1,31 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::auction::ParticipantId;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+use crate::auction::{BroadcastEvent, BroadcastMessage, ParticipantId};
 use crate::protocol::Phase;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DeliveredMessage {
     pub sender: ParticipantId,
     pub recipient: ParticipantId,
     pub phase: Phase,
     pub payload: MessagePayload,
+    /// Time at which the recipient can first act on the message.
+    pub arrival: u64,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum MessagePayload {
     Commitment { from: ParticipantId },
     Reveal { from: ParticipantId, success: bool },
+    Withhold { from: ParticipantId },
     EndPhase { phase: Phase },
     Timeout { target: ParticipantId },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BroadcastLog {
     deliveries: Vec<DeliveredMessage>,
 }
@@ -47,6 +53,52 @@ impl BroadcastLog {
     }
 }
 
+/// A payload that reached some of the watched participants but not others, as found by
+/// [`detect_selective_delivery`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SelectiveDelivery {
+    pub sender: ParticipantId,
+    pub payload: MessagePayload,
+    pub received: Vec<ParticipantId>,
+    pub missed: Vec<ParticipantId>,
+}
+
+/// Flag every payload in `log` that was delivered to some of `participants` but withheld from
+/// others, in order of first delivery. An honest broadcaster sends each payload to everyone or
+/// no one, so a non-empty result is evidence of selective delivery. Participants that only
+/// subscribed after a payload was sent are reported as having missed it, so pass the set that
+/// was listening throughout.
+pub fn detect_selective_delivery(
+    log: &BroadcastLog,
+    participants: &[ParticipantId],
+) -> Vec<SelectiveDelivery> {
+    let mut payloads: Vec<(&ParticipantId, &MessagePayload)> = Vec::new();
+    for msg in log.all() {
+        if !payloads.contains(&(&msg.sender, &msg.payload)) {
+            payloads.push((&msg.sender, &msg.payload));
+        }
+    }
+    payloads
+        .into_iter()
+        .filter_map(|(sender, payload)| {
+            let (received, missed): (Vec<_>, Vec<_>) =
+                participants.iter().cloned().partition(|participant| {
+                    log.all().iter().any(|msg| {
+                        &msg.sender == sender
+                            && &msg.payload == payload
+                            && &msg.recipient == participant
+                    })
+                });
+            (!received.is_empty() && !missed.is_empty()).then(|| SelectiveDelivery {
+                sender: sender.clone(),
+                payload: payload.clone(),
+                received,
+                missed,
+            })
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct OmittedDelivery {
     pub sender: ParticipantId,
@@ -55,25 +107,81 @@ pub struct OmittedDelivery {
     pub payload: MessagePayload,
 }
 
+/// Payloads one recipient received that another did not, as produced by
+/// [`CentralizedChannel::view_diff`]. A non-empty diff means the two views are inconsistent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewDiff {
+    pub only_a: Vec<MessagePayload>,
+    pub only_b: Vec<MessagePayload>,
+}
+
+impl ViewDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_a.is_empty() && self.only_b.is_empty()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CentralizedChannel {
     subscribers: Vec<ParticipantId>,
     deliveries: Vec<DeliveredMessage>,
     omissions: Vec<OmittedDelivery>,
+    clock: u64,
+    reliability: HashMap<(ParticipantId, ParticipantId), f64>,
+    link_rng: StdRng,
 }
 
 impl CentralizedChannel {
     pub fn new(mut participants: Vec<ParticipantId>) -> Self {
-        if !participants.iter().any(|p| *p == ParticipantId::Auctioneer) {
+        if !participants.contains(&ParticipantId::Auctioneer) {
             participants.push(ParticipantId::Auctioneer);
         }
         Self {
             subscribers: participants,
             deliveries: Vec::new(),
             omissions: Vec::new(),
+            clock: 0,
+            reliability: HashMap::new(),
+            link_rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    /// Channel whose `(sender, recipient)` links deliver with the given probability; links
+    /// not listed are perfectly reliable. `seed` drives the draws for partially reliable links.
+    pub fn with_reliability(
+        participants: Vec<ParticipantId>,
+        reliability: HashMap<(ParticipantId, ParticipantId), f64>,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            reliability.values().all(|p| (0.0..=1.0).contains(p)),
+            "link reliability must be in [0, 1]"
+        );
+        Self {
+            reliability,
+            link_rng: StdRng::seed_from_u64(seed),
+            ..Self::new(participants)
         }
     }
 
+    fn link_delivers(&mut self, sender: &ParticipantId, recipient: &ParticipantId) -> bool {
+        match self.reliability.get(&(sender.clone(), recipient.clone())) {
+            None => true,
+            Some(p) if *p >= 1.0 => true,
+            Some(p) if *p <= 0.0 => false,
+            Some(p) => self.link_rng.gen_bool(*p),
+        }
+    }
+
+    pub fn now(&self) -> u64 {
+        self.clock
+    }
+
+    /// Move the send clock forward; messages sent afterwards arrive at `now + delay`.
+    pub fn advance_to(&mut self, now: u64) {
+        self.clock = self.clock.max(now);
+    }
+
     pub fn register(&mut self, participant: ParticipantId) {
         if !self.subscribers.contains(&participant) {
             self.subscribers.push(participant);
@@ -86,12 +194,14 @@ impl CentralizedChannel {
         recipient: ParticipantId,
         phase: Phase,
         payload: MessagePayload,
+        delay: u64,
     ) {
         self.deliveries.push(DeliveredMessage {
             sender,
             recipient,
             phase,
             payload,
+            arrival: self.clock + delay,
         });
     }
 
@@ -101,30 +211,114 @@ impl CentralizedChannel {
         phase: Phase,
         payload: MessagePayload,
         allowed: &[ParticipantId],
+        delay: u64,
     ) {
         let allow_set: HashSet<_> = allowed.iter().cloned().collect();
         for recipient in self.subscribers.clone() {
             if recipient == sender {
                 continue;
             }
-            if allow_set.contains(&recipient) {
-                self.deliveries.push(DeliveredMessage {
-                    sender: sender.clone(),
-                    recipient: recipient.clone(),
-                    phase,
-                    payload: payload.clone(),
-                });
+            let delivered =
+                allow_set.contains(&recipient) && self.link_delivers(&sender, &recipient);
+            self.route(&sender, recipient, phase, &payload, delivered, delay);
+        }
+    }
+
+    /// Like `broadcast_subset`, but a Byzantine relay rewrites each delivered copy with
+    /// `mutate` (e.g. impersonating the sender or flipping a reveal flag). Omissions keep the
+    /// original payload.
+    pub fn broadcast_subset_mutated<F: Fn(MessagePayload) -> MessagePayload>(
+        &mut self,
+        sender: ParticipantId,
+        phase: Phase,
+        payload: MessagePayload,
+        mutate: F,
+        allowed: &[ParticipantId],
+        delay: u64,
+    ) {
+        let allow_set: HashSet<_> = allowed.iter().cloned().collect();
+        for recipient in self.subscribers.clone() {
+            if recipient == sender {
+                continue;
+            }
+            if allow_set.contains(&recipient) && self.link_delivers(&sender, &recipient) {
+                let mutated = mutate(payload.clone());
+                self.route(&sender, recipient, phase, &mutated, true, delay);
             } else {
-                self.omissions.push(OmittedDelivery {
-                    sender: sender.clone(),
-                    omitted: recipient.clone(),
-                    phase,
-                    payload: payload.clone(),
-                });
+                self.route(&sender, recipient, phase, &payload, false, delay);
             }
         }
     }
 
+    /// Broadcast to every subscriber, independently dropping each copy with `drop_prob` on top
+    /// of the link's own reliability. Dropped copies are recorded as omissions, exactly like
+    /// `broadcast_subset`.
+    pub fn broadcast_lossy<R: Rng>(
+        &mut self,
+        sender: ParticipantId,
+        phase: Phase,
+        payload: MessagePayload,
+        drop_prob: f64,
+        delay: u64,
+        rng: &mut R,
+    ) {
+        assert!(
+            (0.0..=1.0).contains(&drop_prob),
+            "drop probability must be in [0, 1]"
+        );
+        for recipient in self.subscribers.clone() {
+            if recipient == sender {
+                continue;
+            }
+            let delivered = !rng.gen_bool(drop_prob) && self.link_delivers(&sender, &recipient);
+            self.route(&sender, recipient, phase, &payload, delivered, delay);
+        }
+    }
+
+    /// The reveal notices `recipient` was delivered, as the broadcast events its own copy of
+    /// the transcript would record, so [`crate::audit_transcript`] can check that view.
+    pub fn reveal_notices_for(&self, recipient: &ParticipantId) -> Vec<BroadcastEvent> {
+        self.deliveries
+            .iter()
+            .filter(|msg| &msg.recipient == recipient)
+            .filter_map(|msg| match &msg.payload {
+                MessagePayload::Reveal { from, success } => Some(BroadcastEvent {
+                    timestamp: msg.arrival,
+                    sender: from.clone(),
+                    message: BroadcastMessage::RevealPublished { success: *success },
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn route(
+        &mut self,
+        sender: &ParticipantId,
+        recipient: ParticipantId,
+        phase: Phase,
+        payload: &MessagePayload,
+        delivered: bool,
+        delay: u64,
+    ) {
+        if delivered {
+            self.deliveries.push(DeliveredMessage {
+                sender: sender.clone(),
+                recipient,
+                phase,
+                payload: payload.clone(),
+                arrival: self.clock + delay,
+            });
+        } else {
+            self.omissions.push(OmittedDelivery {
+                sender: sender.clone(),
+                omitted: recipient,
+                phase,
+                payload: payload.clone(),
+            });
+        }
+    }
+
     pub fn deliveries(&self) -> &[DeliveredMessage] {
         &self.deliveries
     }
@@ -140,6 +334,63 @@ impl CentralizedChannel {
             .collect()
     }
 
+    /// Messages that have reached `recipient` by `time`.
+    pub fn delivered_by(&self, recipient: &ParticipantId, time: u64) -> Vec<&DeliveredMessage> {
+        self.deliveries
+            .iter()
+            .filter(|msg| &msg.recipient == recipient && msg.arrival <= time)
+            .collect()
+    }
+
+    /// Compare what `a` and `b` know: every payload delivered to them plus every payload they
+    /// sent themselves, since a sender is never delivered its own broadcast.
+    pub fn view_diff(&self, a: &ParticipantId, b: &ParticipantId) -> ViewDiff {
+        let view_a = self.known_payloads(a);
+        let view_b = self.known_payloads(b);
+        let missing = |from: &[&MessagePayload], other: &[&MessagePayload]| {
+            from.iter()
+                .filter(|payload| !other.contains(payload))
+                .map(|payload| (*payload).clone())
+                .collect()
+        };
+        ViewDiff {
+            only_a: missing(&view_a, &view_b),
+            only_b: missing(&view_b, &view_a),
+        }
+    }
+
+    /// Distinct payloads `participant` sent or was delivered, in first-seen order.
+    fn known_payloads(&self, participant: &ParticipantId) -> Vec<&MessagePayload> {
+        let sent = self
+            .omissions
+            .iter()
+            .filter(|o| &o.sender == participant)
+            .map(|o| &o.payload);
+        let mut payloads: Vec<&MessagePayload> = Vec::new();
+        for payload in self
+            .deliveries
+            .iter()
+            .filter(|msg| &msg.recipient == participant || &msg.sender == participant)
+            .map(|msg| &msg.payload)
+            .chain(sent)
+        {
+            if !payloads.contains(&payload) {
+                payloads.push(payload);
+            }
+        }
+        payloads
+    }
+
+    /// Deliveries and omissions recorded during `phase`, each in send order.
+    pub fn messages_in_phase(
+        &self,
+        phase: Phase,
+    ) -> (Vec<&DeliveredMessage>, Vec<&OmittedDelivery>) {
+        let delivered = self.deliveries.iter().filter(|m| m.phase == phase).collect();
+        let omitted = self.omissions.iter().filter(|o| o.phase == phase).collect();
+        (delivered, omitted)
+    }
+
     pub fn omitted_for(&self, recipient: &ParticipantId) -> Vec<&OmittedDelivery> {
         self.omissions
             .iter()
@@ -147,3 +398,251 @@ impl CentralizedChannel {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auction::{PhaseTimings, PublicBroadcastDRA};
+    use crate::commitment::NonMalleableShaCommitment;
+    use crate::distribution::Uniform;
+    use crate::protocol::ProtocolSession;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn crowd(size: usize) -> CentralizedChannel {
+        CentralizedChannel::new((0..size).map(ParticipantId::Real).collect())
+    }
+
+    fn lossy_commitment(channel: &mut CentralizedChannel, drop_prob: f64, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        channel.broadcast_lossy(
+            ParticipantId::Auctioneer,
+            Phase::Commit,
+            MessagePayload::Commitment {
+                from: ParticipantId::Real(0),
+            },
+            drop_prob,
+            0,
+            &mut rng,
+        );
+    }
+
+    #[test]
+    fn link_reliability_applies_per_direction_on_top_of_the_allow_list() {
+        let colluder = ParticipantId::False(0);
+        let victim = ParticipantId::Real(1);
+        let reliability = HashMap::from([
+            ((ParticipantId::Auctioneer, colluder.clone()), 1.0),
+            ((ParticipantId::Auctioneer, victim.clone()), 0.0),
+        ]);
+        let participants = vec![ParticipantId::Real(0), victim.clone(), colluder.clone()];
+        let mut channel = CentralizedChannel::with_reliability(participants, reliability, 303);
+        let end_commit = MessagePayload::EndPhase {
+            phase: Phase::Commit,
+        };
+        for _ in 0..10 {
+            channel.broadcast_subset(
+                ParticipantId::Auctioneer,
+                Phase::Commit,
+                end_commit.clone(),
+                &[ParticipantId::Real(0), victim.clone(), colluder.clone()],
+                0,
+            );
+        }
+        assert_eq!(channel.per_recipient_view(&colluder).len(), 10);
+        assert_eq!(channel.per_recipient_view(&ParticipantId::Real(0)).len(), 10);
+        assert!(channel.per_recipient_view(&victim).is_empty());
+        assert_eq!(channel.omitted_for(&victim).len(), 10);
+
+        // A perfect link still needs the allow-list to include the recipient.
+        channel.broadcast_subset(
+            ParticipantId::Auctioneer,
+            Phase::Commit,
+            end_commit.clone(),
+            &[ParticipantId::Real(0)],
+            0,
+        );
+        assert_eq!(channel.per_recipient_view(&colluder).len(), 10);
+        // The dead auctioneer -> victim link says nothing about victim -> auctioneer.
+        channel.broadcast_subset(
+            victim.clone(),
+            Phase::Commit,
+            end_commit,
+            &[ParticipantId::Auctioneer],
+            0,
+        );
+        assert_eq!(
+            channel.per_recipient_view(&ParticipantId::Auctioneer).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn view_diff_counts_what_a_sender_broadcast_itself() {
+        let buyer_a = ParticipantId::Real(0);
+        let buyer_b = ParticipantId::Real(1);
+        let mut channel = CentralizedChannel::new(vec![buyer_a.clone(), buyer_b.clone()]);
+        let commitment = MessagePayload::Commitment {
+            from: buyer_a.clone(),
+        };
+        channel.broadcast_subset(
+            buyer_a.clone(),
+            Phase::Commit,
+            commitment.clone(),
+            &[ParticipantId::Auctioneer],
+            0,
+        );
+        // Neither buyer is delivered the commitment, but A made it and B never got it.
+        let diff = channel.view_diff(&buyer_a, &buyer_b);
+        assert_eq!(diff.only_a, vec![commitment]);
+        assert!(diff.only_b.is_empty());
+    }
+
+    #[test]
+    fn mutated_and_lossy_broadcasts_honour_links_and_delay() {
+        let victim = ParticipantId::Real(1);
+        let reliability = HashMap::from([((ParticipantId::Auctioneer, victim.clone()), 0.0)]);
+        let participants = vec![ParticipantId::Real(0), victim.clone()];
+        let mut channel = CentralizedChannel::with_reliability(participants, reliability, 302);
+        let payload = MessagePayload::EndPhase {
+            phase: Phase::Commit,
+        };
+        channel.broadcast_subset_mutated(
+            ParticipantId::Auctioneer,
+            Phase::Commit,
+            payload.clone(),
+            |payload| payload,
+            &[ParticipantId::Real(0), victim.clone()],
+            3,
+        );
+        let mut rng = StdRng::seed_from_u64(302);
+        channel.broadcast_lossy(
+            ParticipantId::Auctioneer,
+            Phase::Commit,
+            payload,
+            0.0,
+            4,
+            &mut rng,
+        );
+        let arrivals: Vec<u64> = channel
+            .per_recipient_view(&ParticipantId::Real(0))
+            .iter()
+            .map(|msg| msg.arrival)
+            .collect();
+        assert_eq!(arrivals, vec![3, 4]);
+        assert!(channel.per_recipient_view(&victim).is_empty());
+        assert_eq!(channel.omitted_for(&victim).len(), 2);
+    }
+
+    #[test]
+    fn lossy_broadcast_extremes_are_deterministic() {
+        let mut all_dropped = crowd(8);
+        lossy_commitment(&mut all_dropped, 1.0, 1);
+        assert!(all_dropped.deliveries().is_empty());
+        assert_eq!(all_dropped.omissions().len(), 8);
+
+        let mut none_dropped = crowd(8);
+        lossy_commitment(&mut none_dropped, 0.0, 1);
+        assert_eq!(none_dropped.deliveries().len(), 8);
+        assert!(none_dropped.omissions().is_empty());
+    }
+
+    #[test]
+    fn lossy_broadcast_drops_about_half_at_one_half() {
+        let mut channel = crowd(2000);
+        lossy_commitment(&mut channel, 0.5, 299);
+        let dropped = channel.omissions().len() as f64 / 2000.0;
+        assert!((0.45..=0.55).contains(&dropped), "dropped fraction {dropped}");
+        assert_eq!(channel.deliveries().len() + channel.omissions().len(), 2000);
+    }
+
+    #[test]
+    fn delayed_message_arrives_after_its_delay() {
+        let buyer = ParticipantId::Real(1);
+        let mut channel = CentralizedChannel::new(vec![ParticipantId::Real(0), buyer.clone()]);
+        channel.broadcast_subset(
+            ParticipantId::Auctioneer,
+            Phase::Commit,
+            MessagePayload::Commitment {
+                from: ParticipantId::Real(0),
+            },
+            std::slice::from_ref(&buyer),
+            5,
+        );
+        channel.advance_to(2);
+        channel.private_message(
+            ParticipantId::Auctioneer,
+            buyer.clone(),
+            Phase::Commit,
+            MessagePayload::EndPhase {
+                phase: Phase::Commit,
+            },
+            0,
+        );
+        assert_eq!(channel.delivered_by(&buyer, 1).len(), 0);
+        let at_three = channel.delivered_by(&buyer, 3);
+        assert_eq!(at_three.len(), 1);
+        assert!(matches!(at_three[0].payload, MessagePayload::EndPhase { .. }));
+        assert_eq!(channel.delivered_by(&buyer, 6).len(), 2);
+    }
+
+    fn two_buyer_session(
+        partition: Option<HashSet<ParticipantId>>,
+    ) -> ProtocolSession<Uniform, NonMalleableShaCommitment> {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(2);
+        let participants = vec![ParticipantId::Real(0), ParticipantId::Real(1)];
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 5, schedule, participants);
+        if let Some(members) = partition {
+            session.set_partition(members, 0..4);
+        }
+        session.commit_real(0, 8.0, collateral).expect("commit 0");
+        session.commit_real(1, 5.0, collateral).expect("commit 1");
+        session.advance_to(4).expect("enter reveal");
+        session.reveal(ParticipantId::Real(0)).expect("reveal 0");
+        session.reveal(ParticipantId::Real(1)).expect("reveal 1");
+        session
+    }
+
+    #[test]
+    fn honest_session_has_no_selective_delivery() {
+        let session = two_buyer_session(None);
+        let everyone = session.subscribers().to_vec();
+        assert!(detect_selective_delivery(session.network_log(), &everyone).is_empty());
+    }
+
+    #[test]
+    fn partition_is_flagged_as_selective_delivery() {
+        let session = two_buyer_session(Some(HashSet::from([
+            ParticipantId::Auctioneer,
+            ParticipantId::Real(0),
+        ])));
+        let everyone = session.subscribers().to_vec();
+        let flagged = detect_selective_delivery(session.network_log(), &everyone);
+        let commitment_from_0 = flagged
+            .iter()
+            .find(|gap| {
+                gap.payload
+                    == MessagePayload::Commitment {
+                        from: ParticipantId::Real(0),
+                    }
+            })
+            .expect("buyer 0's commitment is flagged");
+        assert_eq!(commitment_from_0.missed, vec![ParticipantId::Real(1)]);
+        assert!(
+            commitment_from_0
+                .received
+                .contains(&ParticipantId::Auctioneer)
+        );
+        // Reveals go out after the window closes and reach everyone.
+        assert!(
+            flagged
+                .iter()
+                .all(|gap| !matches!(gap.payload, MessagePayload::Reveal { .. }))
+        );
+    }
+}
@@ -1,15 +1,21 @@
-use rand::{SeedableRng, rngs::StdRng};
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
 
 use crate::auction::{
     AuctionOutcome, BroadcastEvent, BroadcastMessage, CommitmentEvent, FalseBid, ParticipantId,
     PhaseTimings, PhaseTransitionReason, PublicBroadcastDRA, RevealEvent, Transcript,
-    audit_transcript,
+    audit_transcript, settle,
 };
+use crate::clock::{Clock, LogicalClock};
 use crate::commitment::{Commitment, CommitmentScheme, Opening};
 use crate::distribution::ValueDistribution;
 use crate::network::{BroadcastLog, DeliveredMessage, MessagePayload};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Phase {
     Commit,
     Reveal,
@@ -22,15 +28,137 @@ pub enum ProtocolError {
     DuplicateCommit(ParticipantId),
     DuplicateReveal(ParticipantId),
     MissingCommit(ParticipantId),
-    ClockRewind { requested: u64, current: u64 },
+    ClockRewind {
+        requested: u64,
+        current: u64,
+    },
     DeadlineExceeded(Phase),
+    /// A single `advance_to` would have crossed both deadlines, leaving no time in this phase.
+    SkippedPhase(Phase),
     AuditFailure,
 }
 
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::WrongPhase => f.write_str("action not allowed in the current phase"),
+            ProtocolError::DuplicateCommit(pid) => write!(f, "{pid} already committed"),
+            ProtocolError::DuplicateReveal(pid) => write!(f, "{pid} already revealed"),
+            ProtocolError::MissingCommit(pid) => write!(f, "{pid} has no commitment"),
+            ProtocolError::ClockRewind { requested, current } => {
+                write!(f, "clock cannot move back from {current} to {requested}")
+            }
+            ProtocolError::DeadlineExceeded(phase) => write!(f, "{phase:?} deadline has passed"),
+            ProtocolError::SkippedPhase(phase) => {
+                write!(f, "advancing the clock would skip the {phase:?} phase")
+            }
+            ProtocolError::AuditFailure => f.write_str("final transcript audit failed"),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+/// Something a participant may do in the session's current phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Commit,
+    Reveal,
+    Withhold,
+}
+
+/// Seeded session RNG that counts the 32-bit words it has produced, so the stream position
+/// survives a checkpoint: restoring reseeds and discards that many words.
+#[derive(Clone, Debug)]
+struct SessionRng {
+    seed: u64,
+    words: u64,
+    inner: StdRng,
+}
+
+impl SessionRng {
+    fn new(seed: u64) -> Self {
+        Self::restore(seed, 0)
+    }
+
+    fn restore(seed: u64, words: u64) -> Self {
+        let mut inner = StdRng::seed_from_u64(seed);
+        for _ in 0..words {
+            inner.next_u32();
+        }
+        Self { seed, words, inner }
+    }
+}
+
+impl RngCore for SessionRng {
+    fn next_u32(&mut self) -> u32 {
+        self.words += 1;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.words += 2;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.words += dest.len().div_ceil(4) as u64;
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Everything needed to resume a [`ProtocolSession`] after a restart. The distribution and
+/// commitment scheme are not serialized; the caller re-supplies them to
+/// [`ProtocolSession::resume`], and they must match the ones the session was created with.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionCheckpoint {
+    pub phase: Phase,
+    pub schedule: PhaseTimings,
+    pub current_time: u64,
+    pub commitments: Vec<(ParticipantId, Commitment, Opening, f64, bool)>,
+    pub transcript: Transcript,
+    pub broadcasts: Vec<BroadcastEvent>,
+    pub network_log: BroadcastLog,
+    pub subscribers: Vec<ParticipantId>,
+    #[serde(default)]
+    pub partition: Option<NetworkPartition>,
+    pub rng_seed: u64,
+    pub rng_words: u64,
+}
+
+/// While the session clock is inside `window`, broadcasts only reach the sender's side of the
+/// split: members talk to members, everyone else to everyone else.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkPartition {
+    pub members: HashSet<ParticipantId>,
+    pub window: Range<u64>,
+}
+
+impl NetworkPartition {
+    fn blocks(&self, now: u64, sender: &ParticipantId, recipient: &ParticipantId) -> bool {
+        self.window.contains(&now)
+            && self.members.contains(sender) != self.members.contains(recipient)
+    }
+}
+
+/// Callback handed each broadcast as a [`ProtocolSession`] logs it.
+pub type EventObserver = Box<dyn FnMut(&BroadcastEvent)>;
+
 /// A simple state machine to model the commit/reveal/resolution phases in the paper’s public-broadcast DRA.
-pub struct ProtocolSession<D: ValueDistribution, S: CommitmentScheme> {
-    dra: PublicBroadcastDRA<D>,
-    rng: StdRng,
+///
+/// The session clock is the DRA's: every action first catches the session up to its reading.
+/// A [`LogicalClock`] never moves on its own, so time advances only through `advance_to`.
+/// With a [`SystemClock`](crate::clock::SystemClock) the schedule must be in epoch
+/// milliseconds; a tick-sized schedule has already expired by the first action.
+pub struct ProtocolSession<D: ValueDistribution, S: CommitmentScheme, C: Clock = LogicalClock> {
+    dra: PublicBroadcastDRA<D, C>,
+    clock: C,
+    rng: SessionRng,
     scheme: S,
     phase: Phase,
     schedule: PhaseTimings,
@@ -40,11 +168,13 @@ pub struct ProtocolSession<D: ValueDistribution, S: CommitmentScheme> {
     broadcasts: Vec<BroadcastEvent>,
     network_log: BroadcastLog,
     subscribers: Vec<ParticipantId>,
+    partition: Option<NetworkPartition>,
+    on_event: Option<EventObserver>,
 }
 
-impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
+impl<D: ValueDistribution, S: CommitmentScheme + Clone, C: Clock> ProtocolSession<D, S, C> {
     pub fn new(
-        dra: PublicBroadcastDRA<D>,
+        dra: PublicBroadcastDRA<D, C>,
         scheme: S,
         seed: u64,
         schedule: PhaseTimings,
@@ -57,8 +187,9 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
             }
         }
         Self {
+            clock: dra.clock().clone(),
             dra,
-            rng: StdRng::seed_from_u64(seed),
+            rng: SessionRng::new(seed),
             scheme,
             phase: Phase::Commit,
             schedule: schedule.clone(),
@@ -70,10 +201,63 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
                 broadcasts: Vec::new(),
                 timings: schedule,
                 outcome: None,
+                shared_proofs: Vec::new(),
             },
             broadcasts: Vec::new(),
             network_log: BroadcastLog::new(),
             subscribers,
+            partition: None,
+            on_event: None,
+        }
+    }
+
+    /// Split the network for the times in `window`, as a centralized relay could.
+    pub fn set_partition(&mut self, members: HashSet<ParticipantId>, window: Range<u64>) {
+        self.partition = Some(NetworkPartition { members, window });
+    }
+
+    /// Call `observer` with every broadcast as it is logged, e.g. to stream phase transitions
+    /// and reveals to a UI. It only reads the events, so the transcript and audit are
+    /// unaffected, and it is not part of a checkpoint.
+    pub fn set_observer(&mut self, observer: impl FnMut(&BroadcastEvent) + 'static) {
+        self.on_event = Some(Box::new(observer));
+    }
+
+    /// Snapshot the recoverable state, e.g. after the commit phase.
+    pub fn checkpoint(&self) -> SessionCheckpoint {
+        SessionCheckpoint {
+            phase: self.phase,
+            schedule: self.schedule.clone(),
+            current_time: self.current_time,
+            commitments: self.commitments.clone(),
+            transcript: self.transcript.clone(),
+            broadcasts: self.broadcasts.clone(),
+            network_log: self.network_log.clone(),
+            subscribers: self.subscribers.clone(),
+            partition: self.partition.clone(),
+            rng_seed: self.rng.seed,
+            rng_words: self.rng.words,
+        }
+    }
+
+    /// Rebuild a session from a checkpoint. `scheme` must be the backend the session committed
+    /// with, since only it can verify the stored openings.
+    pub fn resume(dra: PublicBroadcastDRA<D, C>, scheme: S, checkpoint: SessionCheckpoint) -> Self {
+        Self {
+            clock: dra.clock().clone(),
+            dra,
+            rng: SessionRng::restore(checkpoint.rng_seed, checkpoint.rng_words),
+            scheme,
+            phase: checkpoint.phase,
+            schedule: checkpoint.schedule,
+            current_time: checkpoint.current_time,
+            commitments: checkpoint.commitments,
+            transcript: checkpoint.transcript,
+            broadcasts: checkpoint.broadcasts,
+            network_log: checkpoint.network_log,
+            subscribers: checkpoint.subscribers,
+            partition: checkpoint.partition,
+            on_event: None,
         }
     }
 
@@ -85,6 +269,32 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         &self.network_log
     }
 
+    /// Participants that currently receive every broadcast, auctioneer first.
+    pub fn subscribers(&self) -> &[ParticipantId] {
+        &self.subscribers
+    }
+
+    /// What `id` may legally do right now, given the phase, the deadlines and what it has
+    /// already done. Empty once its part in the current phase is over.
+    pub fn allowed_actions(&self, id: &ParticipantId) -> Vec<Action> {
+        if id.is_auctioneer() {
+            return Vec::new();
+        }
+        let committed = self.commitments.iter().any(|(p, _, _, _, _)| p == id);
+        let opened = self.transcript.reveals.iter().any(|r| r.participant == *id);
+        match self.phase {
+            Phase::Commit if !committed && self.current_time < self.schedule.commit_deadline => {
+                vec![Action::Commit]
+            }
+            Phase::Reveal
+                if committed && !opened && self.current_time < self.schedule.reveal_deadline =>
+            {
+                vec![Action::Reveal, Action::Withhold]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     pub fn advance_to(&mut self, now: u64) -> Result<(), ProtocolError> {
         if now < self.current_time {
             return Err(ProtocolError::ClockRewind {
@@ -92,6 +302,9 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
                 current: self.current_time,
             });
         }
+        if self.phase == Phase::Commit && now >= self.schedule.reveal_deadline {
+            return Err(ProtocolError::SkippedPhase(Phase::Reveal));
+        }
         self.current_time = now;
         if self.phase == Phase::Commit && now >= self.schedule.commit_deadline {
             self.transition_to_phase(Phase::Reveal, PhaseTransitionReason::Deadline)?;
@@ -102,6 +315,20 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         Ok(())
     }
 
+    /// Catch the session up to the clock, firing any deadlines that have passed. Unlike an
+    /// explicit `advance_to`, a clock that has run past both deadlines closes each phase in
+    /// turn, since refusing would leave a wall-clock session stuck in the commit phase.
+    fn sync_clock(&mut self) -> Result<(), ProtocolError> {
+        let now = self.clock.now();
+        if now > self.current_time {
+            if self.phase == Phase::Commit && now >= self.schedule.reveal_deadline {
+                self.advance_to(self.schedule.commit_deadline)?;
+            }
+            self.advance_to(now)?;
+        }
+        Ok(())
+    }
+
     pub fn commit_real(
         &mut self,
         buyer_idx: usize,
@@ -128,6 +355,7 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         collateral: f64,
         will_reveal: bool,
     ) -> Result<(), ProtocolError> {
+        self.sync_clock()?;
         if self.phase != Phase::Commit {
             return Err(ProtocolError::WrongPhase);
         }
@@ -160,11 +388,15 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         message: BroadcastMessage,
         payload: Option<MessagePayload>,
     ) {
-        self.broadcasts.push(BroadcastEvent {
+        let event = BroadcastEvent {
             timestamp: self.current_time,
             sender: sender.clone(),
             message,
-        });
+        };
+        if let Some(on_event) = self.on_event.as_mut() {
+            on_event(&event);
+        }
+        self.broadcasts.push(event);
         if let Some(payload) = payload {
             self.deliver_payload(sender, payload);
         }
@@ -178,11 +410,19 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
 
     fn deliver_payload(&mut self, sender: ParticipantId, payload: MessagePayload) {
         for recipient in self.subscribers.clone() {
+            if self
+                .partition
+                .as_ref()
+                .is_some_and(|p| p.blocks(self.current_time, &sender, &recipient))
+            {
+                continue;
+            }
             self.network_log.record(DeliveredMessage {
                 sender: sender.clone(),
                 recipient,
                 phase: self.phase,
                 payload: payload.clone(),
+                arrival: self.current_time,
             });
         }
     }
@@ -214,10 +454,17 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         if self.phase != Phase::Commit {
             return Err(ProtocolError::WrongPhase);
         }
-        self.transition_to_phase(Phase::Reveal, PhaseTransitionReason::Manual)
+        // The audit rejects a reveal phase opening before the commit deadline, so closing the
+        // commit phase early runs the clock out rather than transitioning immediately.
+        self.sync_clock()?;
+        if self.phase == Phase::Commit {
+            self.advance_to(self.schedule.commit_deadline)?;
+        }
+        Ok(())
     }
 
     pub fn reveal(&mut self, id: ParticipantId) -> Result<(), ProtocolError> {
+        self.sync_clock()?;
         if self.phase != Phase::Reveal {
             return Err(ProtocolError::WrongPhase);
         }
@@ -232,6 +479,8 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         if self.transcript.reveals.iter().any(|r| r.participant == id) {
             return Err(ProtocolError::DuplicateReveal(id));
         }
+        // The commit phase is closed, so proof material can be refreshed against its end state.
+        self.scheme.refresh_opening(&mut self.commitments[idx].2);
         let (_pid, commitment, opening, _collateral, _will_reveal) = &self.commitments[idx];
         let reveals_ok = self.scheme.verify(commitment, opening);
         self.transcript.reveals.push(RevealEvent {
@@ -258,13 +507,74 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
         Ok(())
     }
 
+    /// Reveal every committed real buyer that intends to and has not yet done so, all at the
+    /// current time. Returns how many were revealed.
+    pub fn auto_reveal_all_honest(&mut self) -> Result<usize, ProtocolError> {
+        self.sync_clock()?;
+        if self.phase != Phase::Reveal {
+            return Err(ProtocolError::WrongPhase);
+        }
+        if self.current_time >= self.schedule.reveal_deadline {
+            return Err(ProtocolError::DeadlineExceeded(Phase::Reveal));
+        }
+        let pending: Vec<ParticipantId> = self
+            .commitments
+            .iter()
+            .filter(|(p, _, _, _, will_reveal)| {
+                *will_reveal
+                    && p.is_real()
+                    && !self.transcript.reveals.iter().any(|r| r.participant == *p)
+            })
+            .map(|(p, _, _, _, _)| p.clone())
+            .collect();
+        for id in pending.iter() {
+            self.reveal(id.clone())?;
+        }
+        Ok(pending.len())
+    }
+
+    /// Announce that `id` deliberately will not open its commitment. Unlike a missed
+    /// deadline, this is recorded as the participant's own broadcast; the collateral is
+    /// forfeited either way.
+    pub fn withhold(&mut self, id: ParticipantId) -> Result<(), ProtocolError> {
+        self.sync_clock()?;
+        if self.phase != Phase::Reveal {
+            return Err(ProtocolError::WrongPhase);
+        }
+        if self.current_time >= self.schedule.reveal_deadline {
+            return Err(ProtocolError::DeadlineExceeded(Phase::Reveal));
+        }
+        if !self.commitments.iter().any(|(p, _, _, _, _)| p == &id) {
+            return Err(ProtocolError::MissingCommit(id));
+        }
+        if self.transcript.reveals.iter().any(|r| r.participant == id) {
+            return Err(ProtocolError::DuplicateReveal(id));
+        }
+        self.transcript.reveals.push(RevealEvent {
+            participant: id.clone(),
+            revealed: false,
+            opening: None,
+            timestamp: self.current_time,
+        });
+        self.log_broadcast(
+            id.clone(),
+            BroadcastMessage::Withheld,
+            Some(MessagePayload::Withhold { from: id }),
+        );
+        Ok(())
+    }
+
     pub fn end_reveal_and_resolve(
         mut self,
     ) -> Result<(AuctionOutcome, Transcript, BroadcastLog), ProtocolError> {
-        if self.phase != Phase::Reveal {
-            return Err(ProtocolError::WrongPhase);
+        // Resolution cannot precede the reveal deadline, so an early call runs the clock out;
+        // a session the deadline already resolved is accepted as-is.
+        self.sync_clock()?;
+        match self.phase {
+            Phase::Commit => return Err(ProtocolError::WrongPhase),
+            Phase::Reveal => self.advance_to(self.schedule.reveal_deadline)?,
+            Phase::Resolved => {}
         }
-        self.transition_to_phase(Phase::Resolved, PhaseTransitionReason::Manual)?;
         // Apply reveals: set will_reveal flags based on reveal events.
         let mut missing: Vec<ParticipantId> = Vec::new();
         for (pid, _, _, _, will_reveal) in self.commitments.iter_mut() {
@@ -280,12 +590,13 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
                 missing.push(pid.clone());
             }
         }
+        // A missed reveal is recorded at the deadline, even if the clock has since moved on.
         for pid in missing {
             self.transcript.reveals.push(RevealEvent {
                 participant: pid.clone(),
                 revealed: false,
                 opening: None,
-                timestamp: self.current_time,
+                timestamp: self.schedule.reveal_deadline,
             });
             self.log_broadcast(
                 ParticipantId::Auctioneer,
@@ -296,12 +607,15 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
                 Some(MessagePayload::Timeout { target: pid }),
             );
         }
-        // Prepare inputs for core DRA.
+        // Prepare inputs for core DRA. It labels bids by position, so feed them in participant
+        // order rather than the order the commitments arrived in.
+        let mut ordered: Vec<_> = self.commitments.iter().collect();
+        ordered.sort_by_key(|(pid, ..)| pid.tie_rank());
         let mut real_bids: Vec<f64> = Vec::new();
         let mut real_reveals: Vec<bool> = Vec::new();
         let mut false_bids: Vec<FalseBid> = Vec::new();
         let mut max_real_idx = 0usize;
-        for (pid, _c, o, _coll, will_reveal) in self.commitments.iter() {
+        for (pid, _c, o, _coll, will_reveal) in ordered.iter().copied() {
             match pid {
                 ParticipantId::Real(i) => {
                     if *i >= max_real_idx {
@@ -317,17 +631,38 @@ impl<D: ValueDistribution, S: CommitmentScheme + Clone> ProtocolSession<D, S> {
                 ParticipantId::Auctioneer => {}
             }
         }
-        // Run auction.
+        // Run auction, seeded from the session so a deterministic DRA resolves reproducibly.
+        let run_seed = self.rng.next_u64();
         let (outcome, mut transcript) = self.dra.run_with_false_bids_using_scheme_with_transcript(
             &real_bids,
             &false_bids,
             Some(&real_reveals),
-            None,
+            Some(run_seed),
             &mut self.scheme.clone(),
         );
+        // Settle against the collateral each participant actually posted in this session.
+        let posted: Vec<(ParticipantId, f64, bool)> = ordered
+            .iter()
+            .map(|(pid, _, _, collateral, revealed)| (pid.clone(), *collateral, *revealed))
+            .collect();
+        let mut outcome = outcome;
+        outcome.settlements = settle(
+            &posted,
+            &outcome.valid_bids,
+            outcome.winner.as_ref(),
+            outcome.payment,
+            outcome.tie_break,
+        );
+        transcript.outcome = Some(outcome.clone());
         // Merge transcripts.
         transcript.commitments = self.transcript.commitments;
         transcript.reveals = self.transcript.reveals;
+        let revealed: Vec<&Opening> = transcript
+            .reveals
+            .iter()
+            .filter_map(|r| r.opening.as_ref())
+            .collect();
+        transcript.shared_proofs = self.scheme.shared_proofs(&revealed);
         transcript.broadcasts = self.broadcasts;
         transcript.timings = self.schedule;
         // Final audit.
@@ -374,4 +709,522 @@ mod tests {
             "buyer 1 should see buyer 0 commitment"
         );
     }
+
+    #[test]
+    fn subscribers_include_auctioneer_and_committed_buyers() {
+        let dist = Uniform::new(0.0, 10.0);
+        let dra = PublicBroadcastDRA::new(dist, 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(2);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 5, schedule, Vec::new());
+        session
+            .commit_real(0, 6.0, collateral)
+            .expect("commit buyer 0");
+        session
+            .commit_real(1, 3.0, collateral)
+            .expect("commit buyer 1");
+        assert_eq!(
+            session.subscribers(),
+            &[
+                ParticipantId::Auctioneer,
+                ParticipantId::Real(0),
+                ParticipantId::Real(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn observer_sees_transition_to_reveal() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(1);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 3, schedule, Vec::new());
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        session.set_observer(move |event| sink.borrow_mut().push(event.message.clone()));
+        session.commit_real(0, 6.0, collateral).expect("commit");
+        session.end_commit_phase().expect("end commit");
+        let seen = seen.borrow();
+        assert!(matches!(seen[0], BroadcastMessage::CommitmentPublished));
+        assert!(matches!(
+            seen.last(),
+            Some(BroadcastMessage::PhaseTransition {
+                phase: Phase::Reveal,
+                ..
+            })
+        ));
+        assert_eq!(seen.len(), session.broadcasts.len());
+    }
+
+    #[test]
+    fn auto_reveal_opens_every_honest_commitment() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(3);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 12, schedule, Vec::new());
+        for (idx, bid) in [8.0, 6.0, 7.0].into_iter().enumerate() {
+            session.commit_real(idx, bid, collateral).expect("commit");
+        }
+        session
+            .commit_false(0, 9.0, collateral, false)
+            .expect("shill");
+        assert!(matches!(
+            session.auto_reveal_all_honest(),
+            Err(ProtocolError::WrongPhase)
+        ));
+        session.end_commit_phase().expect("enter reveal");
+        session
+            .reveal(ParticipantId::Real(1))
+            .expect("manual reveal");
+        assert_eq!(session.auto_reveal_all_honest().expect("auto reveal"), 2);
+        for idx in 0..3 {
+            assert!(
+                session
+                    .transcript
+                    .reveals
+                    .iter()
+                    .any(|r| r.participant == ParticipantId::Real(idx) && r.revealed)
+            );
+        }
+        assert!(
+            !session
+                .transcript
+                .reveals
+                .iter()
+                .any(|r| r.participant == ParticipantId::False(0))
+        );
+        let (outcome, _, _) = session.end_reveal_and_resolve().expect("resolve");
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+    }
+
+    #[test]
+    fn jumping_past_both_deadlines_is_flagged() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(1);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 2, schedule, Vec::new());
+        session.commit_real(0, 6.0, collateral).expect("commit");
+        assert!(matches!(
+            session.advance_to(9),
+            Err(ProtocolError::SkippedPhase(Phase::Reveal))
+        ));
+        assert_eq!(session.phase(), Phase::Commit);
+        session.advance_to(4).expect("enter reveal");
+        session.advance_to(9).expect("close reveal");
+        assert_eq!(session.phase(), Phase::Resolved);
+    }
+
+    #[test]
+    fn resolving_early_runs_the_clock_to_the_reveal_deadline() {
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let session_at = |resolve_from: u64| {
+            let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+            let collateral = dra.collateral(2);
+            let mut session = ProtocolSession::new(
+                dra,
+                NonMalleableShaCommitment,
+                5,
+                schedule.clone(),
+                Vec::new(),
+            );
+            session.commit_real(0, 7.0, collateral).expect("commit 0");
+            session.commit_real(1, 5.0, collateral).expect("commit 1");
+            session.advance_to(4).expect("enter reveal");
+            session.reveal(ParticipantId::Real(0)).expect("reveal 0");
+            session.advance_to(resolve_from).expect("advance");
+            session
+        };
+
+        let early = session_at(5);
+        assert_eq!(early.phase(), Phase::Reveal);
+        let (outcome, transcript, _) = early.end_reveal_and_resolve().expect("early resolve");
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+        let resolved_at = transcript
+            .broadcasts
+            .iter()
+            .find(|e| {
+                matches!(
+                    e.message,
+                    BroadcastMessage::PhaseTransition {
+                        phase: Phase::Resolved,
+                        reason: PhaseTransitionReason::Deadline,
+                    }
+                )
+            })
+            .expect("resolution broadcast")
+            .timestamp;
+        assert_eq!(resolved_at, schedule.reveal_deadline);
+        let timeout = transcript
+            .reveals
+            .iter()
+            .find(|r| r.participant == ParticipantId::Real(1))
+            .expect("timed-out reveal");
+        assert!(!timeout.revealed);
+        assert_eq!(timeout.timestamp, schedule.reveal_deadline);
+
+        // A session the deadline already resolved is accepted and resolves the same way.
+        let late = session_at(9);
+        assert_eq!(late.phase(), Phase::Resolved);
+        let (late_outcome, _, _) = late.end_reveal_and_resolve().expect("late resolve");
+        assert_eq!(
+            serde_json::to_value(&late_outcome).expect("serialize"),
+            serde_json::to_value(&outcome).expect("serialize")
+        );
+    }
+
+    #[test]
+    fn system_clock_session_runs_on_an_epoch_millisecond_schedule() {
+        use crate::clock::SystemClock;
+        let start = SystemClock::default().now();
+        let schedule = PhaseTimings {
+            commit_deadline: start + 60_000,
+            reveal_deadline: start + 120_000,
+        };
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0)
+            .with_clock(SystemClock::default());
+        let collateral = dra.collateral(2);
+        let mut session = ProtocolSession::new(
+            dra,
+            NonMalleableShaCommitment,
+            7,
+            schedule.clone(),
+            Vec::new(),
+        );
+        session.commit_real(0, 8.0, collateral).expect("commit 0");
+        session.commit_real(1, 6.0, collateral).expect("commit 1");
+        session.end_commit_phase().expect("enter reveal");
+        session.auto_reveal_all_honest().expect("reveal");
+        let (outcome, transcript, _) = session.end_reveal_and_resolve().expect("resolve");
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+        assert!(
+            transcript
+                .commitments
+                .iter()
+                .all(|c| (start..schedule.commit_deadline).contains(&c.timestamp))
+        );
+        assert!(
+            transcript
+                .reveals
+                .iter()
+                .all(|r| r.timestamp == schedule.commit_deadline)
+        );
+    }
+
+    #[test]
+    fn system_clock_past_both_deadlines_closes_each_phase() {
+        use crate::clock::SystemClock;
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0)
+            .with_clock(SystemClock::default());
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 4, schedule, Vec::new());
+        assert!(matches!(
+            session.commit_real(0, 6.0, 1.0),
+            Err(ProtocolError::WrongPhase)
+        ));
+        assert_eq!(session.phase(), Phase::Resolved);
+        let stamps: Vec<u64> = session.broadcasts.iter().map(|e| e.timestamp).collect();
+        assert_eq!(stamps.len(), 2);
+        assert_eq!(stamps[0], 4);
+        assert!(stamps[1] > 1_600_000_000_000);
+    }
+
+    #[test]
+    fn deterministic_dra_resolves_reproducibly_in_a_session() {
+        let resolve = || {
+            let dra = PublicBroadcastDRA::builder()
+                .distribution(Uniform::new(0.0, 10.0))
+                .alpha(1.0)
+                .deterministic(true)
+                .build()
+                .expect("complete builder");
+            let schedule = PhaseTimings {
+                commit_deadline: 4,
+                reveal_deadline: 8,
+            };
+            let collateral = dra.collateral(2);
+            let mut session =
+                ProtocolSession::new(dra, NonMalleableShaCommitment, 355, schedule, Vec::new());
+            session.commit_real(0, 7.0, collateral).expect("commit 0");
+            session.commit_real(1, 7.0, collateral).expect("commit 1");
+            session.end_commit_phase().expect("enter reveal");
+            session.auto_reveal_all_honest().expect("reveal");
+            let (outcome, _, _) = session.end_reveal_and_resolve().expect("resolve");
+            serde_json::to_value(&outcome).expect("serialize outcome")
+        };
+        assert_eq!(resolve(), resolve());
+    }
+
+    #[test]
+    fn allowed_actions_follow_a_participant_through_the_phases() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(2);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 6, schedule, Vec::new());
+        let (buyer, idle) = (ParticipantId::Real(0), ParticipantId::Real(1));
+        assert_eq!(session.allowed_actions(&buyer), vec![Action::Commit]);
+        session.commit_real(0, 6.0, collateral).expect("commit");
+        assert!(session.allowed_actions(&buyer).is_empty());
+        assert!(
+            session
+                .allowed_actions(&ParticipantId::Auctioneer)
+                .is_empty()
+        );
+        session.end_commit_phase().expect("enter reveal");
+        assert_eq!(
+            session.allowed_actions(&buyer),
+            vec![Action::Reveal, Action::Withhold]
+        );
+        assert!(session.allowed_actions(&idle).is_empty());
+        session.reveal(buyer.clone()).expect("reveal");
+        assert!(session.allowed_actions(&buyer).is_empty());
+    }
+
+    #[test]
+    fn honest_losing_revealer_is_refunded_in_full() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(2);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 8, schedule, Vec::new());
+        session
+            .commit_real(0, 9.0, collateral)
+            .expect("commit winner");
+        session
+            .commit_real(1, 7.0, collateral)
+            .expect("commit loser");
+        session
+            .commit_false(0, 8.0, collateral, false)
+            .expect("commit shill");
+        session.end_commit_phase().expect("enter reveal");
+        session.auto_reveal_all_honest().expect("reveal");
+        let (outcome, transcript, _) = session.end_reveal_and_resolve().expect("resolve");
+        let settlement = |id: ParticipantId| {
+            outcome
+                .settlements
+                .iter()
+                .find(|s| s.participant == id)
+                .cloned()
+                .expect("settled")
+        };
+        let loser = settlement(ParticipantId::Real(1));
+        assert_eq!(loser.posted_collateral, collateral);
+        assert_eq!(loser.refund, collateral);
+        assert_eq!((loser.payment, loser.received), (0.0, 0.0));
+        let winner = settlement(ParticipantId::Real(0));
+        assert_eq!(winner.refund, collateral);
+        assert_eq!(winner.payment, outcome.payment);
+        assert_eq!(winner.received, collateral);
+        assert_eq!(settlement(ParticipantId::False(0)).refund, 0.0);
+        assert_eq!(
+            transcript.outcome.expect("outcome").settlements,
+            outcome.settlements
+        );
+    }
+
+    #[test]
+    fn withholding_forfeits_collateral_and_differs_from_timeout() {
+        let dist = Uniform::new(0.0, 10.0);
+        let dra = PublicBroadcastDRA::new(dist, 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(3);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 9, schedule, Vec::new());
+        for (idx, bid) in [8.0, 6.0, 7.0].into_iter().enumerate() {
+            session.commit_real(idx, bid, collateral).expect("commit");
+        }
+        session.advance_to(4).expect("enter reveal");
+        session.reveal(ParticipantId::Real(0)).expect("reveal");
+        session.withhold(ParticipantId::Real(2)).expect("withhold");
+        assert!(matches!(
+            session.withhold(ParticipantId::Real(2)),
+            Err(ProtocolError::DuplicateReveal(_))
+        ));
+        let (outcome, transcript, log) = session.end_reveal_and_resolve().expect("resolve");
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+        assert!((outcome.transferred_collateral - 2.0 * collateral).abs() < 1e-9);
+
+        let withheld = |target: &ParticipantId| {
+            transcript.broadcasts.iter().any(|e| {
+                e.sender == *target && matches!(e.message, BroadcastMessage::Withheld)
+            })
+        };
+        let timed_out = |target: &ParticipantId| {
+            transcript.broadcasts.iter().any(|e| {
+                matches!(&e.message, BroadcastMessage::Timeout { target: t, .. } if t == target)
+            })
+        };
+        assert!(withheld(&ParticipantId::Real(2)) && !timed_out(&ParticipantId::Real(2)));
+        assert!(timed_out(&ParticipantId::Real(1)) && !withheld(&ParticipantId::Real(1)));
+        assert!(
+            log.per_recipient_view(&ParticipantId::Real(0))
+                .iter()
+                .any(|m| matches!(m.payload, MessagePayload::Withhold { .. }))
+        );
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_json_and_resumes() {
+        let dist = Uniform::new(0.0, 10.0);
+        let dra = PublicBroadcastDRA::new(dist.clone(), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(2);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 21, schedule, Vec::new());
+        session.commit_real(0, 7.0, collateral).expect("commit 0");
+        session.commit_real(1, 4.0, collateral).expect("commit 1");
+        session.advance_to(4).expect("enter reveal");
+
+        let json = serde_json::to_string(&session.checkpoint()).expect("serialize");
+        let checkpoint: SessionCheckpoint = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(checkpoint.rng_words, session.rng.words);
+        let mut resumed = ProtocolSession::resume(
+            PublicBroadcastDRA::new(dist, 1.0),
+            NonMalleableShaCommitment,
+            checkpoint,
+        );
+        assert_eq!(resumed.phase(), Phase::Reveal);
+        assert_eq!(resumed.rng.next_u64(), session.rng.next_u64());
+
+        resumed.reveal(ParticipantId::Real(0)).expect("reveal 0");
+        resumed.reveal(ParticipantId::Real(1)).expect("reveal 1");
+        let (outcome, transcript, _) = resumed.end_reveal_and_resolve().expect("resolve");
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(0)));
+        assert_eq!(transcript.commitments.len(), 2);
+    }
+
+    #[test]
+    fn partitioned_buyer_never_sees_the_other_commitment() {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(2);
+        let participants = vec![ParticipantId::Real(0), ParticipantId::Real(1)];
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 3, schedule, participants);
+        session.set_partition(
+            HashSet::from([ParticipantId::Auctioneer, ParticipantId::Real(0)]),
+            0..4,
+        );
+        session.commit_real(0, 8.0, collateral).expect("commit 0");
+        session.commit_real(1, 5.0, collateral).expect("commit 1");
+        session.advance_to(4).expect("enter reveal");
+        session.reveal(ParticipantId::Real(0)).expect("reveal 0");
+
+        let sees_commitment = |recipient: ParticipantId, from: ParticipantId| {
+            session
+                .network_log()
+                .per_recipient_view(&recipient)
+                .iter()
+                .any(|m| matches!(&m.payload, MessagePayload::Commitment { from: f } if *f == from))
+        };
+        assert!(!sees_commitment(ParticipantId::Real(1), ParticipantId::Real(0)));
+        assert!(!sees_commitment(ParticipantId::Auctioneer, ParticipantId::Real(1)));
+        assert!(sees_commitment(ParticipantId::Auctioneer, ParticipantId::Real(0)));
+        // Once the window closes, buyer 1 hears the reveal like everyone else.
+        assert!(
+            session
+                .network_log()
+                .per_recipient_view(&ParticipantId::Real(1))
+                .iter()
+                .any(|m| matches!(m.payload, MessagePayload::Reveal { .. }))
+        );
+    }
+
+    /// Resolve three buyers committing in `commit_order` and revealing in `reveal_order`.
+    fn resolve_in_order(commit_order: [usize; 3], reveal_order: [usize; 3]) -> serde_json::Value {
+        let dra = PublicBroadcastDRA::new(Uniform::new(0.0, 10.0), 1.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 8,
+        };
+        let collateral = dra.collateral(3);
+        let mut session =
+            ProtocolSession::new(dra, NonMalleableShaCommitment, 9, schedule, Vec::new());
+        let values = [6.0, 9.0, 7.5];
+        for i in commit_order {
+            session
+                .commit_real(i, values[i], collateral)
+                .expect("commit");
+        }
+        session.advance_to(4).expect("enter reveal");
+        for i in reveal_order {
+            session.reveal(ParticipantId::Real(i)).expect("reveal");
+        }
+        let (outcome, _, _) = session.end_reveal_and_resolve().expect("resolve");
+        assert_eq!(outcome.winner, Some(ParticipantId::Real(1)));
+        serde_json::to_value(&outcome).expect("serialize outcome")
+    }
+
+    const ORDERINGS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+
+    #[test]
+    fn outcome_is_independent_of_reveal_order() {
+        let expected = resolve_in_order([0, 1, 2], [0, 1, 2]);
+        for order in ORDERINGS {
+            assert_eq!(
+                resolve_in_order([0, 1, 2], order),
+                expected,
+                "reveal order {order:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn outcome_is_independent_of_commit_order() {
+        let expected = resolve_in_order([0, 1, 2], [0, 1, 2]);
+        for order in ORDERINGS {
+            assert_eq!(
+                resolve_in_order(order, [0, 1, 2]),
+                expected,
+                "commit order {order:?}"
+            );
+        }
+    }
 }
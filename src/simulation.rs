@@ -1,19 +1,30 @@
-use rand::RngCore;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
 use rand::SeedableRng;
 use rand::rngs::StdRng;
+use rand::{Rng, RngCore};
+use rayon::prelude::*;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 
 use crate::FalseBid;
-use crate::auction::{AuctionOutcome, ParticipantId, PhaseTimings, PublicBroadcastDRA};
+use crate::auction::{
+    AuctionOutcome, ParticipantId, PhaseTimings, PublicBroadcastDRA, ValidationError,
+};
+use crate::centralized::adaptive_reserve_deviation;
 use crate::commitment::{
-    AuditedNonMalleableCommitment, BulletproofsCommitment, NonMalleableShaCommitment,
-    PedersenRistrettoCommitment, RealNonMalleableCommitment,
+    AuditedNonMalleableCommitment, Blake3Commitment, BulletproofsCommitment, DynCommitmentScheme,
+    NonMalleableShaCommitment, PedersenRistrettoCommitment, RealNonMalleableCommitment,
 };
-use crate::distribution::ValueDistribution;
+use crate::distribution::{DynValueDistribution, Mixture, ValueDistribution};
 use crate::protocol::ProtocolSession;
 
 /// Numerically integrate expected optimal revenue via Myerson's virtual surplus:
 /// Rev(D^n) = ∫ phi^+(v) * n f(v) F(v)^{n-1} dv.
+#[cfg(test)]
 fn numeric_optimal_revenue<D: ValueDistribution>(
     dist: &D,
     buyers: usize,
@@ -36,6 +47,7 @@ fn numeric_optimal_revenue<D: ValueDistribution>(
 }
 
 /// Binary search for x where CDF(x) ~= q.
+#[cfg(test)]
 fn find_quantile<D: ValueDistribution>(dist: &D, q: f64) -> f64 {
     assert!(q > 0.0 && q < 1.0, "quantile must be in (0,1)");
     let mut lo = 0.0;
@@ -54,11 +66,13 @@ fn find_quantile<D: ValueDistribution>(dist: &D, q: f64) -> f64 {
     hi
 }
 
+#[cfg(test)]
 fn closed_form_optimal_revenue_uniform(low: f64, high: f64, buyers: usize) -> f64 {
     // Use numeric integration for uniform to avoid algebraic mistakes.
     numeric_optimal_revenue(&crate::distribution::Uniform::new(low, high), buyers, 50_000, 0.999999)
 }
 
+#[cfg(test)]
 fn closed_form_optimal_revenue_pareto(scale: f64, shape: f64, buyers: usize) -> f64 {
     numeric_optimal_revenue(&crate::distribution::Pareto::new(scale, shape), buyers, 50_000, 0.999999)
 }
@@ -67,9 +81,15 @@ fn closed_form_optimal_revenue_pareto(scale: f64, shape: f64, buyers: usize) ->
 pub struct RevenueStats {
     pub baseline: f64,
     pub deviated: f64,
+    /// `deviated` without the payment of a winning false bid; see
+    /// [`SimulationResult::deviated_net_revenue`].
+    pub deviated_net: f64,
 }
 
-#[derive(Clone, Debug)]
+/// Deviation chosen from the realised valuations of the real buyers.
+pub type AdaptiveFalseBids = Arc<dyn Fn(&[f64]) -> Vec<FalseBid> + Send + Sync>;
+
+#[derive(Clone)]
 pub enum DeviationModel {
     Fixed(FalseBid),
     Multiple(Vec<FalseBid>),
@@ -77,13 +97,119 @@ pub enum DeviationModel {
         bid: f64,
         reveal_if_top_at_least: f64,
     },
+    /// Layer several models; each is evaluated against the same top real bid.
+    Combined(Vec<DeviationModel>),
+    /// Evaluated per trial after valuations are sampled, before any commitment is made.
+    Adaptive(AdaptiveFalseBids),
+}
+
+impl fmt::Debug for DeviationModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviationModel::Fixed(fb) => f.debug_tuple("Fixed").field(fb).finish(),
+            DeviationModel::Multiple(fbs) => f.debug_tuple("Multiple").field(fbs).finish(),
+            DeviationModel::ThresholdReveal {
+                bid,
+                reveal_if_top_at_least,
+            } => f
+                .debug_struct("ThresholdReveal")
+                .field("bid", bid)
+                .field("reveal_if_top_at_least", reveal_if_top_at_least)
+                .finish(),
+            DeviationModel::Combined(models) => f.debug_tuple("Combined").field(models).finish(),
+            DeviationModel::Adaptive(_) => f.write_str("Adaptive(..)"),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct SimulationResult {
     pub baseline_revenue: f64,
     pub deviated_revenue: f64,
+    /// Deviated revenue without the payment of a winning false bid, which the auctioneer would
+    /// pay to itself while keeping the item. The baseline has no false bids, so its net revenue
+    /// is `baseline_revenue`.
+    pub deviated_net_revenue: f64,
     pub allocation_change_rate: f64,
+    /// How the winner changed, as fractions of trials summing to `allocation_change_rate`.
+    pub allocation_changes: AllocationChangeBreakdown,
+    pub winner_types: WinnerTally,
+    pub baseline_stddev: f64,
+    pub deviated_stddev: f64,
+    /// Normal-approximation 95% interval for the mean paired gain `deviated - baseline`.
+    pub revenue_gain_ci95: (f64, f64),
+    /// Mean true valuation of the winning real buyer (0 when unsold or a false bid wins).
+    pub baseline_welfare: f64,
+    pub deviated_welfare: f64,
+    /// Fraction of trials where the deviation left the item with a lower-valued holder.
+    pub efficiency_loss_rate: f64,
+    /// Fraction of trials with any winner, a winning false bid included; `winner_types` tells
+    /// real and false winners apart.
+    pub baseline_sale_rate: f64,
+    pub deviated_sale_rate: f64,
+    /// Mean collateral forfeited to the auctioneer, the part of the revenue above that is not
+    /// a payment. This only happens when no bid at all is revealed.
+    pub baseline_forfeit_mean: f64,
+    pub deviated_forfeit_mean: f64,
+    /// Mean withheld collateral handed to the highest valid bidder instead.
+    pub baseline_transfer_mean: f64,
+    pub deviated_transfer_mean: f64,
+    /// Trials dropped because a valuation or revenue was NaN or infinite. Every mean and rate
+    /// above is taken over the remaining `trials - skipped` trials.
+    pub skipped: usize,
+}
+
+/// Fractions of trials in which the deviation changed the winner, by kind of change.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AllocationChangeBreakdown {
+    /// Unsold in the baseline, sold under the deviation.
+    pub created_sale: f64,
+    /// Sold in the baseline, unsold under the deviation.
+    pub suppressed_sale: f64,
+    /// One real buyer replaced by another.
+    pub real_swap: f64,
+    /// A real buyer (or no one) replaced by a false bid, which keeps the item unallocated.
+    pub false_takeover: f64,
+}
+
+impl AllocationChangeBreakdown {
+    fn record(&mut self, baseline: &AuctionOutcome, deviated: &AuctionOutcome) {
+        match (&baseline.winner, &deviated.winner) {
+            (before, after) if before == after => {}
+            (_, Some(ParticipantId::False(_))) => self.false_takeover += 1.0,
+            (None, Some(_)) => self.created_sale += 1.0,
+            (Some(_), None) => self.suppressed_sale += 1.0,
+            (Some(_), Some(_)) => self.real_swap += 1.0,
+            (None, None) => {}
+        }
+    }
+
+    fn scaled(self, n: f64) -> Self {
+        Self {
+            created_sale: self.created_sale / n,
+            suppressed_sale: self.suppressed_sale / n,
+            real_swap: self.real_swap / n,
+            false_takeover: self.false_takeover / n,
+        }
+    }
+}
+
+/// Counts who ended up with the item across the deviated runs.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct WinnerTally {
+    pub real_wins: usize,
+    pub false_wins: usize,
+    pub no_sale: usize,
+}
+
+impl WinnerTally {
+    fn record(&mut self, outcome: &AuctionOutcome) {
+        match outcome.winner {
+            Some(ParticipantId::False(_)) => self.false_wins += 1,
+            Some(_) => self.real_wins += 1,
+            None => self.no_sale += 1,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -91,28 +217,153 @@ pub struct TimedSimulationReport {
     pub successful_runs: usize,
     pub deadline_failures: usize,
     pub average_revenue: f64,
+    /// Largest utility gain any probed bidder could have had by misreporting; ~0 when truthful.
+    pub max_regret: f64,
+    /// Honest reveals forgotten under `reveal_failure_prob`, summed over trials.
+    pub missed_reveals: usize,
+}
+
+/// Mean revenues with and without the centralized censorship deviation (Definition 23).
+#[derive(Clone, Debug, Serialize)]
+pub struct CensorshipSimulationResult {
+    pub baseline_revenue: f64,
+    pub deviation_revenue: f64,
+    /// Fraction of trials in which censoring strictly raised revenue.
+    pub helped_rate: f64,
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct SafeDeviationStats {
     pub satisfied: bool,
     pub max_violation: f64,
+    /// Fraction of trials whose deviation revenue beat the baseline.
+    pub violation_rate: f64,
+    /// Inputs of the trial that produced `max_violation`, if any trial violated the bound.
+    pub worst_case: Option<ViolationWitness>,
+    /// [`theoretical_safe_bound`](crate::theoretical_safe_bound) for this distribution,
+    /// alpha and buyer count.
+    pub theoretical_bound: f64,
+    /// Mean gain per trial, charging the auctioneer the collateral of its withheld false bids.
+    pub mean_gain: f64,
+    /// The 95% interval of `mean_gain` lies wholly above `theoretical_bound`. The bound covers
+    /// one false bid revealed exactly when it raises the price, so a deviation outside that
+    /// model (several false bids, or one revealed to win) can exceed it legitimately.
+    pub exceeds_theory: bool,
+}
+
+/// A replayable counterexample: run the baseline with `base_seed` and the deviation with
+/// `dev_seed` on `valuations` to reproduce both revenues.
+#[derive(Clone, Debug, Serialize)]
+pub struct ViolationWitness {
+    pub valuations: Vec<f64>,
+    pub base_revenue: f64,
+    pub dev_revenue: f64,
+    pub base_seed: u64,
+    pub dev_seed: u64,
 }
 
+/// Built-in backends by value; converts into a `Box<dyn DynCommitmentScheme>`.
 #[derive(Clone, Debug)]
 pub enum Backend {
     Sha(NonMalleableShaCommitment),
+    Blake3(Blake3Commitment),
     Pedersen(PedersenRistrettoCommitment),
     Audited(AuditedNonMalleableCommitment),
     Fischlin(RealNonMalleableCommitment),
     Bulletproofs(BulletproofsCommitment),
 }
 
+impl From<Backend> for Box<dyn DynCommitmentScheme> {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Sha(s) => Box::new(s),
+            Backend::Blake3(b) => Box::new(b),
+            Backend::Pedersen(p) => Box::new(p),
+            Backend::Audited(a) => Box::new(a),
+            Backend::Fischlin(f) => Box::new(f),
+            Backend::Bulletproofs(b) => Box::new(b),
+        }
+    }
+}
+
 fn auctioneer_revenue(outcome: &AuctionOutcome) -> f64 {
     outcome.payment + outcome.forfeited_to_auctioneer - outcome.auctioneer_penalty
 }
 
-fn false_bids_from_model(model: &DeviationModel, top_real_bid: f64) -> Vec<FalseBid> {
+/// [`auctioneer_revenue`] less the payment of a winning false bid: that bid keeps the item
+/// unsold, and its "payment" would come out of the auctioneer's own pocket.
+fn net_auctioneer_revenue(outcome: &AuctionOutcome) -> f64 {
+    match outcome.winner {
+        Some(ParticipantId::False(_)) => auctioneer_revenue(outcome) - outcome.payment,
+        _ => auctioneer_revenue(outcome),
+    }
+}
+
+/// Valuation realised by the allocation: the winner's true value if a real buyer wins.
+fn social_welfare(valuations: &[f64], outcome: &AuctionOutcome) -> f64 {
+    match outcome.winner {
+        Some(ParticipantId::Real(i)) => valuations[i],
+        _ => 0.0,
+    }
+}
+
+/// Efficient benchmark: the highest-valued buyer wins (lowest index on ties) and pays the
+/// second-highest value, with no reserve or collateral. `(None, 0.0)` when there are no buyers.
+pub fn vcg_outcome(valuations: &[f64]) -> (Option<usize>, f64) {
+    let mut ranked: Vec<usize> = (0..valuations.len()).collect();
+    ranked.sort_by(|&a, &b| valuations[b].total_cmp(&valuations[a]).then(a.cmp(&b)));
+    let payment = ranked.get(1).map_or(0.0, |&i| valuations[i]);
+    (ranked.first().copied(), payment)
+}
+
+/// Mean honest revenue at each posted reserve in `reserves`: the top bid wins if it strictly
+/// exceeds the reserve and pays the larger of the reserve and the second bid. Every reserve
+/// sees the same `trials` draws of `buyers` values, so the curve is smooth in the reserve.
+pub fn revenue_curve<D: ValueDistribution>(
+    dist: D,
+    buyers: usize,
+    trials: usize,
+    reserves: &[f64],
+    seed: u64,
+) -> Vec<f64> {
+    let mut totals = vec![0.0; reserves.len()];
+    for trial in 0..trials {
+        let mut rng = trial_rng(seed, trial);
+        let vals: Vec<f64> = (0..buyers).map(|_| dist.sample(&mut rng)).collect();
+        let (Some(winner), second) = vcg_outcome(&vals) else {
+            continue;
+        };
+        for (total, &reserve) in totals.iter_mut().zip(reserves) {
+            if vals[winner] > reserve {
+                *total += reserve.max(second);
+            }
+        }
+    }
+    let n = trials.max(1) as f64;
+    totals.into_iter().map(|total| total / n).collect()
+}
+
+/// The candidate reserve with the highest [`revenue_curve`] value, the lowest one on ties.
+pub fn empirical_optimal_reserve<D: ValueDistribution>(
+    dist: D,
+    buyers: usize,
+    trials: usize,
+    candidates: &[f64],
+    seed: u64,
+) -> f64 {
+    assert!(
+        !candidates.is_empty(),
+        "need at least one candidate reserve"
+    );
+    let curve = revenue_curve(dist, buyers, trials, candidates, seed);
+    let best = (0..candidates.len())
+        .reduce(|best, i| if curve[i] > curve[best] { i } else { best })
+        .expect("non-empty candidates");
+    candidates[best]
+}
+
+fn false_bids_from_model(model: &DeviationModel, valuations: &[f64]) -> Vec<FalseBid> {
+    let top_real_bid = valuations.iter().cloned().fold(0.0_f64, f64::max);
     match model {
         DeviationModel::Fixed(fb) => vec![fb.clone()],
         DeviationModel::Multiple(fbs) => fbs.clone(),
@@ -123,6 +374,11 @@ fn false_bids_from_model(model: &DeviationModel, top_real_bid: f64) -> Vec<False
             bid: *bid,
             reveal: top_real_bid >= *reveal_if_top_at_least,
         }],
+        DeviationModel::Combined(models) => models
+            .iter()
+            .flat_map(|m| false_bids_from_model(m, valuations))
+            .collect(),
+        DeviationModel::Adaptive(choose) => choose(valuations),
     }
 }
 
@@ -146,7 +402,79 @@ pub fn simulate_false_bid_impact<D: ValueDistribution + Clone>(
     RevenueStats {
         baseline: result.baseline_revenue,
         deviated: result.deviated_revenue,
+        deviated_net: result.deviated_net_revenue,
+    }
+}
+
+/// Grid points scanned by [`optimize_false_bid`] before golden-section refinement.
+const FALSE_BID_GRID: usize = 24;
+/// Golden-section iterations used to refine the best grid bracket.
+const FALSE_BID_REFINE_STEPS: usize = 20;
+
+/// Search for the revealed false bid that maximises the auctioneer's simulated net revenue,
+/// returning `(bid, deviated_net_revenue)`. Net revenue is used because a shill that outbids
+/// every buyer would otherwise be rewarded for buying the item back from itself.
+///
+/// Only revealed bids are searched: withholding the opening (forfeiting collateral) is a separate
+/// deviation that this search does not consider, so the result is a best response within
+/// `FalseBid { reveal: true, .. }` rather than over every shill strategy.
+///
+/// Bids in `[0, 2 * (reserve + collateral)]` are scanned on a grid and the best bracket is
+/// refined by golden-section search. Every evaluation reuses `seed`, so all candidate bids face
+/// the same sampled valuations and differences reflect the bid rather than sampling noise.
+pub fn optimize_false_bid<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    seed: u64,
+) -> (f64, f64) {
+    let dra = PublicBroadcastDRA::new(dist.clone(), alpha);
+    let upper = 2.0 * (dist.reserve_price() + dra.collateral(buyers));
+    let revenue = |bid: f64| {
+        simulate_false_bid_impact(
+            dist.clone(),
+            alpha,
+            buyers,
+            trials,
+            FalseBid { bid, reveal: true },
+            seed,
+        )
+        .deviated_net
+    };
+
+    let step = upper / FALSE_BID_GRID as f64;
+    let (best_idx, mut best) = (0..=FALSE_BID_GRID)
+        .map(|i| (i, (i as f64 * step, revenue(i as f64 * step))))
+        .fold((0, (0.0, f64::NEG_INFINITY)), |acc, (i, point)| {
+            if point.1 > acc.1.1 { (i, point) } else { acc }
+        });
+
+    let inv_phi = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let mut lo = best_idx.saturating_sub(1) as f64 * step;
+    let mut hi = (best_idx + 1).min(FALSE_BID_GRID) as f64 * step;
+    let mut x1 = hi - inv_phi * (hi - lo);
+    let mut x2 = lo + inv_phi * (hi - lo);
+    let (mut f1, mut f2) = (revenue(x1), revenue(x2));
+    for _ in 0..FALSE_BID_REFINE_STEPS {
+        if f1 >= f2 {
+            hi = x2;
+            (x2, f2) = (x1, f1);
+            x1 = hi - inv_phi * (hi - lo);
+            f1 = revenue(x1);
+        } else {
+            lo = x1;
+            (x1, f1) = (x2, f2);
+            x2 = lo + inv_phi * (hi - lo);
+            f2 = revenue(x2);
+        }
     }
+    for candidate in [(x1, f1), (x2, f2)] {
+        if candidate.1 > best.1 {
+            best = candidate;
+        }
+    }
+    best
 }
 
 /// Monte Carlo compare baseline vs. an arbitrary deviation model.
@@ -176,122 +504,673 @@ pub fn simulate_deviation_with_scheme<D: ValueDistribution + Clone>(
     trials: usize,
     deviation: DeviationModel,
     seed: u64,
-    backend: Backend,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
 ) -> SimulationResult {
-    let dra = PublicBroadcastDRA::new(dist.clone(), alpha);
-    let mut rng = StdRng::seed_from_u64(seed);
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    let mut tally = DeviationTally::default();
+    for trial in 0..trials {
+        tally.record(setup.run(trial).as_ref());
+    }
+    tally.finish(trials)
+}
 
-    let mut baseline_total = 0.0;
-    let mut deviated_total = 0.0;
-    let mut allocation_changes = 0usize;
-    for _ in 0..trials {
-        let mut vals = Vec::with_capacity(buyers);
-        for _ in 0..buyers {
-            vals.push(dist.sample(&mut rng));
-        }
-        let top_real = vals.iter().cloned().fold(0.0_f64, f64::max);
-        let base_outcome = match &backend {
-            Backend::Sha(s) => {
-                let mut s = s.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &[], None, &mut s)
-            }
-            Backend::Pedersen(p) => {
-                let mut p = p.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &[], None, &mut p)
-            }
-            Backend::Audited(a) => {
-                let mut a = a.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &[], None, &mut a)
-            }
-            Backend::Fischlin(f) => {
-                let mut f = f.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &[], None, &mut f)
-            }
-            Backend::Bulletproofs(b) => {
-                let mut b = b.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &[], None, &mut b)
-            }
-        };
-        let false_bids = false_bids_from_model(&deviation, top_real);
-        let dev_outcome = match &backend {
-            Backend::Sha(s) => {
-                let mut s = s.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &false_bids, None, &mut s)
-            }
-            Backend::Pedersen(p) => {
-                let mut p = p.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &false_bids, None, &mut p)
-            }
-            Backend::Audited(a) => {
-                let mut a = a.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &false_bids, None, &mut a)
-            }
-            Backend::Fischlin(f) => {
-                let mut f = f.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &false_bids, None, &mut f)
-            }
-            Backend::Bulletproofs(b) => {
-                let mut b = b.clone();
-                dra.run_with_false_bids_using_scheme(&vals, &false_bids, None, &mut b)
-            }
-        };
+/// [`simulate_deviation_with_scheme`] for heterogeneous buyers: buyer `i` draws from `dists[i]`.
+/// The auction itself needs a single distribution for its reserve and collateral; it uses the
+/// equal-weight [`Mixture`] of `dists`, the value of a buyer picked at random from the pool.
+pub fn simulate_deviation_asymmetric(
+    dists: Vec<Box<dyn DynValueDistribution>>,
+    alpha: f64,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> SimulationResult {
+    let setup = asymmetric_setup(dists, alpha, deviation, seed, backend);
+    let mut tally = DeviationTally::default();
+    for trial in 0..trials {
+        tally.record(setup.run(trial).as_ref());
+    }
+    tally.finish(trials)
+}
 
-        baseline_total += auctioneer_revenue(&base_outcome);
-        deviated_total += auctioneer_revenue(&dev_outcome);
-        if dev_outcome.winner != base_outcome.winner {
-            allocation_changes += 1;
+fn asymmetric_setup(
+    dists: Vec<Box<dyn DynValueDistribution>>,
+    alpha: f64,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> TrialSetup<Box<dyn DynValueDistribution>> {
+    let reference: Box<dyn DynValueDistribution> = Box::new(Mixture::new(dists.clone()));
+    TrialSetup::with_buyer_dists(reference, dists, alpha, deviation, seed, backend)
+}
+
+/// Trials evaluated per parallel block, bounding how many outcomes are held at once.
+const PARALLEL_BLOCK: usize = 256;
+
+/// Parallel [`simulate_deviation_with_scheme`]. Every trial draws from its own seed and
+/// per-trial results are folded in trial order, so the result is bit-for-bit identical to the
+/// sequential run whatever the thread count.
+pub fn simulate_deviation_parallel<D: ValueDistribution + Clone + Send + Sync>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> SimulationResult {
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    let mut tally = DeviationTally::default();
+    for start in (0..trials).step_by(PARALLEL_BLOCK) {
+        let end = (start + PARALLEL_BLOCK).min(trials);
+        let outcomes: Vec<Option<TrialOutcome>> = (start..end)
+            .into_par_iter()
+            .map(|trial| setup.run(trial))
+            .collect();
+        for outcome in &outcomes {
+            tally.record(outcome.as_ref());
         }
     }
+    tally.finish(trials)
+}
 
-    let n = trials as f64;
-    SimulationResult {
-        baseline_revenue: baseline_total / n,
-        deviated_revenue: deviated_total / n,
-        allocation_change_rate: allocation_changes as f64 / n,
+/// Seed for sweep point `buyers`, derived from the base seed so points are independent.
+fn sweep_seed(seed: u64, buyers: usize) -> u64 {
+    trial_rng(seed, buyers).next_u64()
+}
+
+/// Run [`simulate_deviation_with_scheme`] once per buyer count in `buyer_range`, each point with
+/// its own seed derived from `seed`.
+pub fn simulate_sweep<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyer_range: RangeInclusive<usize>,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> Vec<(usize, SimulationResult)> {
+    let scheme: Box<dyn DynCommitmentScheme> = backend.into();
+    buyer_range
+        .map(|buyers| {
+            let result = simulate_deviation_with_scheme(
+                dist.clone(),
+                alpha,
+                buyers,
+                trials,
+                deviation.clone(),
+                sweep_seed(seed, buyers),
+                scheme.clone(),
+            );
+            (buyers, result)
+        })
+        .collect()
+}
+
+/// Parallel [`simulate_sweep`]: points run concurrently and match the sequential sweep exactly.
+pub fn simulate_sweep_parallel<D: ValueDistribution + Clone + Send + Sync>(
+    dist: D,
+    alpha: f64,
+    buyer_range: RangeInclusive<usize>,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> Vec<(usize, SimulationResult)> {
+    let scheme: Box<dyn DynCommitmentScheme> = backend.into();
+    buyer_range
+        .into_par_iter()
+        .map(|buyers| {
+            let result = simulate_deviation_with_scheme(
+                dist.clone(),
+                alpha,
+                buyers,
+                trials,
+                deviation.clone(),
+                sweep_seed(seed, buyers),
+                scheme.clone(),
+            );
+            (buyers, result)
+        })
+        .collect()
+}
+
+/// One trial of a deviation simulation, for downstream analysis of the raw outcomes.
+#[derive(Clone, Debug, Serialize)]
+pub struct TrialRecord {
+    pub trial: usize,
+    pub baseline_revenue: f64,
+    pub deviated_revenue: f64,
+    pub baseline_winner: Option<ParticipantId>,
+    pub deviated_winner: Option<ParticipantId>,
+    pub allocation_changed: bool,
+}
+
+/// Per-trial counterpart of [`simulate_deviation_with_scheme`]; trial `i` matches trial `i` of
+/// the aggregate run with the same arguments. Trials the aggregate skips, for a non-finite
+/// valuation or revenue, are omitted.
+pub fn simulate_deviation_records<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> Vec<TrialRecord> {
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    (0..trials).filter_map(|trial| setup.record(trial)).collect()
+}
+
+/// [`simulate_deviation_with_scheme`] that also returns the records of
+/// [`simulate_deviation_records`], built in the same pass over the trials.
+pub fn simulate_deviation_with_records<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> (SimulationResult, Vec<TrialRecord>) {
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    let mut tally = DeviationTally::default();
+    let mut records = Vec::with_capacity(trials);
+    for trial in 0..trials {
+        let outcome = setup.run(trial);
+        records.extend(outcome.as_ref().and_then(|o| o.record(trial)));
+        tally.record(outcome.as_ref());
     }
+    (tally.finish(trials), records)
 }
 
-/// Drive the full ProtocolSession with explicit time slots and report audit outcomes.
-pub fn simulate_timed_protocol<D: ValueDistribution + Clone>(
+/// [`simulate_deviation_with_scheme`] that also returns, for each trial, the deviated run's
+/// valid bids in descending order, keeping only the top `top_k`. Entry `i` belongs to trial
+/// `i`; a skipped trial has an empty list.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_deviation_with_order_stats<D: ValueDistribution + Clone>(
     dist: D,
     alpha: f64,
     buyers: usize,
     trials: usize,
     deviation: DeviationModel,
-    schedule: PhaseTimings,
     seed: u64,
-) -> TimedSimulationReport {
-    let mut rng = StdRng::seed_from_u64(seed);
-    let mut successes = 0usize;
-    let mut deadline_failures = 0usize;
-    let mut revenue_sum = 0.0;
-    for _ in 0..trials {
-        let per_trial_dra = PublicBroadcastDRA::new(dist.clone(), alpha);
-        let mut vals = Vec::with_capacity(buyers);
-        for _ in 0..buyers {
-            vals.push(dist.sample(&mut rng));
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+    top_k: usize,
+) -> (SimulationResult, Vec<Vec<f64>>) {
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    let mut tally = DeviationTally::default();
+    let mut order_stats = Vec::with_capacity(trials);
+    for trial in 0..trials {
+        let outcome = setup.run(trial).filter(|o| o.revenues().is_some());
+        let mut bids: Vec<f64> = outcome.as_ref().map_or_else(Vec::new, |o| {
+            o.deviated.valid_bids.iter().map(|(_, bid)| *bid).collect()
+        });
+        bids.sort_by(|a, b| b.total_cmp(a));
+        bids.truncate(top_k);
+        order_stats.push(bids);
+        tally.record(outcome.as_ref());
+    }
+    (tally.finish(trials), order_stats)
+}
+
+/// Lazily yield one [`TrialRecord`] per trial, seeded exactly as [`simulate_deviation_records`],
+/// so `deviation_trials(..).take(n)` reproduces a batch of `n` trials. The stream is unbounded;
+/// trials with a non-finite valuation or revenue are omitted.
+pub fn deviation_trials<'a, D: ValueDistribution + Clone + 'a>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+) -> impl Iterator<Item = TrialRecord> + 'a {
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    (0..).filter_map(move |trial| setup.record(trial))
+}
+
+/// Write records as CSV with a header row; winners use their `Debug` form, empty when unsold.
+pub fn write_records_csv<W: Write>(records: &[TrialRecord], mut writer: W) -> io::Result<()> {
+    let winner = |w: &Option<ParticipantId>| w.as_ref().map_or(String::new(), |id| id.to_string());
+    writeln!(
+        writer,
+        "trial,baseline_revenue,deviated_revenue,baseline_winner,deviated_winner,allocation_changed"
+    )?;
+    for r in records {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            r.trial,
+            r.baseline_revenue,
+            r.deviated_revenue,
+            winner(&r.baseline_winner),
+            winner(&r.deviated_winner),
+            r.allocation_changed
+        )?;
+    }
+    Ok(())
+}
+
+/// Write records as JSON Lines, one object per trial.
+pub fn write_records_jsonl<W: Write>(records: &[TrialRecord], mut writer: W) -> io::Result<()> {
+    for r in records {
+        serde_json::to_writer(&mut writer, r)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Per-trial auctioneer revenue binned on shared edges (`bins + 1` of them, ascending).
+#[derive(Clone, Debug, Serialize)]
+pub struct Histogram {
+    pub edges: Vec<f64>,
+    pub baseline_counts: Vec<usize>,
+    pub deviated_counts: Vec<usize>,
+}
+
+impl Histogram {
+    fn bin(&self, revenue: f64) -> usize {
+        let bins = self.baseline_counts.len();
+        let (low, high) = (self.edges[0], self.edges[bins]);
+        let idx = ((revenue - low) / (high - low) * bins as f64).floor();
+        (idx.max(0.0) as usize).min(bins - 1)
+    }
+}
+
+/// Histogram of baseline and deviated revenue across `trials`. The trials are replayed from
+/// `seed` twice (once for the range, once to count) so memory stays independent of `trials`.
+/// Trials the aggregate skips are left out of both passes.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_deviation_histogram<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    backend: impl Into<Box<dyn DynCommitmentScheme>>,
+    bins: usize,
+) -> Histogram {
+    assert!(bins > 0, "histogram needs at least one bin");
+    let setup = TrialSetup::new(dist, alpha, buyers, deviation, seed, backend);
+    let replay = |visit: &mut dyn FnMut(f64, f64)| {
+        setup.for_each(trials, |outcome| {
+            visit(
+                auctioneer_revenue(&outcome.baseline),
+                auctioneer_revenue(&outcome.deviated),
+            )
+        });
+    };
+
+    let (mut low, mut high) = (f64::INFINITY, f64::NEG_INFINITY);
+    replay(&mut |baseline, deviated| {
+        low = low.min(baseline).min(deviated);
+        high = high.max(baseline).max(deviated);
+    });
+    if !low.is_finite() {
+        (low, high) = (0.0, 0.0);
+    }
+    if high <= low {
+        high = low + 1.0;
+    }
+    let width = (high - low) / bins as f64;
+    let mut edges: Vec<f64> = (0..bins).map(|i| low + width * i as f64).collect();
+    edges.push(high);
+    let mut histogram = Histogram {
+        edges,
+        baseline_counts: vec![0; bins],
+        deviated_counts: vec![0; bins],
+    };
+    replay(&mut |baseline, deviated| {
+        let (b, d) = (histogram.bin(baseline), histogram.bin(deviated));
+        histogram.baseline_counts[b] += 1;
+        histogram.deviated_counts[d] += 1;
+    });
+    histogram
+}
+
+/// Fresh RNG for trial `trial`, seeded with `SHA-256(seed || trial)` (little-endian), so a
+/// trial's inputs depend only on the base seed and its index, not on how many trials run.
+fn trial_rng(seed: u64, trial: usize) -> StdRng {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update((trial as u64).to_le_bytes());
+    StdRng::from_seed(hasher.finalize().into())
+}
+
+/// One trial's sampled valuations with its baseline and deviated outcomes.
+struct TrialOutcome {
+    valuations: Vec<f64>,
+    baseline: AuctionOutcome,
+    deviated: AuctionOutcome,
+}
+
+impl TrialOutcome {
+    /// Baseline and deviated auctioneer revenue, or `None` if either is not finite. Every
+    /// aggregate, record and histogram skips such a trial.
+    fn revenues(&self) -> Option<(f64, f64)> {
+        let baseline = auctioneer_revenue(&self.baseline);
+        let deviated = auctioneer_revenue(&self.deviated);
+        (baseline.is_finite() && deviated.is_finite()).then_some((baseline, deviated))
+    }
+
+    fn record(&self, trial: usize) -> Option<TrialRecord> {
+        let (baseline_revenue, deviated_revenue) = self.revenues()?;
+        Some(TrialRecord {
+            trial,
+            baseline_revenue,
+            deviated_revenue,
+            allocation_changed: self.baseline.winner != self.deviated.winner,
+            baseline_winner: self.baseline.winner.clone(),
+            deviated_winner: self.deviated.winner.clone(),
+        })
+    }
+}
+
+/// Everything fixed across the trials of one deviation simulation.
+struct TrialSetup<D: ValueDistribution> {
+    dra: PublicBroadcastDRA<D>,
+    /// Each buyer's value distribution, sampled in buyer order.
+    buyer_dists: Vec<D>,
+    scheme: Box<dyn DynCommitmentScheme>,
+    deviation: DeviationModel,
+    seed: u64,
+}
+
+impl<D: ValueDistribution + Clone> TrialSetup<D> {
+    fn new(
+        dist: D,
+        alpha: f64,
+        buyers: usize,
+        deviation: DeviationModel,
+        seed: u64,
+        backend: impl Into<Box<dyn DynCommitmentScheme>>,
+    ) -> Self {
+        let buyer_dists = vec![dist.clone(); buyers];
+        Self::with_buyer_dists(dist, buyer_dists, alpha, deviation, seed, backend)
+    }
+
+    /// Buyers drawn from `buyer_dists`, while the auction's reserve and collateral come from
+    /// `reference`.
+    fn with_buyer_dists(
+        reference: D,
+        buyer_dists: Vec<D>,
+        alpha: f64,
+        deviation: DeviationModel,
+        seed: u64,
+        backend: impl Into<Box<dyn DynCommitmentScheme>>,
+    ) -> Self {
+        Self {
+            dra: PublicBroadcastDRA::new(reference, alpha),
+            buyer_dists,
+            scheme: backend.into(),
+            deviation,
+            seed,
         }
-        let top_real = vals.iter().cloned().fold(0.0_f64, f64::max);
-        let false_bids = false_bids_from_model(&deviation, top_real);
-        let collateral = per_trial_dra.collateral(buyers);
-        let participants = (0..buyers).map(ParticipantId::Real).collect();
-        let mut session = ProtocolSession::new(
-            per_trial_dra,
-            RealNonMalleableCommitment,
-            rng.next_u64(),
-            schedule.clone(),
-            participants,
+    }
+
+    /// Run trial `trial` without and then with the deviation; `None` if a sampled valuation is
+    /// not finite, since no commitment scheme can encode it.
+    fn run(&self, trial: usize) -> Option<TrialOutcome> {
+        let mut rng = trial_rng(self.seed, trial);
+        let vals: Vec<f64> = self
+            .buyer_dists
+            .iter()
+            .map(|dist| dist.sample(&mut rng))
+            .collect();
+        if vals.iter().any(|v| !v.is_finite()) {
+            return None;
+        }
+        let baseline =
+            self.dra
+                .run_with_false_bids_using_scheme(&vals, &[], None, &mut self.scheme.clone());
+        let false_bids = false_bids_from_model(&self.deviation, &vals);
+        let deviated = self.dra.run_with_false_bids_using_scheme(
+            &vals,
+            &false_bids,
+            None,
+            &mut self.scheme.clone(),
         );
-        let mut now = 0u64;
-        let mut failed = false;
-        for (idx, bid) in vals.iter().enumerate() {
-            if session.advance_to(now).is_err()
-                || session.commit_real(idx, *bid, collateral).is_err()
-            {
-                failed = true;
-                break;
+        Some(TrialOutcome {
+            valuations: vals,
+            baseline,
+            deviated,
+        })
+    }
+
+    fn record(&self, trial: usize) -> Option<TrialRecord> {
+        self.run(trial)?.record(trial)
+    }
+
+    /// Visit every trial that produced an outcome with finite revenues, in trial order.
+    fn for_each<F: FnMut(&TrialOutcome)>(&self, trials: usize, mut visit: F) {
+        for trial in 0..trials {
+            if let Some(outcome) = self.run(trial).filter(|o| o.revenues().is_some()) {
+                visit(&outcome);
             }
-            now += 1;
+        }
+    }
+}
+
+/// Welford's online mean/variance, so dispersion needs no second pass over the trials.
+#[derive(Clone, Copy, Debug, Default)]
+struct Welford {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Welford {
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Sample standard deviation; zero until there are two observations.
+    fn stddev(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            (self.m2 / (self.count - 1) as f64).sqrt()
+        }
+    }
+
+    fn ci95(&self) -> (f64, f64) {
+        let half = if self.count == 0 {
+            0.0
+        } else {
+            1.96 * self.stddev() / (self.count as f64).sqrt()
+        };
+        (self.mean - half, self.mean + half)
+    }
+}
+
+/// Running sums behind a [`SimulationResult`].
+#[derive(Default)]
+struct DeviationTally {
+    baseline_total: f64,
+    deviated_total: f64,
+    deviated_net_total: f64,
+    allocation_changes: usize,
+    change_kinds: AllocationChangeBreakdown,
+    winner_types: WinnerTally,
+    baseline_spread: Welford,
+    deviated_spread: Welford,
+    gain: Welford,
+    baseline_welfare: f64,
+    deviated_welfare: f64,
+    efficiency_losses: usize,
+    baseline_sales: usize,
+    deviated_sales: usize,
+    baseline_forfeit: f64,
+    deviated_forfeit: f64,
+    baseline_transfer: f64,
+    deviated_transfer: f64,
+    skipped: usize,
+}
+
+impl DeviationTally {
+    /// Fold one trial in; a missing trial or a non-finite revenue counts as skipped.
+    fn record(&mut self, trial: Option<&TrialOutcome>) {
+        let Some(trial) = trial else {
+            self.skipped += 1;
+            return;
+        };
+        let Some((baseline, deviated)) = trial.revenues() else {
+            self.skipped += 1;
+            return;
+        };
+        let (base_outcome, dev_outcome) = (&trial.baseline, &trial.deviated);
+        self.baseline_total += baseline;
+        self.deviated_total += deviated;
+        self.deviated_net_total += net_auctioneer_revenue(dev_outcome);
+        self.baseline_spread.push(baseline);
+        self.deviated_spread.push(deviated);
+        self.gain.push(deviated - baseline);
+        self.baseline_forfeit += base_outcome.forfeited_to_auctioneer;
+        self.deviated_forfeit += dev_outcome.forfeited_to_auctioneer;
+        self.baseline_transfer += base_outcome.transferred_collateral;
+        self.deviated_transfer += dev_outcome.transferred_collateral;
+        if dev_outcome.winner != base_outcome.winner {
+            self.allocation_changes += 1;
+        }
+        self.change_kinds.record(base_outcome, dev_outcome);
+        self.winner_types.record(dev_outcome);
+        let base_welfare = social_welfare(&trial.valuations, base_outcome);
+        let dev_welfare = social_welfare(&trial.valuations, dev_outcome);
+        self.baseline_welfare += base_welfare;
+        self.deviated_welfare += dev_welfare;
+        if dev_welfare < base_welfare {
+            self.efficiency_losses += 1;
+        }
+        self.baseline_sales += usize::from(base_outcome.winner.is_some());
+        self.deviated_sales += usize::from(dev_outcome.winner.is_some());
+    }
+
+    fn finish(self, trials: usize) -> SimulationResult {
+        // With every trial skipped the totals are all zero, so any positive divisor keeps the
+        // means at zero rather than NaN.
+        let n = trials.saturating_sub(self.skipped).max(1) as f64;
+        SimulationResult {
+            baseline_revenue: self.baseline_total / n,
+            deviated_revenue: self.deviated_total / n,
+            deviated_net_revenue: self.deviated_net_total / n,
+            allocation_change_rate: self.allocation_changes as f64 / n,
+            allocation_changes: self.change_kinds.scaled(n),
+            winner_types: self.winner_types,
+            baseline_stddev: self.baseline_spread.stddev(),
+            deviated_stddev: self.deviated_spread.stddev(),
+            revenue_gain_ci95: self.gain.ci95(),
+            baseline_welfare: self.baseline_welfare / n,
+            deviated_welfare: self.deviated_welfare / n,
+            efficiency_loss_rate: self.efficiency_losses as f64 / n,
+            baseline_sale_rate: self.baseline_sales as f64 / n,
+            deviated_sale_rate: self.deviated_sales as f64 / n,
+            baseline_forfeit_mean: self.baseline_forfeit / n,
+            deviated_forfeit_mean: self.deviated_forfeit / n,
+            baseline_transfer_mean: self.baseline_transfer / n,
+            deviated_transfer_mean: self.deviated_transfer / n,
+            skipped: self.skipped,
+        }
+    }
+}
+
+/// Misreports probed for regret, as multiples of the bidder's true value.
+const REGRET_PROBES: [f64; 4] = [0.0, 0.5, 1.5, 2.0];
+
+/// Ex-post utility gain to real bidder `bidder` from bidding `misreport` instead of its value,
+/// holding every other bid fixed. Utility is value minus payment when winning, zero otherwise.
+pub fn bidder_regret<D: ValueDistribution>(
+    dra: &PublicBroadcastDRA<D>,
+    valuations: &[f64],
+    false_bids: &[FalseBid],
+    bidder: usize,
+    misreport: f64,
+) -> f64 {
+    let utility = |bids: &[f64]| {
+        let outcome = dra.run_with_false_bids(bids, false_bids, Some(0));
+        match outcome.winner {
+            Some(ParticipantId::Real(i)) if i == bidder => valuations[bidder] - outcome.payment,
+            _ => 0.0,
+        }
+    };
+    let mut misreported = valuations.to_vec();
+    misreported[bidder] = misreport;
+    utility(&misreported) - utility(valuations)
+}
+
+/// Drive the full ProtocolSession with explicit time slots and report audit outcomes.
+///
+/// For each successful trial one bidder (rotating by trial index) is probed with misreports at
+/// fixed multiples of its value and just above the reserve; the worst gain is `max_regret`.
+pub fn simulate_timed_protocol<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    schedule: PhaseTimings,
+    seed: u64,
+) -> TimedSimulationReport {
+    simulate_timed_protocol_with_reveal_failures(
+        dist, alpha, buyers, trials, deviation, schedule, seed, 0.0,
+    )
+}
+
+/// [`simulate_timed_protocol`] with forgetful honest bidders: each real buyer independently
+/// misses its reveal with probability `reveal_failure_prob` and times out, forfeiting its
+/// collateral exactly as a deliberate withholding would.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_timed_protocol_with_reveal_failures<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    schedule: PhaseTimings,
+    seed: u64,
+    reveal_failure_prob: f64,
+) -> TimedSimulationReport {
+    assert!(
+        (0.0..=1.0).contains(&reveal_failure_prob),
+        "reveal_failure_prob must be in [0, 1]"
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut successes = 0usize;
+    let mut deadline_failures = 0usize;
+    let mut missed_reveals = 0usize;
+    let mut revenue_sum = 0.0;
+    let mut max_regret = 0.0_f64;
+    let regret_dra = PublicBroadcastDRA::new(dist.clone(), alpha);
+    let reserve = dist.reserve_price();
+    for trial in 0..trials {
+        let per_trial_dra = PublicBroadcastDRA::new(dist.clone(), alpha);
+        let mut vals = Vec::with_capacity(buyers);
+        for _ in 0..buyers {
+            vals.push(dist.sample(&mut rng));
+        }
+        let false_bids = false_bids_from_model(&deviation, &vals);
+        let collateral = per_trial_dra.collateral(buyers);
+        let participants = (0..buyers).map(ParticipantId::Real).collect();
+        let mut session = ProtocolSession::new(
+            per_trial_dra,
+            RealNonMalleableCommitment,
+            rng.next_u64(),
+            schedule.clone(),
+            participants,
+        );
+        let mut now = 0u64;
+        let mut failed = false;
+        for (idx, bid) in vals.iter().enumerate() {
+            if session.advance_to(now).is_err()
+                || session.commit_real(idx, *bid, collateral).is_err()
+            {
+                failed = true;
+                break;
+            }
+            now += 1;
         }
         if failed {
             deadline_failures += 1;
@@ -313,13 +1192,16 @@ pub fn simulate_timed_protocol<D: ValueDistribution + Clone>(
             continue;
         }
         now = schedule.commit_deadline;
-        for idx in 0..buyers {
-            if session.advance_to(now).is_err() || session.reveal(ParticipantId::Real(idx)).is_err()
-            {
+        // Draw only when forgetting is possible, so a zero probability leaves the stream intact.
+        let forgot: Vec<bool> = (0..buyers)
+            .map(|_| reveal_failure_prob > 0.0 && rng.gen_bool(reveal_failure_prob))
+            .collect();
+        missed_reveals += forgot.iter().filter(|&&f| f).count();
+        for idx in (0..buyers).filter(|&idx| !forgot[idx]) {
+            if session.reveal(ParticipantId::Real(idx)).is_err() {
                 failed = true;
                 break;
             }
-            now += 1;
         }
         if failed {
             deadline_failures += 1;
@@ -348,148 +1230,1008 @@ pub fn simulate_timed_protocol<D: ValueDistribution + Clone>(
             Ok((outcome, _, _)) => {
                 revenue_sum += auctioneer_revenue(&outcome);
                 successes += 1;
+                if buyers > 0 {
+                    let bidder = trial % buyers;
+                    let probes = REGRET_PROBES
+                        .iter()
+                        .map(|factor| factor * vals[bidder])
+                        .chain([reserve * (1.0 + 1e-6)]);
+                    for misreport in probes {
+                        let regret =
+                            bidder_regret(&regret_dra, &vals, &false_bids, bidder, misreport);
+                        max_regret = max_regret.max(regret);
+                    }
+                }
             }
             Err(_) => deadline_failures += 1,
         }
     }
-    TimedSimulationReport {
-        successful_runs: successes,
-        deadline_failures,
-        average_revenue: if successes > 0 {
-            revenue_sum / successes as f64
-        } else {
-            0.0
-        },
+    TimedSimulationReport {
+        successful_runs: successes,
+        deadline_failures,
+        average_revenue: if successes > 0 {
+            revenue_sum / successes as f64
+        } else {
+            0.0
+        },
+        max_regret,
+        missed_reveals,
+    }
+}
+
+/// Monte Carlo the two-buyer censorship attack: per trial, sample both buyers and compare the
+/// honest auction against [`adaptive_reserve_deviation`], in which the auctioneer withholds buyer
+/// A's commitment from buyer B and, once A's bid reaches `threshold`, shows B a shill just above it.
+pub fn simulate_censorship_deviation<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    trials: usize,
+    threshold: f64,
+    seed: u64,
+) -> CensorshipSimulationResult {
+    let (mut baseline_total, mut deviation_total) = (0.0, 0.0);
+    let mut helped = 0usize;
+    for trial in 0..trials {
+        let mut rng = trial_rng(seed, trial);
+        let buyer_a = dist.sample(&mut rng);
+        let buyer_b = dist.sample(&mut rng);
+        let report = adaptive_reserve_deviation(dist.clone(), alpha, buyer_a, buyer_b, threshold);
+        baseline_total += report.baseline_revenue;
+        deviation_total += report.deviation_revenue;
+        if report.deviation_revenue > report.baseline_revenue + 1e-9 {
+            helped += 1;
+        }
+    }
+    let n = trials.max(1) as f64;
+    CensorshipSimulationResult {
+        baseline_revenue: baseline_total / n,
+        deviation_revenue: deviation_total / n,
+        helped_rate: helped as f64 / n,
+    }
+}
+
+/// Empirically verify the Lemma 18/20 revenue bounds by comparing deviation revenue against the optimal baseline.
+///
+/// The mean gain is also checked against the analytic bound, with `exceeds_theory` flagging a
+/// deviation that beats it.
+pub fn simulate_safe_deviation_bound<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+) -> SafeDeviationStats {
+    let dra = PublicBroadcastDRA::new(dist.clone(), alpha);
+    let tally = safe_deviation_tally(&dra, &dist, buyers, trials, &deviation, seed, false);
+    let bound = crate::collateral::theoretical_safe_bound(&dist, alpha, buyers);
+    tally.into_stats(bound)
+}
+
+/// [`simulate_safe_deviation_bound`] for an auction posting `collateral` per commitment in
+/// place of the Theorem 21 amount, e.g. to under-collateralise it on purpose. Fails with
+/// [`ValidationError::InvalidCollateral`] if `collateral` is negative or not finite.
+pub fn simulate_safe_deviation_bound_with_collateral<D: ValueDistribution + Clone>(
+    dist: D,
+    alpha: f64,
+    buyers: usize,
+    trials: usize,
+    deviation: DeviationModel,
+    seed: u64,
+    collateral: f64,
+) -> Result<SafeDeviationStats, ValidationError> {
+    let dra = PublicBroadcastDRA::new(dist.clone(), alpha).with_collateral_override(collateral)?;
+    let tally = safe_deviation_tally(&dra, &dist, buyers, trials, &deviation, seed, false);
+    let bound = crate::collateral::safe_deviation_gain(buyers, &dist, collateral);
+    Ok(tally.into_stats(bound))
+}
+
+/// Per-trial results of a safe-deviation simulation, before the comparison with the analytic
+/// bound, which callers that only need the violation rate can skip.
+struct SafeDeviationTally {
+    trials: usize,
+    violations: usize,
+    max_violation: f64,
+    worst_case: Option<ViolationWitness>,
+    gain: Welford,
+}
+
+impl SafeDeviationTally {
+    fn violation_rate(&self) -> f64 {
+        self.violations as f64 / self.trials.max(1) as f64
+    }
+
+    fn into_stats(self, theoretical_bound: f64) -> SafeDeviationStats {
+        SafeDeviationStats {
+            satisfied: self.max_violation <= 1e-9,
+            max_violation: self.max_violation,
+            violation_rate: self.violation_rate(),
+            worst_case: self.worst_case,
+            theoretical_bound,
+            mean_gain: self.gain.mean,
+            exceeds_theory: self.gain.count > 1 && self.gain.ci95().0 > theoretical_bound,
+        }
+    }
+}
+
+/// Shared body of the safe-deviation simulations. With `net_of_posted`, a trial only counts
+/// as a violation when its revenue gain exceeds the collateral the false bids posted.
+fn safe_deviation_tally<D: ValueDistribution>(
+    dra: &PublicBroadcastDRA<D>,
+    dist: &D,
+    buyers: usize,
+    trials: usize,
+    deviation: &DeviationModel,
+    seed: u64,
+    net_of_posted: bool,
+) -> SafeDeviationTally {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut tally = SafeDeviationTally {
+        trials,
+        violations: 0,
+        max_violation: 0.0,
+        worst_case: None,
+        gain: Welford::default(),
+    };
+    for _ in 0..trials {
+        let mut vals = Vec::with_capacity(buyers);
+        for _ in 0..buyers {
+            vals.push(dist.sample(&mut rng));
+        }
+        let base_seed = rng.next_u64();
+        let dev_seed = rng.next_u64();
+        let baseline = dra.run_with_false_bids(&vals, &[], Some(base_seed));
+        let false_bids = false_bids_from_model(deviation, &vals);
+        let deviated = dra.run_with_false_bids(&vals, &false_bids, Some(dev_seed));
+        let base_rev = auctioneer_revenue(&baseline);
+        let dev_rev = auctioneer_revenue(&deviated);
+        let (mut forfeited, mut posted) = (0.0, 0.0);
+        for settlement in &deviated.settlements {
+            if settlement.participant.is_false() {
+                forfeited += settlement.posted_collateral - settlement.refund;
+                posted += settlement.posted_collateral;
+            }
+        }
+        tally.gain.push(dev_rev - base_rev - forfeited);
+        let tolerance = if net_of_posted { posted } else { 0.0 };
+        if dev_rev - base_rev > tolerance + 1e-9 {
+            tally.violations += 1;
+        }
+        if dev_rev > base_rev + 1e-9 && dev_rev - base_rev > tally.max_violation {
+            tally.max_violation = dev_rev - base_rev;
+            tally.worst_case = Some(ViolationWitness {
+                valuations: vals,
+                base_revenue: base_rev,
+                dev_revenue: dev_rev,
+                base_seed,
+                dev_seed,
+            });
+        }
+    }
+    tally
+}
+
+/// Bisection steps used by [`collateral_for_deterrence`].
+const DETERRENCE_BISECT_STEPS: usize = 40;
+
+/// Smallest collateral that deters withholding in at least a `p` fraction of sampled profiles.
+///
+/// Each candidate `c` is posted by the auction itself and checked with the safe-deviation
+/// simulation against an oracle false bid just under the top valuation, which bounds what any
+/// withheld false bid could extract; a trial counts as a violation when that extraction exceeds
+/// the `c` the false bid put at risk. Unlike [`collateral_requirement`], this accepts a `1 - p`
+/// rate of profitable deviations in exchange for a smaller deposit.
+///
+/// [`collateral_requirement`]: crate::collateral::collateral_requirement
+pub fn collateral_for_deterrence<D: ValueDistribution + Clone>(
+    n: usize,
+    dist: D,
+    alpha: f64,
+    p: f64,
+    trials: usize,
+    seed: u64,
+) -> f64 {
+    let allowed = (1.0 - p).max(0.0);
+    let shill: AdaptiveFalseBids = Arc::new(|vals: &[f64]| {
+        let top = vals.iter().copied().fold(0.0_f64, f64::max);
+        vec![FalseBid {
+            bid: top * (1.0 - 1e-9),
+            reveal: true,
+        }]
+    });
+    let deviation = DeviationModel::Adaptive(shill);
+    let rate = |collateral: f64| {
+        let dra = PublicBroadcastDRA::new(dist.clone(), alpha)
+            .with_collateral_override(collateral)
+            .expect("bisection only tries positive collateral");
+        safe_deviation_tally(&dra, &dist, n, trials, &deviation, seed, true).violation_rate()
+    };
+    let mut hi = crate::collateral::collateral_requirement(n, &dist, alpha).max(1e-9);
+    let mut doublings = 0;
+    while rate(hi) > allowed && doublings < 64 {
+        hi *= 2.0;
+        doublings += 1;
+    }
+    let mut lo = 0.0_f64;
+    if rate(lo) <= allowed {
+        return lo;
+    }
+    for _ in 0..DETERRENCE_BISECT_STEPS {
+        let mid = 0.5 * (lo + hi);
+        if rate(mid) <= allowed {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::{
+        AuditedNonMalleableCommitment, PedersenRistrettoCommitment, RealNonMalleableCommitment,
+    };
+    use crate::distribution::{EqualRevenue, Exponential, Pareto, Uniform};
+    use proptest::prelude::*;
+
+    #[test]
+    fn simulation_runs_and_returns_finite_values() {
+        let dist = Exponential::new(1.0);
+        let stats = simulate_false_bid_impact(
+            dist.clone(),
+            1.0,
+            3,
+            200,
+            FalseBid {
+                bid: 10.0,
+                reveal: false,
+            },
+            123,
+        );
+        assert!(stats.baseline.is_finite());
+        assert!(stats.deviated.is_finite());
+
+        let dev = simulate_deviation(
+            dist,
+            1.0,
+            3,
+            200,
+            DeviationModel::ThresholdReveal {
+                bid: 15.0,
+                reveal_if_top_at_least: 8.0,
+            },
+            456,
+        );
+        assert!(dev.allocation_change_rate >= 0.0);
+    }
+
+    #[test]
+    fn simulation_runs_with_pedersen_backend() {
+        let dist = Exponential::new(1.0);
+        let dev = simulate_deviation_with_scheme(
+            dist,
+            1.0,
+            2,
+            50,
+            DeviationModel::Fixed(FalseBid {
+                bid: 3.0,
+                reveal: true,
+            }),
+            999,
+            Backend::Pedersen(PedersenRistrettoCommitment),
+        );
+        assert!(dev.deviated_revenue.is_finite());
+    }
+
+    #[test]
+    fn simulation_runs_with_blake3_backend() {
+        let dist = Exponential::new(1.0);
+        let dev = simulate_deviation_with_scheme(
+            dist,
+            1.0,
+            2,
+            50,
+            DeviationModel::Fixed(FalseBid {
+                bid: 3.0,
+                reveal: true,
+            }),
+            818,
+            Backend::Blake3(Blake3Commitment),
+        );
+        assert!(dev.deviated_revenue.is_finite());
+    }
+
+    #[test]
+    fn simulation_runs_with_fischlin_backend() {
+        let dist = Exponential::new(1.0);
+        let dev = simulate_deviation_with_scheme(
+            dist,
+            1.0,
+            2,
+            50,
+            DeviationModel::Fixed(FalseBid {
+                bid: 3.0,
+                reveal: true,
+            }),
+            321,
+            Backend::Fischlin(RealNonMalleableCommitment),
+        );
+        assert!(dev.deviated_revenue.is_finite());
+    }
+
+    #[test]
+    fn simulation_runs_with_audited_backend() {
+        let dist = Exponential::new(1.0);
+        let dev = simulate_deviation_with_scheme(
+            dist,
+            1.0,
+            2,
+            1,
+            DeviationModel::Fixed(FalseBid {
+                bid: 3.0,
+                reveal: true,
+            }),
+            222,
+            Backend::Audited(AuditedNonMalleableCommitment::default()),
+        );
+        assert!(dev.deviated_revenue.is_finite());
+    }
+
+    #[test]
+    fn empirical_reserve_matters_only_for_few_buyers() {
+        let dist = Uniform::new(0.0, 10.0);
+        let candidates: Vec<f64> = (0..=10).map(f64::from).collect();
+        let single = empirical_optimal_reserve(dist.clone(), 1, 4000, &candidates, 320);
+        assert!((single - dist.reserve_price()).abs() <= 1.0, "{single}");
+
+        // With many buyers the second bid almost always clears any reserve up to the Myerson
+        // one, so the curve is flat there and the reserve barely moves revenue.
+        let curve = revenue_curve(dist, 20, 1000, &candidates, 320);
+        let best = curve.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert!(
+            curve[..=5].iter().all(|r| (best - r) / best < 1e-3),
+            "{curve:?}"
+        );
+    }
+
+    #[test]
+    fn asymmetric_buyers_sample_from_their_own_distributions() {
+        let dists: Vec<Box<dyn DynValueDistribution>> = vec![
+            Box::new(Exponential::new(1.0)),
+            Box::new(Uniform::new(5.0, 6.0)),
+        ];
+        let no_shill = DeviationModel::Multiple(Vec::new());
+        let setup = asymmetric_setup(
+            dists.clone(),
+            1.0,
+            no_shill.clone(),
+            319,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        let mut exp_total = 0.0;
+        for trial in 0..200 {
+            let vals = setup.run(trial).expect("finite values").valuations;
+            assert!(vals[0] >= 0.0);
+            assert!((5.0..=6.0).contains(&vals[1]), "{vals:?}");
+            exp_total += vals[0];
+        }
+        assert!((exp_total / 200.0 - 1.0).abs() < 0.25);
+
+        let result = simulate_deviation_asymmetric(
+            dists,
+            1.0,
+            200,
+            no_shill,
+            319,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        assert!(result.baseline_sale_rate > 0.9);
+        assert!(result.baseline_revenue.is_finite());
+    }
+
+    #[test]
+    fn early_trials_do_not_depend_on_trial_count() {
+        let setup = TrialSetup::new(
+            Exponential::new(1.0),
+            1.0,
+            3,
+            DeviationModel::Fixed(FalseBid {
+                bid: 2.0,
+                reveal: true,
+            }),
+            308,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        let first_ten = |trials| {
+            let mut revenues = Vec::new();
+            setup.for_each(trials, |outcome| {
+                if revenues.len() < 10 {
+                    revenues.push((
+                        auctioneer_revenue(&outcome.baseline),
+                        auctioneer_revenue(&outcome.deviated),
+                        outcome.deviated.winner.clone(),
+                    ));
+                }
+            });
+            revenues
+        };
+        assert_eq!(first_ten(10), first_ten(1000));
+        assert_ne!(first_ten(10)[0].0, first_ten(10)[1].0);
+    }
+
+    #[test]
+    fn records_match_aggregate_and_serialize_one_row_per_trial() {
+        let deviation = DeviationModel::Fixed(FalseBid {
+            bid: 3.0,
+            reveal: true,
+        });
+        let records = simulate_deviation_records(
+            Uniform::new(0.0, 10.0),
+            1.0,
+            3,
+            40,
+            deviation.clone(),
+            311,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        let aggregate = simulate_deviation(Uniform::new(0.0, 10.0), 1.0, 3, 40, deviation, 311);
+        let mean = records.iter().map(|r| r.deviated_revenue).sum::<f64>() / 40.0;
+        assert!((mean - aggregate.deviated_revenue).abs() < 1e-9);
+
+        let mut csv = Vec::new();
+        write_records_csv(&records, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 41);
+        for (line, record) in csv.lines().skip(1).zip(&records) {
+            let deviated_winner = line.split(',').nth(4).expect("winner column");
+            let parsed = (!deviated_winner.is_empty()).then(|| {
+                deviated_winner
+                    .parse::<ParticipantId>()
+                    .expect("winner parses")
+            });
+            assert_eq!(parsed, record.deviated_winner);
+        }
+        let mut jsonl = Vec::new();
+        write_records_jsonl(&records, &mut jsonl).unwrap();
+        assert_eq!(String::from_utf8(jsonl).unwrap().lines().count(), 40);
+    }
+
+    #[test]
+    fn payment_is_the_second_order_statistic_above_reserve() {
+        let dist = Uniform::new(0.0, 10.0);
+        let deviation = DeviationModel::Fixed(FalseBid {
+            bid: 0.0,
+            reveal: true,
+        });
+        let run = |top_k| {
+            simulate_deviation_with_order_stats(
+                dist.clone(),
+                1.0,
+                4,
+                60,
+                deviation.clone(),
+                17,
+                Backend::Sha(NonMalleableShaCommitment),
+                top_k,
+            )
+        };
+        let (result, order_stats) = run(2);
+        let records = simulate_deviation_records(
+            dist.clone(),
+            1.0,
+            4,
+            60,
+            deviation.clone(),
+            17,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        assert_eq!(order_stats.len(), 60);
+        let aggregate = simulate_deviation(dist.clone(), 1.0, 4, 60, deviation.clone(), 17);
+        assert_eq!(result.deviated_revenue, aggregate.deviated_revenue);
+
+        let mut checked = 0;
+        for (bids, record) in order_stats.iter().zip(&records) {
+            assert!(bids.len() <= 2);
+            if bids.len() == 2 && bids[1] >= dist.reserve_price() {
+                assert_eq!(record.deviated_revenue, bids[1]);
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+        assert!(run(10).1.iter().all(|bids| bids.len() == 5));
+    }
+
+    #[test]
+    fn sweep_returns_one_finite_point_per_buyer_count() {
+        let deviation = DeviationModel::Fixed(FalseBid {
+            bid: 1.0,
+            reveal: true,
+        });
+        let sweep = simulate_sweep(
+            Exponential::new(1.0),
+            1.0,
+            1..=4,
+            60,
+            deviation.clone(),
+            312,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        let counts: Vec<usize> = sweep.iter().map(|(buyers, _)| *buyers).collect();
+        assert_eq!(counts, vec![1, 2, 3, 4]);
+        for (_, result) in &sweep {
+            assert!(result.baseline_revenue.is_finite() && result.deviated_revenue.is_finite());
+        }
+        let parallel = simulate_sweep_parallel(
+            Exponential::new(1.0),
+            1.0,
+            1..=4,
+            60,
+            deviation,
+            312,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        for ((_, a), (_, b)) in sweep.iter().zip(&parallel) {
+            assert_eq!(a.deviated_revenue.to_bits(), b.deviated_revenue.to_bits());
+        }
+    }
+
+    #[test]
+    fn equal_revenue_heavy_tail_keeps_results_finite() {
+        let dist = EqualRevenue::new(1.0);
+        // Seed 833 draws a valuation near 4e5 for buyer 0 in trial 39.
+        assert!(dist.sample(&mut trial_rng(833, 39)) > 1e5);
+        let result = simulate_deviation(
+            dist,
+            0.5,
+            2,
+            50,
+            DeviationModel::Fixed(FalseBid {
+                bid: 2.0,
+                reveal: true,
+            }),
+            833,
+        );
+        assert_eq!(result.skipped, 0);
+        for value in [
+            result.baseline_revenue,
+            result.deviated_revenue,
+            result.baseline_stddev,
+            result.deviated_welfare,
+        ] {
+            assert!(value.is_finite());
+        }
+    }
+
+    /// Emits an infinite valuation on roughly one draw in five.
+    #[derive(Clone)]
+    struct Spiky;
+
+    impl ValueDistribution for Spiky {
+        fn cdf(&self, x: f64) -> f64 {
+            x.clamp(0.0, 1.0)
+        }
+
+        fn pdf(&self, x: f64) -> f64 {
+            if (0.0..=1.0).contains(&x) { 1.0 } else { 0.0 }
+        }
+
+        fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+            let u: f64 = rng.gen_range(0.0..1.0);
+            if u < 0.2 { f64::INFINITY } else { u }
+        }
+    }
+
+    #[test]
+    fn non_finite_valuations_are_skipped_not_averaged() {
+        let result = simulate_deviation(
+            Spiky,
+            1.0,
+            1,
+            200,
+            DeviationModel::Multiple(Vec::new()),
+            318,
+        );
+        assert!(result.skipped > 0 && result.skipped < 200);
+        assert!(result.baseline_revenue.is_finite());
+        let records = simulate_deviation_records(
+            Spiky,
+            1.0,
+            1,
+            200,
+            DeviationModel::Multiple(Vec::new()),
+            318,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        assert_eq!(records.len(), 200 - result.skipped);
+    }
+
+    #[test]
+    fn non_finite_revenue_is_skipped_consistently() {
+        let setup = TrialSetup::new(
+            Exponential::new(1.0),
+            1.0,
+            2,
+            DeviationModel::Multiple(Vec::new()),
+            318,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        let mut outcome = setup.run(0).expect("finite valuations");
+        assert!(outcome.revenues().is_some());
+        outcome.deviated.payment = f64::INFINITY;
+        outcome.deviated.winner = Some(ParticipantId::Real(0));
+        assert!(outcome.revenues().is_none());
+        let mut tally = DeviationTally::default();
+        tally.record(Some(&outcome));
+        assert_eq!(tally.finish(1).skipped, 1);
+
+        // Every consumer counts the same trials: the histogram bins exactly the recorded ones.
+        let records = simulate_deviation_records(
+            Spiky,
+            1.0,
+            1,
+            200,
+            DeviationModel::Multiple(Vec::new()),
+            318,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        let histogram = simulate_deviation_histogram(
+            Spiky,
+            1.0,
+            1,
+            200,
+            DeviationModel::Multiple(Vec::new()),
+            318,
+            Backend::Sha(NonMalleableShaCommitment),
+            8,
+        );
+        assert!(histogram.edges.iter().all(|e| e.is_finite()));
+        assert_eq!(
+            histogram.baseline_counts.iter().sum::<usize>(),
+            records.len()
+        );
+    }
+
+    #[test]
+    fn single_pass_records_match_separate_runs() {
+        let deviation = DeviationModel::Multiple(Vec::new());
+        let backend = || Backend::Sha(NonMalleableShaCommitment);
+        let (result, records) =
+            simulate_deviation_with_records(Spiky, 1.0, 2, 120, deviation.clone(), 311, backend());
+        let separate_result =
+            simulate_deviation_with_scheme(Spiky, 1.0, 2, 120, deviation.clone(), 311, backend());
+        let separate_records =
+            simulate_deviation_records(Spiky, 1.0, 2, 120, deviation, 311, backend());
+        assert_eq!(
+            serde_json::to_value(&result).expect("serialize"),
+            serde_json::to_value(&separate_result).expect("serialize")
+        );
+        assert_eq!(
+            serde_json::to_value(&records).expect("serialize"),
+            serde_json::to_value(&separate_records).expect("serialize")
+        );
+        assert_eq!(records.len(), 120 - result.skipped);
     }
-}
 
-/// Empirically verify the Lemma 18/20 revenue bounds by comparing deviation revenue against the optimal baseline.
-pub fn simulate_safe_deviation_bound<D: ValueDistribution + Clone>(
-    dist: D,
-    alpha: f64,
-    buyers: usize,
-    trials: usize,
-    deviation: DeviationModel,
-    seed: u64,
-) -> SafeDeviationStats {
-    let dra = PublicBroadcastDRA::new(dist.clone(), alpha);
-    let mut rng = StdRng::seed_from_u64(seed);
-    let mut max_violation = 0.0_f64;
-    for _ in 0..trials {
-        let mut vals = Vec::with_capacity(buyers);
-        for _ in 0..buyers {
-            vals.push(dist.sample(&mut rng));
-        }
-        let base_seed = rng.next_u64();
-        let dev_seed = rng.next_u64();
-        let baseline = dra.run_with_false_bids(&vals, &[], Some(base_seed));
-        let top_real = vals.iter().cloned().fold(0.0_f64, f64::max);
-        let false_bids = false_bids_from_model(&deviation, top_real);
-        let deviated = dra.run_with_false_bids(&vals, &false_bids, Some(dev_seed));
-        let base_rev = auctioneer_revenue(&baseline);
-        let dev_rev = auctioneer_revenue(&deviated);
-        if dev_rev > base_rev + 1e-9 {
-            max_violation = max_violation.max(dev_rev - base_rev);
+    #[test]
+    fn streamed_trials_match_batch_records() {
+        let deviation = DeviationModel::ThresholdReveal {
+            bid: 1.2,
+            reveal_if_top_at_least: 1.0,
+        };
+        let batch = simulate_deviation_records(
+            Exponential::new(1.0),
+            1.0,
+            3,
+            80,
+            deviation.clone(),
+            319,
+            Backend::Blake3(Blake3Commitment),
+        );
+        let streamed: Vec<TrialRecord> = deviation_trials(
+            Exponential::new(1.0),
+            1.0,
+            3,
+            deviation,
+            319,
+            Backend::Blake3(Blake3Commitment),
+        )
+        .take(50)
+        .collect();
+        assert_eq!(streamed.len(), 50);
+        for (a, b) in streamed.iter().zip(&batch) {
+            assert_eq!(a.trial, b.trial);
+            assert_eq!(a.baseline_revenue.to_bits(), b.baseline_revenue.to_bits());
+            assert_eq!(a.deviated_revenue.to_bits(), b.deviated_revenue.to_bits());
+            assert_eq!(a.deviated_winner, b.deviated_winner);
         }
     }
-    SafeDeviationStats {
-        satisfied: max_violation <= 1e-9,
-        max_violation,
+
+    #[test]
+    fn histogram_counts_every_trial_once() {
+        let histogram = simulate_deviation_histogram(
+            Exponential::new(1.0),
+            1.0,
+            3,
+            250,
+            DeviationModel::Fixed(FalseBid {
+                bid: 2.0,
+                reveal: true,
+            }),
+            307,
+            Backend::Sha(NonMalleableShaCommitment),
+            12,
+        );
+        assert_eq!(histogram.edges.len(), 13);
+        assert!(histogram.edges.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(histogram.baseline_counts.iter().sum::<usize>(), 250);
+        assert_eq!(histogram.deviated_counts.iter().sum::<usize>(), 250);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::commitment::{
-        AuditedNonMalleableCommitment, PedersenRistrettoCommitment, RealNonMalleableCommitment,
-    };
-    use crate::distribution::{EqualRevenue, Exponential, Pareto, Uniform};
-    use proptest::prelude::*;
+    #[test]
+    fn gain_confidence_interval_shrinks_and_covers_the_mean() {
+        let deviation = DeviationModel::Fixed(FalseBid {
+            bid: 7.0,
+            reveal: true,
+        });
+        let run = |trials| {
+            simulate_deviation_parallel(
+                Uniform::new(0.0, 10.0),
+                1.0,
+                2,
+                trials,
+                deviation.clone(),
+                306,
+                Backend::Sha(NonMalleableShaCommitment),
+            )
+        };
+        let small = run(100);
+        let large = run(4000);
+        let width = |r: &SimulationResult| r.revenue_gain_ci95.1 - r.revenue_gain_ci95.0;
+        assert!(width(&large) < width(&small) / 4.0);
+        let gain = large.deviated_revenue - large.baseline_revenue;
+        assert!(large.revenue_gain_ci95.0 <= gain && gain <= large.revenue_gain_ci95.1);
+        assert!(large.baseline_stddev > 0.0 && large.deviated_stddev > 0.0);
+    }
 
     #[test]
-    fn simulation_runs_and_returns_finite_values() {
-        let dist = Exponential::new(1.0);
-        let stats = simulate_false_bid_impact(
-            dist.clone(),
+    fn parallel_simulation_matches_sequential_bit_for_bit() {
+        let deviation = DeviationModel::ThresholdReveal {
+            bid: 6.0,
+            reveal_if_top_at_least: 5.0,
+        };
+        let run_sequential = simulate_deviation_with_scheme(
+            Uniform::new(0.0, 10.0),
             1.0,
             3,
-            200,
-            FalseBid {
-                bid: 10.0,
-                reveal: false,
-            },
-            123,
+            300,
+            deviation.clone(),
+            305,
+            Backend::Sha(NonMalleableShaCommitment),
         );
-        assert!(stats.baseline.is_finite());
-        assert!(stats.deviated.is_finite());
-
-        let dev = simulate_deviation(
-            dist,
+        let run_parallel = simulate_deviation_parallel(
+            Uniform::new(0.0, 10.0),
             1.0,
             3,
-            200,
-            DeviationModel::ThresholdReveal {
-                bid: 15.0,
-                reveal_if_top_at_least: 8.0,
-            },
-            456,
+            300,
+            deviation,
+            305,
+            Backend::Sha(NonMalleableShaCommitment),
+        );
+        assert_eq!(
+            run_sequential.baseline_revenue.to_bits(),
+            run_parallel.baseline_revenue.to_bits()
+        );
+        assert_eq!(
+            run_sequential.deviated_revenue.to_bits(),
+            run_parallel.deviated_revenue.to_bits()
+        );
+        assert_eq!(
+            run_sequential.allocation_change_rate.to_bits(),
+            run_parallel.allocation_change_rate.to_bits()
+        );
+        assert_eq!(
+            run_sequential.winner_types.false_wins,
+            run_parallel.winner_types.false_wins
+        );
+        assert_eq!(
+            run_sequential.winner_types.no_sale,
+            run_parallel.winner_types.no_sale
         );
-        assert!(dev.allocation_change_rate >= 0.0);
     }
 
     #[test]
-    fn simulation_runs_with_pedersen_backend() {
-        let dist = Exponential::new(1.0);
-        let dev = simulate_deviation_with_scheme(
+    fn winner_tally_separates_shill_captures_from_price_extraction() {
+        let dist = Uniform::new(0.0, 10.0);
+        let large = simulate_deviation(
+            dist.clone(),
+            1.0,
+            2,
+            100,
+            DeviationModel::Fixed(FalseBid {
+                bid: 50.0,
+                reveal: true,
+            }),
+            77,
+        );
+        assert!(large.winner_types.false_wins > 0);
+        let tiny = simulate_deviation(
             dist,
             1.0,
             2,
-            50,
+            100,
             DeviationModel::Fixed(FalseBid {
-                bid: 3.0,
+                bid: 0.01,
                 reveal: true,
             }),
-            999,
-            Backend::Pedersen(PedersenRistrettoCommitment),
+            77,
         );
-        assert!(dev.deviated_revenue.is_finite());
+        assert_eq!(tiny.winner_types.false_wins, 0);
+        let tally = &tiny.winner_types;
+        assert_eq!(tally.real_wins + tally.false_wins + tally.no_sale, 100);
     }
 
     #[test]
-    fn simulation_runs_with_fischlin_backend() {
-        let dist = Exponential::new(1.0);
-        let dev = simulate_deviation_with_scheme(
-            dist,
+    fn unpaid_winning_false_bid_destroys_welfare() {
+        let result = simulate_deviation(
+            Uniform::new(0.0, 10.0),
             1.0,
-            2,
-            50,
+            3,
+            200,
             DeviationModel::Fixed(FalseBid {
-                bid: 3.0,
+                bid: 50.0,
                 reveal: true,
             }),
-            321,
-            Backend::Fischlin(RealNonMalleableCommitment),
+            310,
         );
-        assert!(dev.deviated_revenue.is_finite());
+        assert!(result.baseline_welfare > 0.0);
+        assert_eq!(result.deviated_welfare, 0.0);
+        assert!(result.efficiency_loss_rate > 0.5);
     }
 
     #[test]
-    fn simulation_runs_with_audited_backend() {
-        let dist = Exponential::new(1.0);
-        let dev = simulate_deviation_with_scheme(
-            dist,
+    fn net_revenue_drops_a_winning_false_bids_payment() {
+        let result = simulate_deviation(
+            Uniform::new(0.0, 10.0),
             1.0,
-            2,
-            1,
+            3,
+            200,
             DeviationModel::Fixed(FalseBid {
-                bid: 3.0,
+                bid: 50.0,
                 reveal: true,
             }),
-            222,
-            Backend::Audited(AuditedNonMalleableCommitment::default()),
+            309,
         );
-        assert!(dev.deviated_revenue.is_finite());
+        // The shill wins every trial and pays the top real bid to the auctioneer itself.
+        assert!(result.deviated_revenue > result.baseline_revenue);
+        assert!(result.deviated_net_revenue.abs() < 1e-9);
+    }
+
+    #[test]
+    fn winning_false_bid_counts_as_a_sale() {
+        let shill = |reveal| {
+            simulate_deviation(
+                Uniform::new(0.0, 10.0),
+                1.0,
+                3,
+                200,
+                DeviationModel::Fixed(FalseBid { bid: 50.0, reveal }),
+                311,
+            )
+        };
+        // Some baseline trials have every buyer below the reserve of 5; the shill always wins.
+        let revealed = shill(true);
+        assert!(revealed.baseline_sale_rate > 0.5 && revealed.baseline_sale_rate < 1.0);
+        assert_eq!(revealed.deviated_sale_rate, 1.0);
+        assert_eq!(revealed.winner_types.false_wins, 200);
+        // Withheld, the false bid is invalid and the real buyers trade as before.
+        let withheld = shill(false);
+        assert_eq!(withheld.deviated_sale_rate, withheld.baseline_sale_rate);
+    }
+
+    #[test]
+    fn combined_model_layers_sub_models() {
+        let model = DeviationModel::Combined(vec![
+            DeviationModel::Fixed(FalseBid {
+                bid: 20.0,
+                reveal: false,
+            }),
+            DeviationModel::ThresholdReveal {
+                bid: 9.0,
+                reveal_if_top_at_least: 5.0,
+            },
+        ]);
+        let high = false_bids_from_model(&model, &[6.0]);
+        assert_eq!(high.len(), 2);
+        assert_eq!(high[0].bid, 20.0);
+        assert!(!high[0].reveal);
+        assert_eq!(high[1].bid, 9.0);
+        assert!(high[1].reveal);
+        let low = false_bids_from_model(&model, &[4.0]);
+        assert_eq!(low.len(), 2);
+        assert!(!low[0].reveal);
+        assert!(!low[1].reveal);
+    }
+
+    #[test]
+    fn adaptive_model_reacts_to_realised_valuations() {
+        let reserve = Exponential::new(1.0).reserve_price();
+        let model = DeviationModel::Adaptive(Arc::new(move |vals: &[f64]| {
+            if vals.iter().filter(|v| **v > reserve).count() == 2 {
+                vec![FalseBid {
+                    bid: 3.0,
+                    reveal: true,
+                }]
+            } else {
+                Vec::new()
+            }
+        }));
+        assert_eq!(false_bids_from_model(&model, &[2.0, 1.5, 0.2]).len(), 1);
+        assert!(false_bids_from_model(&model, &[2.0, 0.1, 0.2]).is_empty());
+        assert!(false_bids_from_model(&model, &[2.0, 1.5, 1.2]).is_empty());
+
+        let schedule = PhaseTimings {
+            commit_deadline: 6,
+            reveal_deadline: 12,
+        };
+        let report = simulate_timed_protocol(
+            Exponential::new(1.0),
+            1.0,
+            3,
+            20,
+            model,
+            schedule.clone(),
+            316,
+        );
+        assert_eq!(report.successful_runs, 20);
+        // Same seed, so the valuations match; only the adaptive shill bids differ.
+        let honest = simulate_timed_protocol(
+            Exponential::new(1.0),
+            1.0,
+            3,
+            20,
+            DeviationModel::Multiple(Vec::new()),
+            schedule,
+            316,
+        );
+        assert_eq!(honest.successful_runs, 20);
+        assert!(
+            (report.average_revenue - honest.average_revenue).abs() > 1e-9,
+            "adaptive shill bids left revenue unchanged at {}",
+            honest.average_revenue
+        );
+    }
+
+    #[test]
+    fn best_revealed_false_bid_does_not_beat_baseline_for_exponential() {
+        let dist = Exponential::new(1.0);
+        let (buyers, trials, seed) = (3, 150, 309);
+        let (bid, deviated) = optimize_false_bid(dist.clone(), 1.0, buyers, trials, seed);
+        let baseline = simulate_false_bid_impact(
+            dist,
+            1.0,
+            buyers,
+            trials,
+            FalseBid { bid, reveal: true },
+            seed,
+        )
+        .baseline;
+        assert!(bid.is_finite() && bid >= 0.0);
+        assert!(
+            deviated <= baseline * 1.05,
+            "optimal shill bid {bid} raised revenue {baseline} -> {deviated}"
+        );
+    }
+
+    #[test]
+    fn censorship_raises_revenue_on_exponential() {
+        let result = simulate_censorship_deviation(Exponential::new(0.01), 1.0, 2000, 120.0, 317);
+        assert!(
+            result.deviation_revenue > result.baseline_revenue,
+            "censorship should pay off: {result:?}"
+        );
+        assert!(result.helped_rate > 0.0 && result.helped_rate < 1.0);
     }
 
     #[test]
@@ -512,6 +2254,44 @@ mod tests {
             2024,
         );
         assert!(report.successful_runs + report.deadline_failures > 0);
+        assert!(report.max_regret <= 1e-9, "regret {}", report.max_regret);
+    }
+
+    #[test]
+    fn forgetful_bidders_all_time_out() {
+        let dist = Uniform::new(0.0, 10.0);
+        let schedule = PhaseTimings {
+            commit_deadline: 4,
+            reveal_deadline: 10,
+        };
+        let report = simulate_timed_protocol_with_reveal_failures(
+            dist.clone(),
+            1.0,
+            3,
+            5,
+            DeviationModel::Multiple(Vec::new()),
+            schedule,
+            318,
+            1.0,
+        );
+        assert_eq!(report.successful_runs, 5);
+        assert_eq!(report.missed_reveals, 15);
+        // No bid opens, so every run forfeits all three collaterals to the auctioneer.
+        let collateral = PublicBroadcastDRA::new(dist, 1.0).collateral(3);
+        assert!((report.average_revenue - 3.0 * collateral).abs() < 1e-9);
+    }
+
+    #[test]
+    fn overbidding_a_losing_bidder_to_the_reserve_never_helps() {
+        let dist = Uniform::new(0.0, 10.0);
+        let dra = PublicBroadcastDRA::new(dist.clone(), 1.0);
+        let just_above = dist.reserve_price() + 0.01;
+        // Bidder 1 loses below the reserve; overbidding still loses to bidder 0.
+        let regret = bidder_regret(&dra, &[8.0, 3.0], &[], 1, just_above);
+        assert!(regret <= 0.0);
+        // Alone, the same overbid wins but pays more than the bidder's value.
+        let regret = bidder_regret(&dra, &[3.0], &[], 0, just_above);
+        assert!(regret < 0.0, "overbid should cost the bidder, got {regret}");
     }
 
     #[test]
@@ -535,6 +2315,137 @@ mod tests {
             "violation observed: {}",
             stats.max_violation
         );
+        assert!(stats.worst_case.is_none());
+    }
+
+    #[test]
+    fn deterrence_collateral_is_the_smallest_meeting_the_target() {
+        let dist = Exponential::new(1.0);
+        let closed = crate::collateral::collateral_requirement(3, &dist, 1.0);
+        let shill: AdaptiveFalseBids = Arc::new(|vals: &[f64]| {
+            let top = vals.iter().copied().fold(0.0_f64, f64::max);
+            vec![FalseBid {
+                bid: top * (1.0 - 1e-9),
+                reveal: true,
+            }]
+        });
+        let rate = |collateral: f64| {
+            let dra = PublicBroadcastDRA::new(dist.clone(), 1.0)
+                .with_collateral_override(collateral)
+                .expect("valid collateral");
+            let deviation = DeviationModel::Adaptive(shill.clone());
+            safe_deviation_tally(&dra, &dist, 3, 300, &deviation, 324, true).violation_rate()
+        };
+        let mut previous = 0.0;
+        for p in [0.5, 0.9, 1.0] {
+            let c = collateral_for_deterrence(3, dist.clone(), 1.0, p, 300, 324);
+            assert!(c >= previous, "p {p}: {c} < {previous}");
+            assert!(rate(c) <= 1.0 - p + 1e-12, "p {p}: {c} misses the target");
+            assert!(rate(c * 0.99) > 1.0 - p, "p {p}: {c} is not minimal");
+            previous = c;
+        }
+        assert!(previous >= closed, "full {previous} < closed form {closed}");
+    }
+
+    #[test]
+    fn withheld_false_bid_collateral_is_transferred_not_forfeited() {
+        let result = simulate_deviation(
+            Exponential::new(1.0),
+            1.0,
+            1,
+            400,
+            DeviationModel::Fixed(FalseBid {
+                bid: 2.0,
+                reveal: false,
+            }),
+            335,
+        );
+        // A real buyer always reveals, so the withheld collateral goes to the highest valid
+        // bidder rather than to the auctioneer.
+        let collateral = PublicBroadcastDRA::new(Exponential::new(1.0), 1.0).collateral(1);
+        assert_eq!(result.baseline_transfer_mean, 0.0);
+        assert!((result.deviated_transfer_mean - collateral).abs() < 1e-9);
+        assert_eq!(result.deviated_forfeit_mean, 0.0);
+    }
+
+    #[test]
+    fn high_revealed_false_bid_takes_over_the_sales() {
+        let deviation = |reveal| DeviationModel::Fixed(FalseBid { bid: 50.0, reveal });
+        let revealed = simulate_deviation(Exponential::new(1.0), 1.0, 3, 300, deviation(true), 336);
+        let changes = &revealed.allocation_changes;
+        assert!(changes.false_takeover > 0.8);
+        assert_eq!(
+            changes.created_sale + changes.suppressed_sale + changes.real_swap,
+            0.0
+        );
+        assert!((changes.false_takeover - revealed.allocation_change_rate).abs() < 1e-12);
+
+        // Withheld, the same bid never enters the valid set and leaves every sale alone.
+        let withheld =
+            simulate_deviation(Exponential::new(1.0), 1.0, 3, 300, deviation(false), 336);
+        assert_eq!(withheld.allocation_change_rate, 0.0);
+        assert_eq!(withheld.allocation_changes.false_takeover, 0.0);
+    }
+
+    #[test]
+    fn violation_witness_replays_to_the_reported_revenues() {
+        // Post a token deposit instead of the Theorem 21 collateral, so withheld shills are
+        // nearly free and the deviation pays on average.
+        let dist = Uniform::new(0.0, 10.0);
+        let collateral = 0.01;
+        let dra = PublicBroadcastDRA::new(dist.clone(), 1.0)
+            .with_collateral_override(collateral)
+            .expect("valid collateral");
+        let deviation = DeviationModel::ThresholdReveal {
+            bid: 9.0,
+            reveal_if_top_at_least: 9.0,
+        };
+        let stats = simulate_safe_deviation_bound_with_collateral(
+            dist,
+            1.0,
+            2,
+            200,
+            deviation.clone(),
+            315,
+            collateral,
+        )
+        .expect("valid collateral");
+        assert!(!stats.satisfied);
+        assert!(
+            stats.mean_gain > 0.0,
+            "under-collateralised shill should pay"
+        );
+        let witness = stats.worst_case.expect("violation should carry a witness");
+        assert_eq!(
+            witness.dev_revenue - witness.base_revenue,
+            stats.max_violation
+        );
+
+        let false_bids = false_bids_from_model(&deviation, &witness.valuations);
+        let base = dra.run_with_false_bids(&witness.valuations, &[], Some(witness.base_seed));
+        let dev = dra.run_with_false_bids(&witness.valuations, &false_bids, Some(witness.dev_seed));
+        assert_eq!(auctioneer_revenue(&base), witness.base_revenue);
+        assert_eq!(auctioneer_revenue(&dev), witness.dev_revenue);
+    }
+
+    #[test]
+    fn threshold_reveal_stays_within_the_theoretical_bound() {
+        let stats = simulate_safe_deviation_bound(
+            Uniform::new(0.0, 10.0),
+            1.0,
+            3,
+            2000,
+            DeviationModel::ThresholdReveal {
+                bid: 8.0,
+                reveal_if_top_at_least: 8.0,
+            },
+            316,
+        );
+        // Ex post the shill raises some prices, but the withheld collateral outweighs it.
+        assert!(!stats.satisfied);
+        assert!(stats.theoretical_bound <= 0.0);
+        assert!(stats.mean_gain < 0.0);
+        assert!(!stats.exceeds_theory);
     }
 
     /// Theorem 25 counterexample: single-buyer equal-revenue distribution admits a profitable
@@ -682,4 +2593,11 @@ mod tests {
             analytic
         );
     }
+
+    #[test]
+    fn vcg_winner_pays_the_second_highest_value() {
+        assert_eq!(vcg_outcome(&[5.0, 3.0, 8.0]), (Some(2), 5.0));
+        assert_eq!(vcg_outcome(&[4.0]), (Some(0), 0.0));
+        assert_eq!(vcg_outcome(&[]), (None, 0.0));
+    }
 }